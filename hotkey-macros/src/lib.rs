@@ -3,28 +3,99 @@ use quote::quote;
 use syn::Token;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{ExprTuple, ItemFn, parse_macro_input, punctuated::Punctuated};
+use syn::{Expr, ExprTuple, ItemFn, LitStr, parse_macro_input, punctuated::Punctuated};
 
-// 定义热键参数结构 - 支持单个热键或多个热键
+// 与 `hotkey` 共用同一份字符串语法
+#[path = "../../hotkey/src/parse.rs"]
+mod parse;
+
+use parse::ParseError;
+
+// 完整的属性参数：热键及可选的 `scope = "..."`、`category = "..."`、`trigger = Trigger::...`
+struct HotkeyAttr {
+    hotkeys: HotkeyArgs,
+    scope: Option<LitStr>,
+    category: Option<LitStr>,
+    trigger: Option<Expr>,
+}
+
+impl Parse for HotkeyAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let hotkeys = input.parse()?;
+
+        let mut scope = None;
+        let mut category = None;
+        let mut trigger = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: syn::Ident = input.parse()?;
+            if name == "trigger" {
+                if trigger.is_some() {
+                    return Err(syn::Error::new(name.span(), "重复的参数"));
+                }
+                input.parse::<Token![=]>()?;
+                trigger = Some(input.parse()?);
+                continue;
+            }
+            let value = if name == "scope" {
+                &mut scope
+            } else if name == "category" {
+                &mut category
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "未知参数，只支持 scope = \"...\"、category = \"...\"、trigger = ...",
+                ));
+            };
+            if value.is_some() {
+                return Err(syn::Error::new(name.span(), "重复的参数"));
+            }
+            input.parse::<Token![=]>()?;
+            *value = Some(input.parse()?);
+        }
+
+        Ok(HotkeyAttr {
+            hotkeys,
+            scope,
+            category,
+            trigger,
+        })
+    }
+}
+
+// 定义热键参数结构 - 支持单个热键或多个热键，多个热键中的数组表示依次按下的序列，
+// 也支持 `"ctrl+shift+tab"` 形式的字符串，空格分隔的字符串表示序列
 enum HotkeyArgs {
+    Strings {
+        hotkeys: Vec<LitStr>,
+    },
     Single {
         modifiers: Box<syn::Expr>,
         _comma: Token![,],
         code: Box<syn::Expr>,
     },
     Multiple {
-        hotkeys: Punctuated<ExprTuple, Token![,]>,
+        hotkeys: Punctuated<Expr, Token![,]>,
     },
 }
 
 impl Parse for HotkeyArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // 字符串形式，逗号后跟的不是字符串时留给 `scope` 参数
+        if input.peek(LitStr) {
+            let mut hotkeys = vec![input.parse()?];
+            while input.peek(Token![,]) && input.peek2(LitStr) {
+                input.parse::<Token![,]>()?;
+                hotkeys.push(input.parse()?);
+            }
+            return Ok(HotkeyArgs::Strings { hotkeys });
+        }
+
         // 尝试解析为数组（多个热键）
         if input.peek(syn::token::Bracket) {
             let content;
             syn::bracketed!(content in input);
 
-            let hotkeys = Punctuated::<ExprTuple, Token![,]>::parse_terminated(&content)?;
+            let hotkeys = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
 
             if hotkeys.is_empty() {
                 return Err(syn::Error::new(content.span(), "至少需要一个热键参数"));
@@ -54,64 +125,72 @@ pub fn hotkey(args: TokenStream, input: TokenStream) -> TokenStream {
     let wrapper_name = syn::Ident::new(&format!("_{}", fn_name), fn_name.span());
 
     // 解析属性参数
-    let args = parse_macro_input!(args as HotkeyArgs);
+    let HotkeyAttr {
+        hotkeys,
+        scope,
+        category,
+        trigger,
+    } = parse_macro_input!(args as HotkeyAttr);
+    let scope = scope.map_or_else(
+        || quote! { ::hotkey::GLOBAL_SCOPE },
+        |scope| quote! { #scope },
+    );
+    let category = category
+        .map(|category| category.value())
+        .unwrap_or_default();
+    let description = doc_comment(&input_fn);
 
-    // 生成注册代码
-    let register_calls = match args {
+    // 收集默认热键
+    let hotkeys = match hotkeys {
+        HotkeyArgs::Strings { hotkeys } => match hotkey_string_calls(&hotkeys) {
+            Ok(calls) => calls,
+            Err(e) => return e.to_compile_error().into(),
+        },
         HotkeyArgs::Single {
             modifiers, code, ..
-        } => {
-            let call = if fn_async {
-                quote! {
-                    #vis fn #wrapper_name(app_handle: ::tauri::AppHandle) {
-                        ::tauri::async_runtime::spawn(#fn_name(app_handle));
-                    }
-                    manager.register(::hotkey::Hotkey::new(#modifiers, #code), #wrapper_name);
-                }
-            } else {
-                quote! {
-                    manager.register(::hotkey::Hotkey::new(#modifiers, #code), #fn_name);
-                }
-            };
-            quote! { #call }
-        }
+        } => vec![quote! { ::hotkey::KeySequence::from(::hotkey::Hotkey::new(#modifiers, #code)) }],
         HotkeyArgs::Multiple { hotkeys } => {
             let mut calls = Vec::new();
-
-            // 如果是异步函数，需要生成包装函数
-            if fn_async {
-                calls.push(quote! {
-                    #vis fn #wrapper_name(app_handle: ::tauri::AppHandle) {
-                        ::tauri::async_runtime::spawn(#fn_name(app_handle));
-                    }
-                });
-            }
-
-            for hotkey_tuple in hotkeys.iter() {
-                if hotkey_tuple.elems.len() != 2 {
-                    return syn::Error::new(
-                        hotkey_tuple.span(),
-                        "每个热键参数必须是 (modifiers, code) 形式的元组",
-                    )
-                    .to_compile_error()
-                    .into();
-                }
-
-                let modifiers = &hotkey_tuple.elems[0];
-                let code = &hotkey_tuple.elems[1];
-
-                if fn_async {
-                    calls.push(quote! {
-                        manager.register(::hotkey::Hotkey::new(#modifiers, #code), #wrapper_name);
-                    });
-                } else {
-                    calls.push(quote! {
-                        manager.register(::hotkey::Hotkey::new(#modifiers, #code), #fn_name);
-                    });
+            for hotkey in hotkeys.iter() {
+                let call = match hotkey {
+                    Expr::Tuple(hotkey_tuple) => hotkey_tuple_call(hotkey_tuple)
+                        .map(|call| quote! { ::hotkey::KeySequence::from(#call) }),
+                    Expr::Array(sequence) if !sequence.elems.is_empty() => sequence
+                        .elems
+                        .iter()
+                        .map(|hotkey| match hotkey {
+                            Expr::Tuple(hotkey_tuple) => hotkey_tuple_call(hotkey_tuple),
+                            _ => Err(tuple_error(hotkey)),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()
+                        .map(|calls| quote! { ::hotkey::KeySequence::new(vec![#(#calls),*]) }),
+                    _ => Err(tuple_error(hotkey)),
+                };
+                match call {
+                    Ok(call) => calls.push(call),
+                    Err(e) => return e.to_compile_error().into(),
                 }
             }
+            calls
+        }
+    };
 
-            quote! { #(#calls)* }
+    // 以函数名作为动作 id 生成注册代码，异步函数需要生成包装函数
+    let action = fn_name.to_string();
+    let trigger = trigger.map_or_else(
+        || quote! { ::std::default::Default::default() },
+        |trigger| quote! { #trigger },
+    );
+    let handler = if fn_async {
+        quote! {
+            fn #wrapper_name(app_handle: ::tauri::AppHandle) {
+                ::tauri::async_runtime::spawn(#fn_name(app_handle));
+            }
+            static HANDLER: fn(::tauri::AppHandle) = #wrapper_name;
+        }
+    } else {
+        quote! {
+            static HANDLER: fn(::tauri::AppHandle) = #fn_name;
         }
     };
 
@@ -123,9 +202,20 @@ pub fn hotkey(args: TokenStream, input: TokenStream) -> TokenStream {
         #vis struct #wrapper_name;
 
         impl ::hotkey::HotkeyRegistrar for #wrapper_name {
-            fn register(&self, mut manager: ::hotkey::HotkeyManager<::tauri::Wry>) -> ::hotkey::HotkeyManager<::tauri::Wry> {
-                #register_calls
-                manager
+            fn definition(&self) -> ::hotkey::HotkeyDefinition {
+                ::hotkey::HotkeyDefinition {
+                    action: #action,
+                    scope: #scope,
+                    description: #description,
+                    category: #category,
+                    trigger: #trigger,
+                    hotkeys: ::std::vec![#(#hotkeys),*],
+                }
+            }
+
+            fn handler(&self) -> &'static (dyn ::std::any::Any + ::std::marker::Send + ::std::marker::Sync) {
+                #handler
+                &HANDLER
             }
         }
 
@@ -134,3 +224,68 @@ pub fn hotkey(args: TokenStream, input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// 函数的文档注释作为动作描述，多行合并为一行
+fn doc_comment(input_fn: &ItemFn) -> String {
+    input_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 编译期按 `parse_sequence` 的语法校验字符串，运行时再解析以便 `CmdOrCtrl` 按目标平台取值
+fn hotkey_string_calls(hotkeys: &[LitStr]) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let mut parsed = Vec::new();
+    let mut calls = Vec::new();
+    for hotkey in hotkeys {
+        let sequence = parse::parse_sequence(&hotkey.value()).map_err(|e| {
+            let message = match e {
+                ParseError::UnsupportedKey { key } => format!("无法识别的按键：{key}"),
+                ParseError::EmptyToken { hotkey } => format!("快捷键中有空的部分：{hotkey}"),
+                ParseError::InvalidFormat { hotkey } => {
+                    format!("快捷键格式错误：{hotkey}，修饰键在前且只能有一个主键，如 \"ctrl+shift+tab\"")
+                }
+            };
+            syn::Error::new(hotkey.span(), message)
+        })?;
+        if parsed.contains(&sequence) {
+            return Err(syn::Error::new(hotkey.span(), "重复的快捷键"));
+        }
+        parsed.push(sequence);
+        calls.push(quote! {
+            #hotkey.parse::<::hotkey::KeySequence>().expect("快捷键已在编译期校验")
+        });
+    }
+    Ok(calls)
+}
+
+fn hotkey_tuple_call(hotkey_tuple: &ExprTuple) -> syn::Result<proc_macro2::TokenStream> {
+    if hotkey_tuple.elems.len() != 2 {
+        return Err(tuple_error(hotkey_tuple));
+    }
+
+    let modifiers = &hotkey_tuple.elems[0];
+    let code = &hotkey_tuple.elems[1];
+    Ok(quote! { ::hotkey::Hotkey::new(#modifiers, #code) })
+}
+
+fn tuple_error(tokens: impl Spanned) -> syn::Error {
+    syn::Error::new(
+        tokens.span(),
+        "每个热键参数必须是 (modifiers, code) 形式的元组，或由元组组成的序列数组",
+    )
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use keyboard_types::Code;
+
+/// 美式 QWERTY 布局中各按键位置输入的字符
+const US: [(Code, char); 47] = [
+    (Code::Backquote, '`'),
+    (Code::Digit1, '1'),
+    (Code::Digit2, '2'),
+    (Code::Digit3, '3'),
+    (Code::Digit4, '4'),
+    (Code::Digit5, '5'),
+    (Code::Digit6, '6'),
+    (Code::Digit7, '7'),
+    (Code::Digit8, '8'),
+    (Code::Digit9, '9'),
+    (Code::Digit0, '0'),
+    (Code::Minus, '-'),
+    (Code::Equal, '='),
+    (Code::KeyQ, 'q'),
+    (Code::KeyW, 'w'),
+    (Code::KeyE, 'e'),
+    (Code::KeyR, 'r'),
+    (Code::KeyT, 't'),
+    (Code::KeyY, 'y'),
+    (Code::KeyU, 'u'),
+    (Code::KeyI, 'i'),
+    (Code::KeyO, 'o'),
+    (Code::KeyP, 'p'),
+    (Code::BracketLeft, '['),
+    (Code::BracketRight, ']'),
+    (Code::Backslash, '\\'),
+    (Code::KeyA, 'a'),
+    (Code::KeyS, 's'),
+    (Code::KeyD, 'd'),
+    (Code::KeyF, 'f'),
+    (Code::KeyG, 'g'),
+    (Code::KeyH, 'h'),
+    (Code::KeyJ, 'j'),
+    (Code::KeyK, 'k'),
+    (Code::KeyL, 'l'),
+    (Code::Semicolon, ';'),
+    (Code::Quote, '\''),
+    (Code::KeyZ, 'z'),
+    (Code::KeyX, 'x'),
+    (Code::KeyC, 'c'),
+    (Code::KeyV, 'v'),
+    (Code::KeyB, 'b'),
+    (Code::KeyN, 'n'),
+    (Code::KeyM, 'm'),
+    (Code::Comma, ','),
+    (Code::Period, '.'),
+    (Code::Slash, '/'),
+];
+
+/// 按键位置到字符的映射，用于把逻辑按键对应到物理按键；未设置时为美式 QWERTY
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    chars: HashMap<Code, char>,
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        Self::new(US)
+    }
+}
+
+impl KeyboardLayout {
+    pub fn new(chars: impl IntoIterator<Item = (Code, char)>) -> Self {
+        Self {
+            chars: chars
+                .into_iter()
+                .map(|(code, c)| (code, c.to_lowercase().next().unwrap_or(c)))
+                .collect(),
+        }
+    }
+
+    /// 按键位置在当前布局下输入的字符
+    pub fn char(&self, code: Code) -> Option<char> {
+        self.chars.get(&code).copied()
+    }
+
+    /// 输入 `c` 的按键位置，当前布局没有该字符时（如 AZERTY 的数字需要 Shift）退回美式 QWERTY 的位置
+    pub fn code(&self, c: char) -> Option<Code> {
+        self.chars
+            .iter()
+            .find(|(_, layout_char)| **layout_char == c)
+            .map(|(code, _)| *code)
+            .or_else(|| US.iter().find(|(_, us)| *us == c).map(|(code, _)| *code))
+    }
+}
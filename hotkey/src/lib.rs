@@ -1,32 +1,87 @@
-use std::sync::Arc;
-use std::{borrow::Borrow, fmt::Display, str::FromStr};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use std::{borrow::Borrow, fmt::Display, str::FromStr, thread};
 
 use error_set::error_set;
 pub use hotkey_macros::hotkey;
 pub use inventory::submit;
 pub use keyboard_types::{Code, KeyState, Modifiers};
-use log::error;
-use scc::{HashMap, HashSet};
+pub use layout::KeyboardLayout;
+pub use matcher::{
+    ActionBinding, DEFAULT_SEQUENCE_TIMEOUT, GLOBAL_SCOPE, KeyMatcher, MatchEvent, Trigger,
+};
+use parse::ParseError;
+pub use parse::{Direction, HotkeyKey, MouseButton};
 use tauri::plugin::TauriPlugin;
-use tauri::{AppHandle, Manager, Runtime, Wry};
+use tauri::{AppHandle, Manager, Runtime};
+
+mod layout;
+mod matcher;
+mod parse;
 
 type HandlerFn<R> = Box<dyn Fn(AppHandle<R>) + Send + Sync + 'static>;
+type PendingFn<R> = Box<dyn Fn(AppHandle<R>, Option<KeySequence>) + Send + Sync + 'static>;
 
-pub fn init() -> TauriPlugin<Wry> {
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
     tauri::plugin::Builder::new("hotkey-manager")
         .setup(move |app, _api| {
-            let mut mamager = HotkeyManager::new(app.clone());
-            for factory in inventory::iter::<&dyn HotkeyRegistrar> {
-                mamager = factory.register(mamager);
+            let manager = HotkeyManager::new(app.clone());
+            let mut conflicts = Vec::new();
+            for registrar in inventory::iter::<&dyn HotkeyRegistrar> {
+                let definition = registrar.definition();
+                // 处理函数按 `AppHandle<Wry>` 生成，其他运行时下动作只参与匹配
+                let handler = registrar
+                    .handler()
+                    .downcast_ref::<fn(AppHandle<R>)>()
+                    .copied();
+                conflicts.extend(manager.register(
+                    definition.action,
+                    definition.scope,
+                    definition.hotkeys,
+                    move |app| {
+                        if let Some(handler) = handler {
+                            handler(app);
+                        }
+                    },
+                ));
+                manager.describe(
+                    definition.action,
+                    definition.description,
+                    definition.category,
+                );
+                manager.set_trigger(definition.action, definition.trigger);
+            }
+            // 调试构建中重复注册直接启动失败，避免默认快捷键悄悄失效
+            if cfg!(debug_assertions) && !conflicts.is_empty() {
+                let report = conflicts
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("；");
+                return Err(format!("快捷键重复注册：{report}").into());
             }
-            app.manage(mamager);
+            app.manage(manager);
             Ok(())
         })
         .build()
 }
 
+/// `#[hotkey]` 声明的动作
+pub struct HotkeyDefinition {
+    pub action: &'static str,
+    pub scope: &'static str,
+    pub description: &'static str,
+    pub category: &'static str,
+    pub trigger: Trigger,
+    pub hotkeys: Vec<KeySequence>,
+}
+
 pub trait HotkeyRegistrar: Send + Sync + 'static {
-    fn register(&self, manager: HotkeyManager<Wry>) -> HotkeyManager<Wry>;
+    fn definition(&self) -> HotkeyDefinition;
+    /// 处理函数，实际类型为 `fn(AppHandle<R>)`
+    fn handler(&self) -> &'static (dyn Any + Send + Sync);
 }
 
 inventory::collect!(&'static dyn HotkeyRegistrar);
@@ -40,69 +95,238 @@ macro_rules! submit_hotkey {
     };
 }
 
+/// 把 [`KeyMatcher`] 接到 Tauri 上：保存处理函数，按匹配结果回调，并用计时线程驱动序列超时、长按与重复
 pub struct HotkeyManager<R: Runtime> {
     app: AppHandle<R>,
-    pressed_keys: HashSet<Code>,
-    hotkeys: HashMap<Hotkey, Arc<HandlerFn<R>>>,
+    matcher: Mutex<KeyMatcher>,
+    callbacks: RwLock<HashMap<String, Arc<HandlerFn<R>>>>,
+    on_pending: RwLock<Option<Arc<PendingFn<R>>>>,
+    /// 已安排的计时线程的到期时间
+    timer: Mutex<Option<Instant>>,
 }
 
 impl<R: Runtime> HotkeyManager<R> {
     pub fn new(app: AppHandle<R>) -> Self {
         Self {
             app,
-            pressed_keys: HashSet::new(),
-            hotkeys: HashMap::new(),
+            matcher: Mutex::new(KeyMatcher::new()),
+            callbacks: RwLock::new(HashMap::new()),
+            on_pending: RwLock::new(None),
+            timer: Mutex::new(None),
         }
     }
 
-    pub fn init(app: AppHandle<R>, hotkeys: HashMap<Hotkey, Arc<HandlerFn<R>>>) -> Self {
-        Self {
-            app,
-            pressed_keys: HashSet::new(),
-            hotkeys,
+    /// 注册动作及其默认快捷键，快捷键已被同一作用域的其他动作占用时跳过，返回跳过的冲突
+    pub fn register<F: Fn(AppHandle<R>) + Send + Sync + 'static>(
+        &self,
+        action: &str,
+        scope: &str,
+        hotkeys: impl IntoIterator<Item = impl Into<KeySequence>>,
+        callback: F,
+    ) -> Vec<BindingError> {
+        let conflicts = self.matcher().register(action, scope, hotkeys);
+        let duplicate = conflicts
+            .iter()
+            .any(|e| matches!(e, BindingError::DuplicateAction { .. }));
+        if !duplicate {
+            self.callbacks
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(action.to_string(), Arc::new(Box::new(callback)));
         }
+        conflicts
     }
 
-    pub fn register<F: Fn(AppHandle<R>) + Send + Sync + 'static>(
+    /// 用新的快捷键替换动作的全部快捷键
+    pub fn rebind(&self, action: &str, hotkeys: Vec<KeySequence>) -> Result<(), BindingError> {
+        self.matcher().rebind(action, hotkeys)
+    }
+
+    /// 同时替换多个动作的快捷键，见 [`KeyMatcher::rebind_all`]
+    pub fn rebind_all(
+        &self,
+        bindings: Vec<(String, Vec<KeySequence>)>,
+    ) -> Vec<(String, BindingError)> {
+        self.matcher().rebind_all(bindings)
+    }
+
+    /// 移除动作的全部快捷键
+    pub fn unbind(&self, action: &str) -> Result<(), BindingError> {
+        self.matcher().unbind(action)
+    }
+
+    /// 恢复动作的默认快捷键
+    pub fn reset(&self, action: &str) -> Result<(), BindingError> {
+        self.matcher().reset(action)
+    }
+
+    /// 设置动作的描述与分类，供快捷键列表展示
+    pub fn describe(&self, action: &str, description: &str, category: &str) {
+        self.matcher().describe(action, description, category);
+    }
+
+    /// 设置动作的触发方式，默认按下时触发
+    pub fn set_trigger(&self, action: &str, trigger: Trigger) {
+        self.matcher().set_trigger(action, trigger);
+    }
+
+    /// 所有动作的绑定及元数据，按分类、动作 id 排序
+    pub fn list(&self) -> Vec<ActionBinding> {
+        self.matcher().list()
+    }
+
+    /// 当前作用域栈，第一个总是 [`GLOBAL_SCOPE`]
+    pub fn scopes(&self) -> Vec<String> {
+        self.matcher().scopes().to_vec()
+    }
+
+    /// 替换 [`GLOBAL_SCOPE`] 之上的作用域，越靠后越内层
+    pub fn set_scopes<S: Into<String>>(&self, scopes: impl IntoIterator<Item = S>) {
+        let events = self.matcher().set_scopes(scopes);
+        self.dispatch(events);
+    }
+
+    /// 压入内层作用域，已在栈中时移到栈顶
+    pub fn push_scope(&self, scope: &str) {
+        let events = self.matcher().push_scope(scope);
+        self.dispatch(events);
+    }
+
+    /// 移除作用域，[`GLOBAL_SCOPE`] 不会被移除
+    pub fn pop_scope(&self, scope: &str) {
+        let events = self.matcher().pop_scope(scope);
+        self.dispatch(events);
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        let events = self.matcher().set_enabled(enabled);
+        self.dispatch(events);
+    }
+
+    pub fn set_sequence_timeout(&self, timeout: Duration) {
+        self.matcher().set_sequence_timeout(timeout);
+    }
+
+    pub fn layout(&self) -> KeyboardLayout {
+        self.matcher().layout().clone()
+    }
+
+    /// 切换键盘布局，逻辑按键随之对应到新的按键位置
+    pub fn set_layout(&self, layout: KeyboardLayout) {
+        self.matcher().set_layout(layout);
+    }
+
+    /// 序列在当前键盘布局下的显示名称
+    pub fn label(&self, sequence: &KeySequence) -> String {
+        sequence.label(self.matcher().layout())
+    }
+
+    /// 序列前缀变化时回调，`None` 表示序列已完成、中断或超时
+    pub fn on_pending<F: Fn(AppHandle<R>, Option<KeySequence>) + Send + Sync + 'static>(
         &self,
-        hotkey: Hotkey,
         callback: F,
     ) {
-        if let Err((k, _)) = self
-            .hotkeys
-            .insert_sync(hotkey, Arc::new(Box::new(callback)))
-        {
-            error!("注册快捷键 {:?} 失败", k);
-        }
+        *self
+            .on_pending
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(Box::new(callback)));
+    }
+
+    /// 正在等待后续按键的序列前缀
+    pub fn pending(&self) -> Option<KeySequence> {
+        self.matcher().pending()
     }
 
     pub fn clear_pressed(&self) {
-        self.pressed_keys.clear_sync();
+        self.matcher().clear_pressed();
     }
 
     pub fn handle_key_event(&self, key: Code, state: KeyState) {
-        if state == KeyState::Down {
-            if self.pressed_keys.insert_sync(key).is_ok() {
-                // 键按下时检查快捷键
-                self.check_hotkeys();
+        let events = self.matcher().handle_key_event(key, state, Instant::now());
+        self.dispatch(events);
+    }
+
+    pub fn handle_mouse_button(&self, button: MouseButton) {
+        let events = self.matcher().handle_mouse_button(button, Instant::now());
+        self.dispatch(events);
+    }
+
+    /// 页面脚本识别出的鼠标手势笔画
+    pub fn handle_gesture(&self, strokes: &[Direction]) {
+        let events = self.matcher().handle_gesture(strokes);
+        self.dispatch(events);
+    }
+
+    /// 释放锁后再执行回调，回调中可以修改绑定
+    fn dispatch(&self, events: Vec<MatchEvent>) {
+        self.schedule();
+        for event in events {
+            match event {
+                MatchEvent::Action(action) => {
+                    let callback = self
+                        .callbacks
+                        .read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .get(&action)
+                        .cloned();
+                    if let Some(callback) = callback {
+                        callback(self.app.clone());
+                    }
+                }
+                MatchEvent::Pending(pending) => {
+                    let callback = self
+                        .on_pending
+                        .read()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .clone();
+                    if let Some(callback) = callback {
+                        callback(self.app.clone(), pending);
+                    }
+                }
             }
-        } else {
-            self.pressed_keys.remove_sync(&key);
         }
     }
 
-    fn check_hotkeys(&self) {
-        let Some(hotkey) = match_hotkey(&self.pressed_keys) else {
+    /// 最早的到期时间早于已安排的计时线程时，另起一个线程到期后驱动匹配器
+    fn schedule(&self) {
+        let Some(deadline) = self.matcher().next_deadline() else {
             return;
         };
+        {
+            let mut timer = lock(&self.timer);
+            if timer.is_some_and(|at| at <= deadline) {
+                return;
+            }
+            *timer = Some(deadline);
+        }
 
-        let Some(callback) = self.hotkeys.get_sync(&hotkey) else {
-            return;
-        };
-        callback(self.app.clone());
+        let app = self.app.clone();
+        thread::spawn(move || {
+            thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            app.hotkey().tick(deadline);
+        });
+    }
+
+    fn tick(&self, deadline: Instant) {
+        {
+            let mut timer = lock(&self.timer);
+            if *timer == Some(deadline) {
+                *timer = None;
+            }
+        }
+        let events = self.matcher().tick(Instant::now());
+        self.dispatch(events);
+    }
+
+    fn matcher(&self) -> MutexGuard<'_, KeyMatcher> {
+        lock(&self.matcher)
     }
 }
 
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 pub trait HotkeyManagerExt<R: Runtime> {
     fn hotkey(&self) -> &HotkeyManager<R>;
 }
@@ -115,24 +339,32 @@ impl<R: Runtime, T: Manager<R>> HotkeyManagerExt<R> for T {
 
 /// A keyboard shortcut that consists of an optional combination
 /// of modifier keys (provided by [`Modifiers`](crate::hotkey::Modifiers)) and
-/// one key ([`Code`](crate::hotkey::Code)).
+/// one key ([`HotkeyKey`]).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Hotkey {
     /// The hotkey modifiers.
     pub mods: Modifiers,
     /// The hotkey key.
-    pub key: Code,
+    pub key: HotkeyKey,
 }
 
 impl Hotkey {
     /// Creates a new hotkey to define keyboard shortcuts throughout your application.
     /// Only [`Modifiers::ALT`], [`Modifiers::SHIFT`], [`Modifiers::CONTROL`], and [`Modifiers::META`]
-    pub fn new(mut mods: Modifiers, key: Code) -> Self {
+    pub fn new(mut mods: Modifiers, key: impl Into<HotkeyKey>) -> Self {
         if mods.contains(Modifiers::META) {
             mods.remove(Modifiers::META);
             mods.insert(Modifiers::META);
         }
 
+        // 只有修饰键的快捷键不区分左右
+        let key = match key.into() {
+            HotkeyKey::Physical(Code::ShiftRight) => HotkeyKey::Physical(Code::ShiftLeft),
+            HotkeyKey::Physical(Code::ControlRight) => HotkeyKey::Physical(Code::ControlLeft),
+            HotkeyKey::Physical(Code::AltRight) => HotkeyKey::Physical(Code::AltLeft),
+            HotkeyKey::Physical(Code::MetaRight) => HotkeyKey::Physical(Code::MetaLeft),
+            key => key,
+        };
         Self { mods, key }
     }
 
@@ -143,7 +375,7 @@ impl Hotkey {
         let base_mods = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META;
         let modifiers = modifiers.borrow();
         let key = key.borrow();
-        self.mods == *modifiers & base_mods && self.key == *key
+        self.mods == *modifiers & base_mods && self.key == HotkeyKey::Physical(*key)
     }
 
     /// Converts this hotkey into a string.
@@ -161,17 +393,131 @@ impl Hotkey {
         if self.mods.contains(Modifiers::META) {
             hotkey.push_str("super+")
         }
-        hotkey.push_str(&self.key.to_string());
+        match self.key {
+            HotkeyKey::Physical(code) => hotkey.push_str(&code.to_string()),
+            HotkeyKey::Logical(c) => hotkey.push(c),
+            HotkeyKey::Mouse(button) => hotkey.push_str(&format!("Mouse{button}")),
+            HotkeyKey::Gesture(direction) => hotkey.push_str(&format!("Gesture{direction}")),
+        }
         hotkey
     }
+
+    /// 给用户看的名称，如 `Ctrl+Shift+Tab`；按键位置显示为当前布局下的字符
+    pub fn label(&self, layout: &KeyboardLayout) -> String {
+        let mut label = String::new();
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl+"),
+            (
+                Modifiers::ALT,
+                if cfg!(target_os = "macos") {
+                    "Option+"
+                } else {
+                    "Alt+"
+                },
+            ),
+            (Modifiers::SHIFT, "Shift+"),
+            (Modifiers::META, META_LABEL),
+        ] {
+            if self.mods.contains(modifier) {
+                label.push_str(name);
+            }
+        }
+
+        match self.key {
+            HotkeyKey::Logical(c) => label.extend(c.to_uppercase()),
+            HotkeyKey::Physical(code) => match layout.char(code) {
+                Some(c) => label.extend(c.to_uppercase()),
+                None => label.push_str(&code_label(code)),
+            },
+            HotkeyKey::Mouse(MouseButton::Back) => label.push_str("鼠标后退键"),
+            HotkeyKey::Mouse(MouseButton::Forward) => label.push_str("鼠标前进键"),
+            HotkeyKey::Gesture(direction) => label.push_str(direction.arrow()),
+        }
+        label
+    }
 }
 
+fn code_label(code: Code) -> String {
+    let name = match code {
+        Code::ArrowUp => "↑",
+        Code::ArrowDown => "↓",
+        Code::ArrowLeft => "←",
+        Code::ArrowRight => "→",
+        Code::Escape => "Esc",
+        Code::Delete => "Del",
+        Code::ShiftLeft => "Shift",
+        Code::ControlLeft => "Ctrl",
+        Code::AltLeft => "Alt",
+        Code::MetaLeft => META_LABEL.trim_end_matches('+'),
+        _ => return code.to_string(),
+    };
+    name.to_string()
+}
+
+/// Super 键在各平台上的叫法
+const META_LABEL: &str = if cfg!(target_os = "macos") {
+    "Cmd+"
+} else if cfg!(windows) {
+    "Win+"
+} else {
+    "Super+"
+};
+
 impl Display for Hotkey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.into_string())
     }
 }
 
+impl Display for MouseButton {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// 页面脚本上报的按键名称，如 `Back`
+impl FromStr for MouseButton {
+    type Err = HotkeyParseError;
+    fn from_str(button: &str) -> Result<Self, Self::Err> {
+        match parse::parse_hotkey(&format!("Mouse{button}"))? {
+            (_, HotkeyKey::Mouse(button)) => Ok(button),
+            _ => Err(HotkeyParseError::UnsupportedKey {
+                key: button.to_string(),
+            }),
+        }
+    }
+}
+
+impl Direction {
+    fn arrow(self) -> &'static str {
+        match self {
+            Direction::Up => "↑",
+            Direction::Down => "↓",
+            Direction::Left => "←",
+            Direction::Right => "→",
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// 页面脚本上报的方向名称，如 `Left`
+impl FromStr for Direction {
+    type Err = HotkeyParseError;
+    fn from_str(direction: &str) -> Result<Self, Self::Err> {
+        match parse::parse_hotkey(&format!("Gesture{direction}"))? {
+            (_, HotkeyKey::Gesture(direction)) => Ok(direction),
+            _ => Err(HotkeyParseError::UnsupportedKey {
+                key: direction.to_string(),
+            }),
+        }
+    }
+}
+
 // Hotkey::from_str is available to be backward
 // compatible with tauri and it also open the option
 // to generate hotkey from string
@@ -198,225 +544,87 @@ impl TryFrom<String> for Hotkey {
     }
 }
 
-fn match_hotkey(pressed_keys: &HashSet<Code>) -> Option<Hotkey> {
-    let mut mods = Modifiers::empty();
-    let mut key = None;
-    pressed_keys.iter_sync(|code| {
-        match *code {
-            Code::ControlLeft | Code::ControlRight => mods |= Modifiers::CONTROL,
-            Code::AltLeft | Code::AltRight => mods |= Modifiers::ALT,
-            Code::ShiftLeft | Code::ShiftRight => mods |= Modifiers::SHIFT,
-            Code::MetaLeft | Code::MetaRight => mods |= Modifiers::META,
-            Code::Fn => mods |= Modifiers::FN,
-            Code::CapsLock => mods |= Modifiers::CAPS_LOCK,
-            Code::NumLock => mods |= Modifiers::NUM_LOCK,
-            Code::ScrollLock => mods |= Modifiers::SCROLL_LOCK,
-            Code::FnLock => mods |= Modifiers::FN_LOCK,
-            _ => {
-                key = Some(*code);
-            }
-        }
-        true
-    });
+/// 依次按下的快捷键序列，如 `ctrl+k ctrl+w`；单个快捷键是长度为 1 的序列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<Hotkey>);
 
-    key.map(|key| Hotkey { mods, key })
-}
-
-fn parse_hotkey(hotkey: &str) -> Result<Hotkey, HotkeyParseError> {
-    let tokens = hotkey.split('+').collect::<Vec<&str>>();
+impl KeySequence {
+    pub fn new(hotkeys: Vec<Hotkey>) -> Self {
+        Self(hotkeys)
+    }
 
-    let mut mods = Modifiers::empty();
-    let mut key = None;
+    /// 相同或互为前缀
+    pub fn overlaps(&self, other: &KeySequence) -> bool {
+        self.0.starts_with(&other.0) || other.0.starts_with(&self.0)
+    }
 
-    match tokens.len() {
-        // single key hotkey
-        1 => {
-            key = Some(parse_key(tokens[0])?);
+    /// 给用户看的名称，如 `Ctrl+K Ctrl+W`，鼠标手势如 `手势 ↓→`
+    pub fn label(&self, layout: &KeyboardLayout) -> String {
+        let gesture = self
+            .0
+            .iter()
+            .all(|hotkey| matches!(hotkey.key, HotkeyKey::Gesture(_)) && hotkey.mods.is_empty());
+        if gesture && !self.0.is_empty() {
+            let strokes: String = self.0.iter().map(|hotkey| hotkey.label(layout)).collect();
+            return format!("手势 {strokes}");
         }
-        // modifiers and key comobo hotkey
-        _ => {
-            for raw in tokens {
-                let token = raw.trim();
-
-                if token.is_empty() {
-                    return Err(HotkeyParseError::EmptyToken {
-                        hotkey: hotkey.to_string(),
-                    });
-                }
 
-                if key.is_some() {
-                    // At this point we have parsed the modifiers and a main key, so by reaching
-                    // this code, the function either received more than one main key or
-                    //  the hotkey is not in the right order
-                    // examples:
-                    // 1. "Ctrl+Shift+C+A" => only one main key should be allowd.
-                    // 2. "Ctrl+C+Shift" => wrong order
-                    return Err(HotkeyParseError::InvalidFormat {
-                        hotkey: hotkey.to_string(),
-                    });
-                }
+        self.0
+            .iter()
+            .map(|hotkey| hotkey.label(layout))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
 
-                match token.to_uppercase().as_str() {
-                    "OPTION" | "ALT" => {
-                        mods |= Modifiers::ALT;
-                    }
-                    "CONTROL" | "CTRL" => {
-                        mods |= Modifiers::CONTROL;
-                    }
-                    "COMMAND" | "CMD" | "META" => {
-                        mods |= Modifiers::META;
-                    }
-                    "SHIFT" => {
-                        mods |= Modifiers::SHIFT;
-                    }
-                    #[cfg(target_os = "macos")]
-                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
-                        mods |= Modifiers::META;
-                    }
-                    #[cfg(not(target_os = "macos"))]
-                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
-                        mods |= Modifiers::CONTROL;
-                    }
-                    _ => {
-                        key = Some(parse_key(token)?);
-                    }
-                }
+impl From<Hotkey> for KeySequence {
+    fn from(hotkey: Hotkey) -> Self {
+        Self(vec![hotkey])
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, hotkey) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
             }
+            write!(f, "{hotkey}")?;
         }
+        Ok(())
     }
+}
 
-    Ok(Hotkey::new(
-        mods,
-        key.ok_or_else(|| HotkeyParseError::InvalidFormat {
-            hotkey: hotkey.to_string(),
-        })?,
+impl FromStr for KeySequence {
+    type Err = HotkeyParseError;
+    fn from_str(sequence: &str) -> Result<Self, Self::Err> {
+        parse_sequence(sequence)
+    }
+}
+
+/// 空白分隔的快捷键序列，`+` 两侧的空白不作分隔，如 `Ctrl + K Ctrl + W`
+fn parse_sequence(sequence: &str) -> Result<KeySequence, HotkeyParseError> {
+    let hotkeys = parse::parse_sequence(sequence)?;
+    Ok(KeySequence(
+        hotkeys
+            .into_iter()
+            .map(|(mods, key)| Hotkey::new(mods, key))
+            .collect(),
     ))
 }
 
-fn parse_key(key: &str) -> Result<Code, HotkeyParseError> {
-    use Code::*;
-    match key.to_uppercase().as_str() {
-        "BACKQUOTE" | "`" => Ok(Backquote),
-        "BACKSLASH" | "\\" => Ok(Backslash),
-        "BRACKETLEFT" | "[" => Ok(BracketLeft),
-        "BRACKETRIGHT" | "]" => Ok(BracketRight),
-        "PAUSE" | "PAUSEBREAK" => Ok(Pause),
-        "COMMA" | "," => Ok(Comma),
-        "DIGIT0" | "0" => Ok(Digit0),
-        "DIGIT1" | "1" => Ok(Digit1),
-        "DIGIT2" | "2" => Ok(Digit2),
-        "DIGIT3" | "3" => Ok(Digit3),
-        "DIGIT4" | "4" => Ok(Digit4),
-        "DIGIT5" | "5" => Ok(Digit5),
-        "DIGIT6" | "6" => Ok(Digit6),
-        "DIGIT7" | "7" => Ok(Digit7),
-        "DIGIT8" | "8" => Ok(Digit8),
-        "DIGIT9" | "9" => Ok(Digit9),
-        "EQUAL" | "=" => Ok(Equal),
-        "KEYA" | "A" => Ok(KeyA),
-        "KEYB" | "B" => Ok(KeyB),
-        "KEYC" | "C" => Ok(KeyC),
-        "KEYD" | "D" => Ok(KeyD),
-        "KEYE" | "E" => Ok(KeyE),
-        "KEYF" | "F" => Ok(KeyF),
-        "KEYG" | "G" => Ok(KeyG),
-        "KEYH" | "H" => Ok(KeyH),
-        "KEYI" | "I" => Ok(KeyI),
-        "KEYJ" | "J" => Ok(KeyJ),
-        "KEYK" | "K" => Ok(KeyK),
-        "KEYL" | "L" => Ok(KeyL),
-        "KEYM" | "M" => Ok(KeyM),
-        "KEYN" | "N" => Ok(KeyN),
-        "KEYO" | "O" => Ok(KeyO),
-        "KEYP" | "P" => Ok(KeyP),
-        "KEYQ" | "Q" => Ok(KeyQ),
-        "KEYR" | "R" => Ok(KeyR),
-        "KEYS" | "S" => Ok(KeyS),
-        "KEYT" | "T" => Ok(KeyT),
-        "KEYU" | "U" => Ok(KeyU),
-        "KEYV" | "V" => Ok(KeyV),
-        "KEYW" | "W" => Ok(KeyW),
-        "KEYX" | "X" => Ok(KeyX),
-        "KEYY" | "Y" => Ok(KeyY),
-        "KEYZ" | "Z" => Ok(KeyZ),
-        "MINUS" | "-" => Ok(Minus),
-        "PERIOD" | "." => Ok(Period),
-        "QUOTE" | "'" => Ok(Quote),
-        "SEMICOLON" | ";" => Ok(Semicolon),
-        "SLASH" | "/" => Ok(Slash),
-        "BACKSPACE" => Ok(Backspace),
-        "CAPSLOCK" => Ok(CapsLock),
-        "ENTER" => Ok(Enter),
-        "SPACE" => Ok(Space),
-        "TAB" => Ok(Tab),
-        "DELETE" => Ok(Delete),
-        "END" => Ok(End),
-        "HOME" => Ok(Home),
-        "INSERT" => Ok(Insert),
-        "PAGEDOWN" => Ok(PageDown),
-        "PAGEUP" => Ok(PageUp),
-        "PRINTSCREEN" => Ok(PrintScreen),
-        "SCROLLLOCK" => Ok(ScrollLock),
-        "ARROWDOWN" | "DOWN" => Ok(ArrowDown),
-        "ARROWLEFT" | "LEFT" => Ok(ArrowLeft),
-        "ARROWRIGHT" | "RIGHT" => Ok(ArrowRight),
-        "ARROWUP" | "UP" => Ok(ArrowUp),
-        "NUMLOCK" => Ok(NumLock),
-        "NUMPAD0" | "NUM0" => Ok(Numpad0),
-        "NUMPAD1" | "NUM1" => Ok(Numpad1),
-        "NUMPAD2" | "NUM2" => Ok(Numpad2),
-        "NUMPAD3" | "NUM3" => Ok(Numpad3),
-        "NUMPAD4" | "NUM4" => Ok(Numpad4),
-        "NUMPAD5" | "NUM5" => Ok(Numpad5),
-        "NUMPAD6" | "NUM6" => Ok(Numpad6),
-        "NUMPAD7" | "NUM7" => Ok(Numpad7),
-        "NUMPAD8" | "NUM8" => Ok(Numpad8),
-        "NUMPAD9" | "NUM9" => Ok(Numpad9),
-        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => Ok(NumpadAdd),
-        "NUMPADDECIMAL" | "NUMDECIMAL" => Ok(NumpadDecimal),
-        "NUMPADDIVIDE" | "NUMDIVIDE" => Ok(NumpadDivide),
-        "NUMPADENTER" | "NUMENTER" => Ok(NumpadEnter),
-        "NUMPADEQUAL" | "NUMEQUAL" => Ok(NumpadEqual),
-        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Ok(NumpadMultiply),
-        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Ok(NumpadSubtract),
-        "ESCAPE" | "ESC" => Ok(Escape),
-        "F1" => Ok(F1),
-        "F2" => Ok(F2),
-        "F3" => Ok(F3),
-        "F4" => Ok(F4),
-        "F5" => Ok(F5),
-        "F6" => Ok(F6),
-        "F7" => Ok(F7),
-        "F8" => Ok(F8),
-        "F9" => Ok(F9),
-        "F10" => Ok(F10),
-        "F11" => Ok(F11),
-        "F12" => Ok(F12),
-        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Ok(AudioVolumeDown),
-        "AUDIOVOLUMEUP" | "VOLUMEUP" => Ok(AudioVolumeUp),
-        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Ok(AudioVolumeMute),
-        "MEDIAPLAY" => Ok(MediaPlay),
-        "MEDIAPAUSE" => Ok(MediaPause),
-        "MEDIAPLAYPAUSE" => Ok(MediaPlayPause),
-        "MEDIASTOP" => Ok(MediaStop),
-        "MEDIATRACKNEXT" => Ok(MediaTrackNext),
-        "MEDIATRACKPREV" | "MEDIATRACKPREVIOUS" => Ok(MediaTrackPrevious),
-        "F13" => Ok(F13),
-        "F14" => Ok(F14),
-        "F15" => Ok(F15),
-        "F16" => Ok(F16),
-        "F17" => Ok(F17),
-        "F18" => Ok(F18),
-        "F19" => Ok(F19),
-        "F20" => Ok(F20),
-        "F21" => Ok(F21),
-        "F22" => Ok(F22),
-        "F23" => Ok(F23),
-        "F24" => Ok(F24),
-
-        _ => Err(HotkeyParseError::UnsupportedKey {
-            key: key.to_string(),
-        }),
+fn parse_hotkey(hotkey: &str) -> Result<Hotkey, HotkeyParseError> {
+    let (mods, key) = parse::parse_hotkey(hotkey)?;
+    Ok(Hotkey::new(mods, key))
+}
+
+impl From<ParseError> for HotkeyParseError {
+    fn from(error: ParseError) -> Self {
+        match error {
+            ParseError::UnsupportedKey { key } => HotkeyParseError::UnsupportedKey { key },
+            ParseError::EmptyToken { hotkey } => HotkeyParseError::EmptyToken { hotkey },
+            ParseError::InvalidFormat { hotkey } => HotkeyParseError::InvalidFormat { hotkey },
+        }
     }
 }
 
@@ -435,4 +643,197 @@ error_set! {
             hotkey: String,
         },
     }
+    BindingError := {
+        #[display("未知的快捷键动作：{action}")]
+        UnknownAction {
+            action: String,
+        },
+        #[display("动作 {action} 重复注册")]
+        DuplicateAction {
+            action: String,
+        },
+        #[display("快捷键 {hotkey} 已绑定到 {action}")]
+        Conflict {
+            hotkey: KeySequence,
+            action: String,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tauri::test::{MockRuntime, mock_app};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey() {
+        let hotkey: Hotkey = "Ctrl + Shift + Tab".parse().unwrap();
+        assert_eq!(
+            hotkey,
+            Hotkey::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::Tab)
+        );
+        assert_eq!(hotkey.to_string(), "shift+control+Tab");
+        assert_eq!(hotkey.to_string().parse::<Hotkey>().unwrap(), hotkey);
+
+        assert_eq!(
+            "alt+F4".parse::<Hotkey>().unwrap(),
+            Hotkey::new(Modifiers::ALT, Code::F4)
+        );
+        assert!(matches!(
+            "ctrl+nope".parse::<Hotkey>(),
+            Err(HotkeyParseError::UnsupportedKey { .. })
+        ));
+        assert!(matches!(
+            "ctrl++".parse::<Hotkey>(),
+            Err(HotkeyParseError::EmptyToken { .. })
+        ));
+        assert!(matches!(
+            "ctrl+a+b".parse::<Hotkey>(),
+            Err(HotkeyParseError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        let sequence: KeySequence = "Ctrl + K  Ctrl+W".parse().unwrap();
+        assert_eq!(
+            sequence,
+            KeySequence::new(vec![
+                Hotkey::new(Modifiers::CONTROL, 'k'),
+                Hotkey::new(Modifiers::CONTROL, 'w'),
+            ])
+        );
+        assert_eq!(sequence.to_string(), "control+k control+w");
+        assert_eq!(
+            sequence.to_string().parse::<KeySequence>().unwrap(),
+            sequence
+        );
+        assert!(sequence.overlaps(&"ctrl+k".parse().unwrap()));
+        assert!(!sequence.overlaps(&"ctrl+w".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_logical_key() {
+        // 单个字符按布局匹配，按键名称按位置匹配
+        assert_eq!(
+            "ctrl+W".parse::<Hotkey>().unwrap(),
+            Hotkey::new(Modifiers::CONTROL, HotkeyKey::Logical('w'))
+        );
+        assert_eq!(
+            "ctrl+KeyW".parse::<Hotkey>().unwrap(),
+            Hotkey::new(Modifiers::CONTROL, HotkeyKey::Physical(Code::KeyW))
+        );
+        assert_eq!(
+            "ctrl+é".parse::<Hotkey>().unwrap().key,
+            HotkeyKey::Logical('é')
+        );
+
+        let azerty = KeyboardLayout::new([
+            (Code::KeyQ, 'a'),
+            (Code::KeyW, 'z'),
+            (Code::KeyZ, 'w'),
+            (Code::Digit1, '&'),
+        ]);
+        let hotkey: KeySequence = "ctrl+KeyW ctrl+w".parse().unwrap();
+        assert_eq!(hotkey.label(&azerty), "Ctrl+Z Ctrl+W");
+        assert_eq!(hotkey.label(&KeyboardLayout::default()), "Ctrl+W Ctrl+W");
+        let hotkey: KeySequence = "alt+ArrowLeft shift+Escape F5".parse().unwrap();
+        assert_eq!(hotkey.label(&azerty), "Alt+← Shift+Esc F5");
+    }
+
+    #[test]
+    fn test_mouse_hotkey() {
+        let back: KeySequence = "MouseBack".parse().unwrap();
+        assert_eq!(
+            back,
+            Hotkey::new(Modifiers::empty(), HotkeyKey::Mouse(MouseButton::Back)).into()
+        );
+        let gesture: KeySequence = "GestureDown GestureRight".parse().unwrap();
+        assert_eq!(gesture.to_string(), "GestureDown GestureRight");
+        assert_eq!(gesture.to_string().parse::<KeySequence>().unwrap(), gesture);
+
+        let layout = KeyboardLayout::default();
+        assert_eq!(gesture.label(&layout), "手势 ↓→");
+        let hotkey: KeySequence = "ctrl+MouseForward".parse().unwrap();
+        assert_eq!(hotkey.label(&layout), "Ctrl+鼠标前进键");
+
+        assert_eq!("Back".parse::<MouseButton>().unwrap(), MouseButton::Back);
+        assert_eq!("Left".parse::<Direction>().unwrap(), Direction::Left);
+        assert!("Middle".parse::<MouseButton>().is_err());
+        assert!("Back".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn test_modifier_normalisation() {
+        // 修饰键的别名
+        for hotkey in ["super+a", "cmd+a", "command+a", "meta+a"] {
+            assert_eq!(
+                hotkey.parse::<Hotkey>().unwrap(),
+                Hotkey::new(Modifiers::META, 'a'),
+                "{hotkey}"
+            );
+        }
+        assert_eq!(
+            "control+a".parse::<Hotkey>().unwrap(),
+            "ctrl+a".parse::<Hotkey>().unwrap()
+        );
+        assert_eq!(
+            "option+a".parse::<Hotkey>().unwrap(),
+            "alt+a".parse::<Hotkey>().unwrap()
+        );
+
+        // 只有修饰键的快捷键不区分左右
+        assert_eq!(
+            Hotkey::new(Modifiers::empty(), Code::ShiftRight),
+            Hotkey::new(Modifiers::empty(), Code::ShiftLeft)
+        );
+        assert_eq!(
+            "shift".parse::<Hotkey>().unwrap(),
+            Hotkey::new(Modifiers::empty(), Code::ShiftLeft)
+        );
+        assert_eq!(
+            "ControlRight".parse::<Hotkey>().unwrap(),
+            "ctrl".parse::<Hotkey>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mock_runtime() {
+        let app = mock_app();
+        app.manage(HotkeyManager::<MockRuntime>::new(app.handle().clone()));
+        let manager = app.hotkey();
+
+        static CLOSED: AtomicUsize = AtomicUsize::new(0);
+        let hotkey: KeySequence = "ctrl+w".parse().unwrap();
+        let conflicts = manager.register("close_tab", "tab", [hotkey], |_| {
+            CLOSED.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(conflicts.is_empty());
+
+        // 作用域未生效时不触发
+        manager.handle_key_event(Code::ControlRight, KeyState::Down);
+        manager.handle_key_event(Code::KeyW, KeyState::Down);
+        assert_eq!(CLOSED.load(Ordering::SeqCst), 0);
+        manager.handle_key_event(Code::KeyW, KeyState::Up);
+
+        manager.push_scope("tab");
+        manager.handle_key_event(Code::KeyW, KeyState::Down);
+        assert_eq!(CLOSED.load(Ordering::SeqCst), 1);
+        manager.handle_key_event(Code::KeyW, KeyState::Up);
+
+        // 残留的 Ctrl 清空后不再组合
+        manager.clear_pressed();
+        manager.handle_key_event(Code::KeyW, KeyState::Down);
+        manager.handle_key_event(Code::KeyW, KeyState::Up);
+        assert_eq!(CLOSED.load(Ordering::SeqCst), 1);
+
+        // 录制期间暂停触发
+        manager.set_enabled(false);
+        manager.handle_key_event(Code::ControlLeft, KeyState::Down);
+        manager.handle_key_event(Code::KeyW, KeyState::Down);
+        assert_eq!(CLOSED.load(Ordering::SeqCst), 1);
+    }
 }
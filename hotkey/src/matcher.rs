@@ -0,0 +1,1020 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use keyboard_types::{Code, KeyState, Modifiers};
+use log::error;
+
+use crate::{BindingError, Direction, Hotkey, HotkeyKey, KeySequence, KeyboardLayout, MouseButton};
+
+/// 始终生效的作用域，位于作用域栈底
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// 等待序列下一个按键的默认时长
+pub const DEFAULT_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// 快捷键的触发方式，序列按最后一个快捷键计算
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Trigger {
+    /// 按下时触发
+    #[default]
+    Press,
+    /// 按下后松开时触发，期间按了其他键则取消
+    Release,
+    /// 按住指定时长后触发一次，提前松开不触发
+    Hold(Duration),
+    /// 按下时触发，按住期间每隔指定时长重复触发
+    Repeat(Duration),
+    /// 指定时长内连续按两次时触发，只有修饰键的快捷键（如 `Shift`）在单独按下并松开时算一次
+    DoubleTap(Duration),
+}
+
+/// 动作及其元数据、默认快捷键、当前快捷键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionBinding {
+    /// 稳定的动作 id，即处理函数名
+    pub action: String,
+    pub scope: String,
+    /// 处理函数的文档注释，没有时为空
+    pub description: String,
+    /// `#[hotkey(..., category = "...")]` 指定的分类，没有时为空
+    pub category: String,
+    pub trigger: Trigger,
+    pub defaults: Vec<KeySequence>,
+    pub hotkeys: Vec<KeySequence>,
+    /// 默认快捷键在当前键盘布局下的显示名称
+    pub default_labels: Vec<String>,
+    /// 当前快捷键在当前键盘布局下的显示名称
+    pub labels: Vec<String>,
+}
+
+/// 匹配器的输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchEvent {
+    /// 触发动作
+    Action(String),
+    /// 序列前缀变化，`None` 表示序列已完成、中断或超时
+    Pending(Option<KeySequence>),
+}
+
+struct Action {
+    scope: String,
+    description: String,
+    category: String,
+    trigger: Trigger,
+    defaults: Vec<KeySequence>,
+    hotkeys: Vec<KeySequence>,
+}
+
+enum Lookup {
+    Matched(String, Trigger),
+    Prefix,
+    None,
+}
+
+/// 等待松开、长按或重复的已匹配快捷键，松开任意键或按下其他键时结束
+struct Held {
+    action: String,
+    trigger: Trigger,
+    /// 长按、重复的下一次触发时间
+    deadline: Option<Instant>,
+}
+
+/// 与 Tauri 无关的按键匹配器：输入按键事件与当前时间，输出触发的动作 id；
+/// 计时由调用方在 [`KeyMatcher::next_deadline`] 到期时调用 [`KeyMatcher::tick`] 驱动
+pub struct KeyMatcher {
+    /// 录制快捷键时暂停触发
+    enabled: bool,
+    pressed_keys: HashSet<Code>,
+    actions: HashMap<String, Action>,
+    /// 生效的作用域，越靠后越内层
+    scopes: Vec<String>,
+    sequence_timeout: Duration,
+    /// 已按下的序列前缀
+    pending: Vec<Hotkey>,
+    pending_since: Option<Instant>,
+    held: Option<Held>,
+    /// 上一次双击触发方式的按键及时间
+    last_tap: Option<(Vec<Hotkey>, Instant)>,
+    /// 单独按下的修饰键，松开前没有按其他键时作为只有修饰键的快捷键
+    modifier_tap: Option<Code>,
+    layout: KeyboardLayout,
+}
+
+impl Default for KeyMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyMatcher {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            pressed_keys: HashSet::new(),
+            actions: HashMap::new(),
+            scopes: vec![GLOBAL_SCOPE.to_string()],
+            sequence_timeout: DEFAULT_SEQUENCE_TIMEOUT,
+            pending: Vec::new(),
+            pending_since: None,
+            held: None,
+            last_tap: None,
+            modifier_tap: None,
+            layout: KeyboardLayout::default(),
+        }
+    }
+
+    /// 注册动作及其默认快捷键，快捷键已被同一作用域的其他动作占用时跳过，返回跳过的冲突
+    pub fn register(
+        &mut self,
+        action: &str,
+        scope: &str,
+        hotkeys: impl IntoIterator<Item = impl Into<KeySequence>>,
+    ) -> Vec<BindingError> {
+        if self.actions.contains_key(action) {
+            error!("注册动作 {action} 失败：动作已存在");
+            return vec![BindingError::DuplicateAction {
+                action: action.to_string(),
+            }];
+        }
+
+        let mut conflicts = Vec::new();
+        let mut defaults: Vec<KeySequence> = Vec::new();
+        for hotkey in hotkeys {
+            let hotkey = hotkey.into();
+            let bound = if let Some((sequence, bound)) = self.conflict(scope, &hotkey, action) {
+                error!("注册快捷键 {hotkey} 失败：与 {bound} 的 {sequence} 冲突");
+                bound.clone()
+            } else if let Some(sequence) =
+                defaults.iter().find(|other| self.overlaps(other, &hotkey))
+            {
+                error!("注册快捷键 {hotkey} 失败：与 {action} 的 {sequence} 冲突");
+                action.to_string()
+            } else {
+                defaults.push(hotkey);
+                continue;
+            };
+            conflicts.push(BindingError::Conflict {
+                hotkey,
+                action: bound,
+            });
+        }
+        self.actions.insert(
+            action.to_string(),
+            Action {
+                scope: scope.to_string(),
+                description: String::new(),
+                category: String::new(),
+                trigger: Trigger::default(),
+                hotkeys: defaults.clone(),
+                defaults,
+            },
+        );
+        conflicts
+    }
+
+    /// 用新的快捷键替换动作的全部快捷键
+    pub fn rebind(&mut self, action: &str, hotkeys: Vec<KeySequence>) -> Result<(), BindingError> {
+        let Some(scope) = self.actions.get(action).map(|action| action.scope.clone()) else {
+            return Err(BindingError::UnknownAction {
+                action: action.to_string(),
+            });
+        };
+        let mut unique: Vec<KeySequence> = Vec::new();
+        for hotkey in hotkeys {
+            if !unique.contains(&hotkey) {
+                unique.push(hotkey);
+            }
+        }
+        let hotkeys = unique;
+        for (i, hotkey) in hotkeys.iter().enumerate() {
+            if let Some((_, bound)) = self.conflict(&scope, hotkey, action) {
+                return Err(BindingError::Conflict {
+                    hotkey: hotkey.clone(),
+                    action: bound.clone(),
+                });
+            }
+            if hotkeys[..i]
+                .iter()
+                .any(|other| self.overlaps(other, hotkey))
+            {
+                return Err(BindingError::Conflict {
+                    hotkey: hotkey.clone(),
+                    action: action.to_string(),
+                });
+            }
+        }
+
+        if let Some(action) = self.actions.get_mut(action) {
+            action.hotkeys = hotkeys;
+        }
+        Ok(())
+    }
+
+    /// 同时替换多个动作的快捷键：先清空这些动作再逐个绑定，互换快捷键不会因先后顺序冲突。
+    /// 返回失败的动作及原因，失败的动作保留原来仍不冲突的快捷键
+    pub fn rebind_all(
+        &mut self,
+        bindings: Vec<(String, Vec<KeySequence>)>,
+    ) -> Vec<(String, BindingError)> {
+        let mut errors = Vec::new();
+        let mut previous = HashMap::new();
+        for (action, _) in &bindings {
+            if let Some(bound) = self.actions.get_mut(action) {
+                previous.insert(action.clone(), std::mem::take(&mut bound.hotkeys));
+            }
+        }
+
+        let mut failed = Vec::new();
+        for (action, hotkeys) in bindings {
+            if let Err(e) = self.rebind(&action, hotkeys) {
+                if previous.contains_key(&action) {
+                    failed.push(action.clone());
+                }
+                errors.push((action, e));
+            }
+        }
+
+        for action in failed {
+            let scope = self.actions[&action].scope.clone();
+            let hotkeys = previous
+                .remove(&action)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|hotkey| self.conflict(&scope, hotkey, &action).is_none())
+                .collect();
+            // 原来的快捷键之间互不冲突
+            let _ = self.rebind(&action, hotkeys);
+        }
+        errors
+    }
+
+    /// 移除动作的全部快捷键
+    pub fn unbind(&mut self, action: &str) -> Result<(), BindingError> {
+        self.rebind(action, Vec::new())
+    }
+
+    /// 恢复动作的默认快捷键
+    pub fn reset(&mut self, action: &str) -> Result<(), BindingError> {
+        let defaults = self
+            .actions
+            .get(action)
+            .map(|action| action.defaults.clone())
+            .ok_or_else(|| BindingError::UnknownAction {
+                action: action.to_string(),
+            })?;
+        self.rebind(action, defaults)
+    }
+
+    /// 设置动作的描述与分类，供快捷键列表展示
+    pub fn describe(&mut self, action: &str, description: &str, category: &str) {
+        if let Some(action) = self.actions.get_mut(action) {
+            action.description = description.to_string();
+            action.category = category.to_string();
+        }
+    }
+
+    /// 设置动作的触发方式，默认按下时触发
+    pub fn set_trigger(&mut self, action: &str, trigger: Trigger) {
+        if let Some(action) = self.actions.get_mut(action) {
+            action.trigger = trigger;
+        }
+    }
+
+    /// 所有动作的绑定及元数据，按分类、动作 id 排序
+    pub fn list(&self) -> Vec<ActionBinding> {
+        let mut actions: Vec<ActionBinding> = self
+            .actions
+            .iter()
+            .map(|(id, action)| ActionBinding {
+                action: id.clone(),
+                scope: action.scope.clone(),
+                description: action.description.clone(),
+                category: action.category.clone(),
+                trigger: action.trigger,
+                defaults: action.defaults.clone(),
+                hotkeys: action.hotkeys.clone(),
+                default_labels: self.labels(&action.defaults),
+                labels: self.labels(&action.hotkeys),
+            })
+            .collect();
+        actions.sort_by(|a, b| (&a.category, &a.action).cmp(&(&b.category, &b.action)));
+        actions
+    }
+
+    /// 当前作用域栈，第一个总是 [`GLOBAL_SCOPE`]
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// 替换 [`GLOBAL_SCOPE`] 之上的作用域，越靠后越内层
+    pub fn set_scopes<S: Into<String>>(
+        &mut self,
+        scopes: impl IntoIterator<Item = S>,
+    ) -> Vec<MatchEvent> {
+        let mut stack = vec![GLOBAL_SCOPE.to_string()];
+        stack.extend(scopes.into_iter().map(Into::into));
+        let mut events = Vec::new();
+        if self.scopes != stack {
+            self.scopes = stack;
+            // 作用域变化后，旧作用域的序列前缀不再有意义
+            self.set_pending(Vec::new(), None, &mut events);
+        }
+        events
+    }
+
+    /// 压入内层作用域，已在栈中时移到栈顶
+    pub fn push_scope(&mut self, scope: &str) -> Vec<MatchEvent> {
+        if scope == GLOBAL_SCOPE {
+            return Vec::new();
+        }
+
+        let mut scopes = self.scopes.clone();
+        scopes.retain(|s| s != scope && s != GLOBAL_SCOPE);
+        scopes.push(scope.to_string());
+        self.set_scopes(scopes)
+    }
+
+    /// 移除作用域，[`GLOBAL_SCOPE`] 不会被移除
+    pub fn pop_scope(&mut self, scope: &str) -> Vec<MatchEvent> {
+        let mut scopes = self.scopes.clone();
+        scopes.retain(|s| s != scope && s != GLOBAL_SCOPE);
+        self.set_scopes(scopes)
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) -> Vec<MatchEvent> {
+        self.enabled = enabled;
+        let mut events = Vec::new();
+        if !enabled {
+            self.set_pending(Vec::new(), None, &mut events);
+            self.held = None;
+            self.modifier_tap = None;
+        }
+        events
+    }
+
+    pub fn set_sequence_timeout(&mut self, timeout: Duration) {
+        self.sequence_timeout = timeout;
+    }
+
+    pub fn layout(&self) -> &KeyboardLayout {
+        &self.layout
+    }
+
+    /// 切换键盘布局，逻辑按键随之对应到新的按键位置
+    pub fn set_layout(&mut self, layout: KeyboardLayout) {
+        self.layout = layout;
+    }
+
+    /// 正在等待后续按键的序列前缀
+    pub fn pending(&self) -> Option<KeySequence> {
+        (!self.pending.is_empty()).then(|| KeySequence(self.pending.clone()))
+    }
+
+    /// 窗口失焦等情况下收不到松开事件，清空已按下的键避免修饰键卡住
+    pub fn clear_pressed(&mut self) {
+        self.pressed_keys.clear();
+        self.held = None;
+        self.modifier_tap = None;
+    }
+
+    pub fn handle_key_event(
+        &mut self,
+        key: Code,
+        state: KeyState,
+        now: Instant,
+    ) -> Vec<MatchEvent> {
+        let mut events = Vec::new();
+        if state == KeyState::Down {
+            if self.pressed_keys.insert(key) && self.enabled {
+                if modifier(key).is_some() {
+                    // 只按下一个修饰键时记为候选，松开前按了其他键则作废
+                    self.modifier_tap = (self.pressed_keys.len() == 1).then_some(key);
+                } else {
+                    self.modifier_tap = None;
+                    // 键按下时检查快捷键
+                    let hotkey = self.match_hotkey(key);
+                    self.check_hotkey(hotkey, true, now, &mut events);
+                }
+            }
+        } else {
+            self.pressed_keys.remove(&key);
+            // 松开按键，松开触发方式的快捷键在此执行
+            if let Some(Held {
+                action,
+                trigger: Trigger::Release,
+                ..
+            }) = self.held.take()
+            {
+                events.push(MatchEvent::Action(action));
+            }
+            let tapped = self.modifier_tap.take_if(|tap| *tap == key).is_some();
+            if tapped && self.enabled {
+                let hotkey = Hotkey::new(Modifiers::empty(), key);
+                self.check_hotkey(hotkey, false, now, &mut events);
+            }
+        }
+        events
+    }
+
+    /// 鼠标按键按一次按下并松开的按键处理，可与键盘修饰键组合，也可作为序列的一部分
+    pub fn handle_mouse_button(&mut self, button: MouseButton, now: Instant) -> Vec<MatchEvent> {
+        let mut events = Vec::new();
+        if self.enabled {
+            self.modifier_tap = None;
+            let hotkey = self.match_hotkey(HotkeyKey::Mouse(button));
+            self.check_hotkey(hotkey, false, now, &mut events);
+        }
+        events
+    }
+
+    /// 完整的鼠标手势，只与整条笔画序列相同的绑定匹配，不产生序列前缀
+    pub fn handle_gesture(&mut self, strokes: &[Direction]) -> Vec<MatchEvent> {
+        let mut events = Vec::new();
+        if !self.enabled || strokes.is_empty() {
+            return events;
+        }
+
+        self.held = None;
+        self.modifier_tap = None;
+        self.last_tap = None;
+        self.set_pending(Vec::new(), None, &mut events);
+        let keys: Vec<Hotkey> = strokes
+            .iter()
+            .map(|direction| self.match_hotkey(HotkeyKey::Gesture(*direction)))
+            .collect();
+        if let Lookup::Matched(action, _) = self.lookup(&keys) {
+            events.push(MatchEvent::Action(action));
+        }
+        events
+    }
+
+    /// 处理到期的序列前缀与长按、重复
+    pub fn tick(&mut self, now: Instant) -> Vec<MatchEvent> {
+        let mut events = Vec::new();
+        if self.pending_expired(now) {
+            self.set_pending(Vec::new(), None, &mut events);
+        }
+
+        if let Some(held) = &mut self.held
+            && let Some(deadline) = held.deadline
+            && deadline <= now
+        {
+            events.push(MatchEvent::Action(held.action.clone()));
+            match held.trigger {
+                Trigger::Repeat(interval) => held.deadline = Some(now + interval),
+                _ => self.held = None,
+            }
+        }
+        events
+    }
+
+    /// 下一次需要调用 [`KeyMatcher::tick`] 的时间
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let pending = self
+            .pending_since
+            .map(|since| since + self.sequence_timeout);
+        let held = self.held.as_ref().and_then(|held| held.deadline);
+        pending.into_iter().chain(held).min()
+    }
+
+    /// 以刚按下的键为主键，已按下的修饰键为修饰符
+    fn match_hotkey(&self, key: impl Into<HotkeyKey>) -> Hotkey {
+        let mods = self
+            .pressed_keys
+            .iter()
+            .filter_map(|code| modifier(*code))
+            .fold(Modifiers::empty(), |mods, modifier| mods | modifier);
+        Hotkey {
+            mods,
+            key: key.into(),
+        }
+    }
+
+    /// `held` 为 `false` 时按键已松开，松开、长按、重复都只触发一次
+    fn check_hotkey(
+        &mut self,
+        hotkey: Hotkey,
+        held: bool,
+        now: Instant,
+        events: &mut Vec<MatchEvent>,
+    ) {
+        self.held = None;
+
+        let mut keys = if self.pending_expired(now) {
+            Vec::new()
+        } else {
+            self.pending.clone()
+        };
+        keys.push(hotkey);
+        let lookup = match self.lookup(&keys) {
+            // 前缀之后按了不相关的键，丢弃前缀重新匹配
+            Lookup::None if keys.len() > 1 => {
+                keys = vec![hotkey];
+                self.lookup(&keys)
+            }
+            lookup => lookup,
+        };
+
+        // 两次按下之间按了其他键时不算双击
+        if !matches!(lookup, Lookup::Matched(_, Trigger::DoubleTap(_))) {
+            self.last_tap = None;
+        }
+
+        match lookup {
+            Lookup::Matched(action, trigger) => {
+                self.set_pending(Vec::new(), None, events);
+                match trigger {
+                    Trigger::DoubleTap(interval) => {
+                        let double = self
+                            .last_tap
+                            .take()
+                            .is_some_and(|(last, at)| last == keys && now - at <= interval);
+                        if double {
+                            events.push(MatchEvent::Action(action));
+                        } else {
+                            self.last_tap = Some((keys, now));
+                        }
+                    }
+                    Trigger::Release if held => self.hold(action, trigger, None),
+                    Trigger::Hold(interval) if held => {
+                        self.hold(action, trigger, Some(now + interval));
+                    }
+                    Trigger::Repeat(interval) if held => {
+                        events.push(MatchEvent::Action(action.clone()));
+                        self.hold(action, trigger, Some(now + interval));
+                    }
+                    _ => events.push(MatchEvent::Action(action)),
+                }
+            }
+            Lookup::Prefix => self.set_pending(keys, Some(now), events),
+            Lookup::None => self.set_pending(Vec::new(), None, events),
+        }
+    }
+
+    fn hold(&mut self, action: String, trigger: Trigger, deadline: Option<Instant>) {
+        self.held = Some(Held {
+            action,
+            trigger,
+            deadline,
+        });
+    }
+
+    fn pending_expired(&self, now: Instant) -> bool {
+        self.pending_since
+            .is_some_and(|since| now - since >= self.sequence_timeout)
+    }
+
+    /// 更新序列前缀，有变化时输出通知
+    fn set_pending(
+        &mut self,
+        keys: Vec<Hotkey>,
+        now: Option<Instant>,
+        events: &mut Vec<MatchEvent>,
+    ) {
+        if self.pending.is_empty() && keys.is_empty() {
+            return;
+        }
+
+        self.pending_since = now.filter(|_| !keys.is_empty());
+        self.pending = keys.clone();
+        events.push(MatchEvent::Pending(
+            (!keys.is_empty()).then_some(KeySequence(keys)),
+        ));
+    }
+
+    /// 同一作用域内与 `sequence` 相同或互为前缀的已绑定序列，前缀会让较长的序列永远无法触发
+    fn conflict(
+        &self,
+        scope: &str,
+        sequence: &KeySequence,
+        action: &str,
+    ) -> Option<(&KeySequence, &String)> {
+        self.actions
+            .iter()
+            .filter(|(id, bound)| *id != action && bound.scope == scope)
+            .find_map(|(id, bound)| {
+                bound
+                    .hotkeys
+                    .iter()
+                    .find(|hotkey| self.overlaps(hotkey, sequence))
+                    .map(|hotkey| (hotkey, id))
+            })
+    }
+
+    /// 从内到外逐个作用域匹配，内层有匹配或前缀时不再查找外层
+    fn lookup(&self, keys: &[Hotkey]) -> Lookup {
+        for scope in self.scopes.iter().rev() {
+            let mut prefix = false;
+            for (id, action) in self
+                .actions
+                .iter()
+                .filter(|(_, action)| action.scope == *scope)
+            {
+                for hotkey in &action.hotkeys {
+                    let hotkey = self.resolve(hotkey);
+                    if hotkey == keys {
+                        return Lookup::Matched(id.clone(), action.trigger);
+                    }
+                    prefix |= hotkey.len() > keys.len() && hotkey.starts_with(keys);
+                }
+            }
+            if prefix {
+                return Lookup::Prefix;
+            }
+        }
+        Lookup::None
+    }
+
+    /// 逻辑按键按当前布局换成按键位置，与按下的键比较
+    fn resolve(&self, sequence: &KeySequence) -> Vec<Hotkey> {
+        sequence
+            .0
+            .iter()
+            .map(|hotkey| match hotkey.key {
+                HotkeyKey::Logical(c) => match self.layout.code(c) {
+                    Some(code) => Hotkey::new(hotkey.mods, code),
+                    None => *hotkey,
+                },
+                _ => *hotkey,
+            })
+            .collect()
+    }
+
+    /// 在当前布局下相同或互为前缀
+    fn overlaps(&self, a: &KeySequence, b: &KeySequence) -> bool {
+        let (a, b) = (self.resolve(a), self.resolve(b));
+        a.starts_with(&b) || b.starts_with(&a)
+    }
+
+    fn labels(&self, sequences: &[KeySequence]) -> Vec<String> {
+        sequences
+            .iter()
+            .map(|sequence| sequence.label(&self.layout))
+            .collect()
+    }
+}
+
+fn modifier(code: Code) -> Option<Modifiers> {
+    match code {
+        Code::ControlLeft | Code::ControlRight => Some(Modifiers::CONTROL),
+        Code::AltLeft | Code::AltRight => Some(Modifiers::ALT),
+        Code::ShiftLeft | Code::ShiftRight => Some(Modifiers::SHIFT),
+        Code::MetaLeft | Code::MetaRight => Some(Modifiers::META),
+        Code::Fn => Some(Modifiers::FN),
+        Code::CapsLock => Some(Modifiers::CAPS_LOCK),
+        Code::NumLock => Some(Modifiers::NUM_LOCK),
+        Code::ScrollLock => Some(Modifiers::SCROLL_LOCK),
+        Code::FnLock => Some(Modifiers::FN_LOCK),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(matcher: &mut KeyMatcher, keys: &[Code], now: Instant) -> Vec<MatchEvent> {
+        keys.iter()
+            .flat_map(|key| matcher.handle_key_event(*key, KeyState::Down, now))
+            .collect()
+    }
+
+    fn release(matcher: &mut KeyMatcher, keys: &[Code], now: Instant) -> Vec<MatchEvent> {
+        keys.iter()
+            .flat_map(|key| matcher.handle_key_event(*key, KeyState::Up, now))
+            .collect()
+    }
+
+    fn action(action: &str) -> Vec<MatchEvent> {
+        vec![MatchEvent::Action(action.to_string())]
+    }
+
+    fn matcher(bindings: &[(&str, &str, &str)]) -> KeyMatcher {
+        let mut matcher = KeyMatcher::new();
+        for (id, scope, hotkey) in bindings {
+            let hotkey: KeySequence = hotkey.parse().unwrap();
+            assert!(matcher.register(id, scope, [hotkey]).is_empty());
+        }
+        matcher
+    }
+
+    #[test]
+    fn test_left_right_modifier() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[("close_tab", GLOBAL_SCOPE, "ctrl+w")]);
+
+        assert_eq!(
+            press(&mut matcher, &[Code::ControlRight, Code::KeyW], now),
+            action("close_tab")
+        );
+        release(&mut matcher, &[Code::KeyW, Code::ControlRight], now);
+
+        // 两侧同时按住时松开一侧，另一侧仍算按住
+        press(&mut matcher, &[Code::ControlLeft, Code::ControlRight], now);
+        release(&mut matcher, &[Code::ControlLeft], now);
+        assert_eq!(press(&mut matcher, &[Code::KeyW], now), action("close_tab"));
+        release(&mut matcher, &[Code::KeyW, Code::ControlRight], now);
+        assert!(press(&mut matcher, &[Code::KeyW], now).is_empty());
+    }
+
+    #[test]
+    fn test_layout() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[
+            ("close_tab", GLOBAL_SCOPE, "ctrl+w"),
+            ("print", GLOBAL_SCOPE, "ctrl+KeyP"),
+            ("near_tab", GLOBAL_SCOPE, "ctrl+1"),
+        ]);
+        matcher.set_layout(KeyboardLayout::new([
+            (Code::KeyW, 'z'),
+            (Code::KeyZ, 'w'),
+            (Code::KeyP, 'p'),
+            (Code::Digit1, '&'),
+        ]));
+
+        // AZERTY 上 w 位于 KeyZ
+        press(&mut matcher, &[Code::ControlLeft], now);
+        assert!(press(&mut matcher, &[Code::KeyW], now).is_empty());
+        release(&mut matcher, &[Code::KeyW], now);
+        assert_eq!(press(&mut matcher, &[Code::KeyZ], now), action("close_tab"));
+        release(&mut matcher, &[Code::KeyZ], now);
+        assert_eq!(press(&mut matcher, &[Code::KeyP], now), action("print"));
+        release(&mut matcher, &[Code::KeyP], now);
+        // 布局中没有的字符退回 QWERTY 的位置
+        assert_eq!(
+            press(&mut matcher, &[Code::Digit1], now),
+            action("near_tab")
+        );
+
+        // 逻辑按键与按键位置在当前布局下相同也算冲突
+        assert!(
+            matcher
+                .rebind("print", vec!["ctrl+KeyZ".parse().unwrap()])
+                .is_err()
+        );
+        let close_tab = matcher.list().into_iter().find(|b| b.action == "close_tab");
+        assert_eq!(close_tab.unwrap().labels, ["Ctrl+W"]);
+    }
+
+    #[test]
+    fn test_mouse() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[
+            ("back", GLOBAL_SCOPE, "MouseBack"),
+            ("forward", GLOBAL_SCOPE, "GestureRight"),
+            ("close_tab", GLOBAL_SCOPE, "GestureDown GestureRight"),
+            ("reopen_tab", GLOBAL_SCOPE, "ctrl+MouseForward"),
+        ]);
+
+        assert_eq!(
+            matcher.handle_mouse_button(MouseButton::Back, now),
+            action("back")
+        );
+        assert!(
+            matcher
+                .handle_mouse_button(MouseButton::Forward, now)
+                .is_empty()
+        );
+        press(&mut matcher, &[Code::ControlLeft], now);
+        assert_eq!(
+            matcher.handle_mouse_button(MouseButton::Forward, now),
+            action("reopen_tab")
+        );
+        // 点击鼠标后松开 Ctrl 不算单独按下 Ctrl
+        release(&mut matcher, &[Code::ControlLeft], now);
+
+        // 手势整条匹配，前缀不会等待后续笔画
+        assert_eq!(
+            matcher.handle_gesture(&[Direction::Right]),
+            action("forward")
+        );
+        assert!(matcher.handle_gesture(&[Direction::Down]).is_empty());
+        assert_eq!(matcher.pending(), None);
+        assert_eq!(
+            matcher.handle_gesture(&[Direction::Down, Direction::Right]),
+            action("close_tab")
+        );
+        assert!(
+            matcher
+                .handle_gesture(&[Direction::Down, Direction::Right, Direction::Up])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_clear_pressed() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[("close_tab", GLOBAL_SCOPE, "ctrl+w")]);
+
+        // 失焦时收不到 Ctrl 松开，之后单按 W 会被当成 Ctrl+W
+        press(&mut matcher, &[Code::ControlLeft], now);
+        matcher.clear_pressed();
+        assert!(press(&mut matcher, &[Code::KeyW], now).is_empty());
+        release(&mut matcher, &[Code::KeyW], now);
+
+        // 清空后仍能正常按下
+        assert_eq!(
+            press(&mut matcher, &[Code::ControlLeft, Code::KeyW], now),
+            action("close_tab")
+        );
+    }
+
+    #[test]
+    fn test_sequence() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[
+            ("close_all", GLOBAL_SCOPE, "ctrl+k ctrl+w"),
+            ("print", GLOBAL_SCOPE, "ctrl+p"),
+        ]);
+        // 前缀是实际按下的按键位置
+        let prefix: KeySequence = "ctrl+KeyK".parse().unwrap();
+
+        assert_eq!(
+            press(&mut matcher, &[Code::ControlLeft, Code::KeyK], now),
+            vec![MatchEvent::Pending(Some(prefix.clone()))]
+        );
+        assert_eq!(matcher.pending(), Some(prefix));
+        release(&mut matcher, &[Code::KeyK], now);
+        assert_eq!(
+            press(&mut matcher, &[Code::KeyW], now),
+            vec![
+                MatchEvent::Pending(None),
+                MatchEvent::Action("close_all".to_string())
+            ]
+        );
+        release(&mut matcher, &[Code::KeyW], now);
+
+        // 前缀之后按了其他快捷键，丢弃前缀重新匹配
+        press(&mut matcher, &[Code::KeyK], now);
+        release(&mut matcher, &[Code::KeyK], now);
+        assert_eq!(
+            press(&mut matcher, &[Code::KeyP], now),
+            vec![
+                MatchEvent::Pending(None),
+                MatchEvent::Action("print".to_string())
+            ]
+        );
+        release(&mut matcher, &[Code::KeyP], now);
+
+        // 超时后前缀失效
+        press(&mut matcher, &[Code::KeyK], now);
+        release(&mut matcher, &[Code::KeyK], now);
+        assert_eq!(
+            matcher.next_deadline(),
+            Some(now + DEFAULT_SEQUENCE_TIMEOUT)
+        );
+        let later = now + DEFAULT_SEQUENCE_TIMEOUT;
+        assert_eq!(matcher.tick(later), vec![MatchEvent::Pending(None)]);
+        assert!(press(&mut matcher, &[Code::KeyW], later).is_empty());
+    }
+
+    #[test]
+    fn test_scope() {
+        let now = Instant::now();
+        let mut matcher = matcher(&[
+            ("blur", GLOBAL_SCOPE, "escape"),
+            ("exit_fullscreen", "fullscreen", "escape"),
+        ]);
+
+        assert_eq!(press(&mut matcher, &[Code::Escape], now), action("blur"));
+        release(&mut matcher, &[Code::Escape], now);
+
+        matcher.push_scope("fullscreen");
+        assert_eq!(
+            press(&mut matcher, &[Code::Escape], now),
+            action("exit_fullscreen")
+        );
+        release(&mut matcher, &[Code::Escape], now);
+
+        matcher.pop_scope("fullscreen");
+        assert_eq!(matcher.scopes(), [GLOBAL_SCOPE]);
+        assert_eq!(press(&mut matcher, &[Code::Escape], now), action("blur"));
+    }
+
+    #[test]
+    fn test_trigger() {
+        let now = Instant::now();
+        let interval = Duration::from_millis(300);
+        let mut matcher = matcher(&[
+            ("peek", GLOBAL_SCOPE, "alt+p"),
+            ("zoom", GLOBAL_SCOPE, "ctrl+equal"),
+            ("search", GLOBAL_SCOPE, "shift"),
+        ]);
+        matcher.set_trigger("peek", Trigger::Hold(interval));
+        matcher.set_trigger("zoom", Trigger::Repeat(interval));
+        matcher.set_trigger("search", Trigger::DoubleTap(interval));
+
+        // 长按：提前松开不触发
+        assert!(press(&mut matcher, &[Code::AltLeft, Code::KeyP], now).is_empty());
+        release(&mut matcher, &[Code::KeyP], now);
+        assert_eq!(matcher.next_deadline(), None);
+        press(&mut matcher, &[Code::KeyP], now);
+        assert_eq!(matcher.tick(now + interval), action("peek"));
+        assert!(matcher.tick(now + interval * 2).is_empty());
+        release(&mut matcher, &[Code::KeyP, Code::AltLeft], now);
+
+        // 重复：按下立即触发，按住期间按间隔重复
+        assert_eq!(
+            press(&mut matcher, &[Code::ControlLeft, Code::Equal], now),
+            action("zoom")
+        );
+        assert_eq!(matcher.tick(now + interval), action("zoom"));
+        assert_eq!(matcher.tick(now + interval * 2), action("zoom"));
+        release(&mut matcher, &[Code::Equal, Code::ControlLeft], now);
+        assert_eq!(matcher.next_deadline(), None);
+
+        // 双击：只有修饰键的快捷键单独按下并松开算一次
+        press(&mut matcher, &[Code::ShiftRight], now);
+        assert!(release(&mut matcher, &[Code::ShiftRight], now).is_empty());
+        press(&mut matcher, &[Code::ShiftLeft], now);
+        assert_eq!(
+            release(&mut matcher, &[Code::ShiftLeft], now + interval),
+            action("search")
+        );
+
+        // 间隔过长不算双击
+        press(&mut matcher, &[Code::ShiftLeft], now);
+        release(&mut matcher, &[Code::ShiftLeft], now);
+        press(&mut matcher, &[Code::ShiftLeft], now);
+        assert!(release(&mut matcher, &[Code::ShiftLeft], now + interval * 2).is_empty());
+    }
+
+    #[test]
+    fn test_rebind() {
+        let mut matcher = matcher(&[
+            ("close_tab", GLOBAL_SCOPE, "ctrl+w"),
+            ("print", GLOBAL_SCOPE, "ctrl+p"),
+        ]);
+
+        let conflict = matcher.rebind("print", vec!["ctrl+w".parse().unwrap()]);
+        assert!(
+            matches!(conflict, Err(BindingError::Conflict { action, .. }) if action == "close_tab")
+        );
+        let prefix = matcher.rebind("print", vec!["ctrl+w ctrl+p".parse().unwrap()]);
+        assert!(prefix.is_err());
+        assert!(matches!(
+            matcher.rebind("unknown", Vec::new()),
+            Err(BindingError::UnknownAction { .. })
+        ));
+
+        matcher.unbind("close_tab").unwrap();
+        matcher
+            .rebind("print", vec!["ctrl+w".parse().unwrap()])
+            .unwrap();
+        matcher.reset("print").unwrap();
+        let print = matcher
+            .list()
+            .into_iter()
+            .find(|b| b.action == "print")
+            .unwrap();
+        assert_eq!(print.hotkeys, print.defaults);
+    }
+
+    #[test]
+    fn test_rebind_all() {
+        let mut matcher = matcher(&[
+            ("close_tab", GLOBAL_SCOPE, "ctrl+w"),
+            ("print", GLOBAL_SCOPE, "ctrl+p"),
+            ("reload", GLOBAL_SCOPE, "ctrl+r"),
+        ]);
+        let hotkeys = |matcher: &KeyMatcher, action: &str| {
+            matcher
+                .list()
+                .into_iter()
+                .find(|b| b.action == action)
+                .unwrap()
+                .hotkeys
+        };
+
+        // 逐个 rebind 时互换会冲突，一起替换则成功
+        let errors = matcher.rebind_all(vec![
+            ("close_tab".to_string(), vec!["ctrl+p".parse().unwrap()]),
+            ("print".to_string(), vec!["ctrl+w".parse().unwrap()]),
+        ]);
+        assert!(errors.is_empty());
+        assert_eq!(hotkeys(&matcher, "close_tab"), ["ctrl+p".parse().unwrap()]);
+        assert_eq!(hotkeys(&matcher, "print"), ["ctrl+w".parse().unwrap()]);
+        assert_eq!(
+            press(
+                &mut matcher,
+                &[Code::ControlLeft, Code::KeyW],
+                Instant::now()
+            ),
+            action("print")
+        );
+
+        // 失败的动作保留原来的快捷键，不影响其他动作
+        let errors = matcher.rebind_all(vec![
+            ("reload".to_string(), vec!["ctrl+p".parse().unwrap()]),
+            ("unknown".to_string(), Vec::new()),
+            ("print".to_string(), Vec::new()),
+        ]);
+        assert_eq!(errors.len(), 2);
+        assert!(
+            matches!(&errors[0], (action, BindingError::Conflict { .. }) if action == "reload")
+        );
+        assert!(matches!(&errors[1].1, BindingError::UnknownAction { .. }));
+        assert_eq!(hotkeys(&matcher, "reload"), ["ctrl+r".parse().unwrap()]);
+        assert!(hotkeys(&matcher, "print").is_empty());
+    }
+}
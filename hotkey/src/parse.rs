@@ -0,0 +1,294 @@
+//! 快捷键字符串语法，`hotkey-macros` 以 `#[path]` 引用同一文件在编译期校验字符串参数，
+//! 因此只能依赖 `keyboard_types`
+
+use keyboard_types::{Code, Modifiers};
+
+/// 快捷键的主键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HotkeyKey {
+    /// 按键位置，如 `KeyW`，不随键盘布局变化
+    Physical(Code),
+    /// 当前键盘布局下输入的字符（小写），如 `w` 在 AZERTY 布局中位于 `KeyZ`
+    Logical(char),
+    /// 鼠标按键，如 `MouseBack`
+    Mouse(MouseButton),
+    /// 按住右键拖动的一笔，如 `GestureLeft`；多笔手势写成序列，如 `GestureDown GestureRight`
+    Gesture(Direction),
+}
+
+/// 由页面脚本上报的鼠标按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Back,
+    Forward,
+}
+
+/// 鼠标手势一笔的方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl From<Code> for HotkeyKey {
+    fn from(code: Code) -> Self {
+        Self::Physical(code)
+    }
+}
+
+impl From<char> for HotkeyKey {
+    fn from(c: char) -> Self {
+        Self::Logical(c.to_lowercase().next().unwrap_or(c))
+    }
+}
+
+pub(crate) enum ParseError {
+    UnsupportedKey { key: String },
+    EmptyToken { hotkey: String },
+    InvalidFormat { hotkey: String },
+}
+
+/// 空白分隔的快捷键序列，`+` 两侧的空白不作分隔，如 `Ctrl + K Ctrl + W`
+pub(crate) fn parse_sequence(sequence: &str) -> Result<Vec<(Modifiers, HotkeyKey)>, ParseError> {
+    let mut hotkeys = Vec::new();
+    let mut current = String::new();
+    let mut after_plus = false;
+    for token in sequence.split_whitespace() {
+        let joined = after_plus || token.starts_with('+');
+        if !joined && !current.is_empty() {
+            hotkeys.push(parse_hotkey(&current)?);
+            current.clear();
+        }
+        current.push_str(token);
+        after_plus = token.ends_with('+');
+    }
+
+    if current.is_empty() {
+        return Err(ParseError::EmptyToken {
+            hotkey: sequence.to_string(),
+        });
+    }
+    hotkeys.push(parse_hotkey(&current)?);
+    Ok(hotkeys)
+}
+
+pub(crate) fn parse_hotkey(hotkey: &str) -> Result<(Modifiers, HotkeyKey), ParseError> {
+    let tokens = hotkey.split('+').collect::<Vec<&str>>();
+
+    let mut mods = Modifiers::empty();
+    let mut key = None;
+
+    match tokens.len() {
+        // single key hotkey
+        1 => {
+            key = Some(parse_key(tokens[0])?);
+        }
+        // modifiers and key comobo hotkey
+        _ => {
+            for raw in tokens {
+                let token = raw.trim();
+
+                if token.is_empty() {
+                    return Err(ParseError::EmptyToken {
+                        hotkey: hotkey.to_string(),
+                    });
+                }
+
+                if key.is_some() {
+                    // At this point we have parsed the modifiers and a main key, so by reaching
+                    // this code, the function either received more than one main key or
+                    //  the hotkey is not in the right order
+                    // examples:
+                    // 1. "Ctrl+Shift+C+A" => only one main key should be allowd.
+                    // 2. "Ctrl+C+Shift" => wrong order
+                    return Err(ParseError::InvalidFormat {
+                        hotkey: hotkey.to_string(),
+                    });
+                }
+
+                match token.to_uppercase().as_str() {
+                    "OPTION" | "ALT" => {
+                        mods |= Modifiers::ALT;
+                    }
+                    "CONTROL" | "CTRL" => {
+                        mods |= Modifiers::CONTROL;
+                    }
+                    "COMMAND" | "CMD" | "META" | "SUPER" => {
+                        mods |= Modifiers::META;
+                    }
+                    "SHIFT" => {
+                        mods |= Modifiers::SHIFT;
+                    }
+                    #[cfg(target_os = "macos")]
+                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+                        mods |= Modifiers::META;
+                    }
+                    #[cfg(not(target_os = "macos"))]
+                    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+                        mods |= Modifiers::CONTROL;
+                    }
+                    _ => {
+                        key = Some(parse_key(token)?);
+                    }
+                }
+            }
+        }
+    }
+
+    let key = key.ok_or_else(|| ParseError::InvalidFormat {
+        hotkey: hotkey.to_string(),
+    })?;
+    Ok((mods, key))
+}
+
+/// 单个字符按当前键盘布局匹配，按键名称（如 `KeyW`、`Slash`）按位置匹配
+fn parse_key(key: &str) -> Result<HotkeyKey, ParseError> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(HotkeyKey::from(c));
+    }
+
+    match key.to_uppercase().as_str() {
+        "MOUSEBACK" => Ok(HotkeyKey::Mouse(MouseButton::Back)),
+        "MOUSEFORWARD" => Ok(HotkeyKey::Mouse(MouseButton::Forward)),
+        "GESTUREUP" => Ok(HotkeyKey::Gesture(Direction::Up)),
+        "GESTUREDOWN" => Ok(HotkeyKey::Gesture(Direction::Down)),
+        "GESTURELEFT" => Ok(HotkeyKey::Gesture(Direction::Left)),
+        "GESTURERIGHT" => Ok(HotkeyKey::Gesture(Direction::Right)),
+        _ => parse_code(key).map(HotkeyKey::Physical),
+    }
+}
+
+fn parse_code(key: &str) -> Result<Code, ParseError> {
+    use Code::*;
+    match key.to_uppercase().as_str() {
+        "BACKQUOTE" => Ok(Backquote),
+        "BACKSLASH" => Ok(Backslash),
+        "BRACKETLEFT" => Ok(BracketLeft),
+        "BRACKETRIGHT" => Ok(BracketRight),
+        "PAUSE" | "PAUSEBREAK" => Ok(Pause),
+        "COMMA" => Ok(Comma),
+        "DIGIT0" => Ok(Digit0),
+        "DIGIT1" => Ok(Digit1),
+        "DIGIT2" => Ok(Digit2),
+        "DIGIT3" => Ok(Digit3),
+        "DIGIT4" => Ok(Digit4),
+        "DIGIT5" => Ok(Digit5),
+        "DIGIT6" => Ok(Digit6),
+        "DIGIT7" => Ok(Digit7),
+        "DIGIT8" => Ok(Digit8),
+        "DIGIT9" => Ok(Digit9),
+        "EQUAL" => Ok(Equal),
+        "KEYA" => Ok(KeyA),
+        "KEYB" => Ok(KeyB),
+        "KEYC" => Ok(KeyC),
+        "KEYD" => Ok(KeyD),
+        "KEYE" => Ok(KeyE),
+        "KEYF" => Ok(KeyF),
+        "KEYG" => Ok(KeyG),
+        "KEYH" => Ok(KeyH),
+        "KEYI" => Ok(KeyI),
+        "KEYJ" => Ok(KeyJ),
+        "KEYK" => Ok(KeyK),
+        "KEYL" => Ok(KeyL),
+        "KEYM" => Ok(KeyM),
+        "KEYN" => Ok(KeyN),
+        "KEYO" => Ok(KeyO),
+        "KEYP" => Ok(KeyP),
+        "KEYQ" => Ok(KeyQ),
+        "KEYR" => Ok(KeyR),
+        "KEYS" => Ok(KeyS),
+        "KEYT" => Ok(KeyT),
+        "KEYU" => Ok(KeyU),
+        "KEYV" => Ok(KeyV),
+        "KEYW" => Ok(KeyW),
+        "KEYX" => Ok(KeyX),
+        "KEYY" => Ok(KeyY),
+        "KEYZ" => Ok(KeyZ),
+        "MINUS" => Ok(Minus),
+        "PERIOD" => Ok(Period),
+        "QUOTE" => Ok(Quote),
+        "SEMICOLON" => Ok(Semicolon),
+        "SLASH" => Ok(Slash),
+        "BACKSPACE" => Ok(Backspace),
+        "CAPSLOCK" => Ok(CapsLock),
+        "ENTER" => Ok(Enter),
+        "SPACE" => Ok(Space),
+        "TAB" => Ok(Tab),
+        "DELETE" => Ok(Delete),
+        "END" => Ok(End),
+        "HOME" => Ok(Home),
+        "INSERT" => Ok(Insert),
+        "PAGEDOWN" => Ok(PageDown),
+        "PAGEUP" => Ok(PageUp),
+        "PRINTSCREEN" => Ok(PrintScreen),
+        "SCROLLLOCK" => Ok(ScrollLock),
+        "ARROWDOWN" | "DOWN" => Ok(ArrowDown),
+        "ARROWLEFT" | "LEFT" => Ok(ArrowLeft),
+        "ARROWRIGHT" | "RIGHT" => Ok(ArrowRight),
+        "ARROWUP" | "UP" => Ok(ArrowUp),
+        "NUMLOCK" => Ok(NumLock),
+        "NUMPAD0" | "NUM0" => Ok(Numpad0),
+        "NUMPAD1" | "NUM1" => Ok(Numpad1),
+        "NUMPAD2" | "NUM2" => Ok(Numpad2),
+        "NUMPAD3" | "NUM3" => Ok(Numpad3),
+        "NUMPAD4" | "NUM4" => Ok(Numpad4),
+        "NUMPAD5" | "NUM5" => Ok(Numpad5),
+        "NUMPAD6" | "NUM6" => Ok(Numpad6),
+        "NUMPAD7" | "NUM7" => Ok(Numpad7),
+        "NUMPAD8" | "NUM8" => Ok(Numpad8),
+        "NUMPAD9" | "NUM9" => Ok(Numpad9),
+        "NUMPADADD" | "NUMADD" | "NUMPADPLUS" | "NUMPLUS" => Ok(NumpadAdd),
+        "NUMPADDECIMAL" | "NUMDECIMAL" => Ok(NumpadDecimal),
+        "NUMPADDIVIDE" | "NUMDIVIDE" => Ok(NumpadDivide),
+        "NUMPADENTER" | "NUMENTER" => Ok(NumpadEnter),
+        "NUMPADEQUAL" | "NUMEQUAL" => Ok(NumpadEqual),
+        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Ok(NumpadMultiply),
+        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Ok(NumpadSubtract),
+        "ESCAPE" | "ESC" => Ok(Escape),
+        "F1" => Ok(F1),
+        "F2" => Ok(F2),
+        "F3" => Ok(F3),
+        "F4" => Ok(F4),
+        "F5" => Ok(F5),
+        "F6" => Ok(F6),
+        "F7" => Ok(F7),
+        "F8" => Ok(F8),
+        "F9" => Ok(F9),
+        "F10" => Ok(F10),
+        "F11" => Ok(F11),
+        "F12" => Ok(F12),
+        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Ok(AudioVolumeDown),
+        "AUDIOVOLUMEUP" | "VOLUMEUP" => Ok(AudioVolumeUp),
+        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Ok(AudioVolumeMute),
+        "MEDIAPLAY" => Ok(MediaPlay),
+        "MEDIAPAUSE" => Ok(MediaPause),
+        "MEDIAPLAYPAUSE" => Ok(MediaPlayPause),
+        "MEDIASTOP" => Ok(MediaStop),
+        "MEDIATRACKNEXT" => Ok(MediaTrackNext),
+        "MEDIATRACKPREV" | "MEDIATRACKPREVIOUS" => Ok(MediaTrackPrevious),
+        "F13" => Ok(F13),
+        "F14" => Ok(F14),
+        "F15" => Ok(F15),
+        "F16" => Ok(F16),
+        "F17" => Ok(F17),
+        "F18" => Ok(F18),
+        "F19" => Ok(F19),
+        "F20" => Ok(F20),
+        "F21" => Ok(F21),
+        "F22" => Ok(F22),
+        "F23" => Ok(F23),
+        "F24" => Ok(F24),
+        // 只有修饰键的快捷键，左右修饰键视为同一个键
+        "SHIFT" | "SHIFTLEFT" | "SHIFTRIGHT" => Ok(ShiftLeft),
+        "CONTROL" | "CTRL" | "CONTROLLEFT" | "CONTROLRIGHT" => Ok(ControlLeft),
+        "ALT" | "OPTION" | "ALTLEFT" | "ALTRIGHT" => Ok(AltLeft),
+        "SUPER" | "META" | "COMMAND" | "CMD" | "METALEFT" | "METARIGHT" => Ok(MetaLeft),
+
+        _ => Err(ParseError::UnsupportedKey {
+            key: key.to_string(),
+        }),
+    }
+}
@@ -0,0 +1,2 @@
+alter table public_suffix_list add column etag text;
+alter table public_suffix_list add column last_modified text;
@@ -0,0 +1,7 @@
+alter table darkreader_blacklist add column by_site boolean not null default 0;
+
+update darkreader_blacklist set by_site = 1 where host in ('github.com', 'gitee.com');
+
+alter table navigation_log add column site text;
+
+create index if not exists navigation_log_site_index on navigation_log(site);
@@ -0,0 +1,10 @@
+create table if not exists http_client_config (
+    id integer primary key check (id = 1),
+    timeout integer not null default 30,
+    connect_timeout integer not null default 10,
+    max_redirects integer not null default 10,
+    proxy text,
+    ca_certificates text not null default ''
+);
+
+insert or ignore into http_client_config (id) values (1);
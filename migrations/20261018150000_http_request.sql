@@ -0,0 +1,32 @@
+create table if not exists http_request_log (
+    id integer primary key autoincrement,
+    method text not null,
+    url text not null,
+    headers text not null default '[]',
+    body text,
+    status integer,
+    elapsed_time integer,
+    error text,
+    create_time datetime not null
+);
+
+create index if not exists http_request_log_time_index on http_request_log(create_time);
+
+create table if not exists http_collection (
+    id integer primary key autoincrement,
+    parent_id integer references http_collection(id) on delete cascade,
+    name text not null
+);
+
+create table if not exists http_saved_request (
+    id integer primary key autoincrement,
+    collection_id integer not null references http_collection(id) on delete cascade,
+    name text not null,
+    method text not null,
+    url text not null,
+    headers text not null default '[]',
+    body text,
+    update_time datetime not null
+);
+
+create index if not exists http_saved_request_collection_index on http_saved_request(collection_id);
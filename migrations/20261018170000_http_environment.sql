@@ -0,0 +1,17 @@
+create table if not exists http_environment (
+    id integer primary key autoincrement,
+    name text not null,
+    active boolean not null default 0
+);
+
+create unique index if not exists http_environment_name_index on http_environment(name);
+
+create table if not exists http_variable (
+    id integer primary key autoincrement,
+    environment_id integer not null references http_environment(id) on delete cascade,
+    key text not null,
+    value text not null,
+    secret boolean not null default 0
+);
+
+create unique index if not exists http_variable_key_index on http_variable(environment_id, key);
@@ -0,0 +1,11 @@
+create table if not exists http_credential (
+    id integer primary key autoincrement,
+    name text not null,
+    auth text not null
+);
+
+create unique index if not exists http_credential_name_index on http_credential(name);
+
+alter table http_saved_request add column credential_id integer;
+
+alter table http_request_log add column credential_id integer;
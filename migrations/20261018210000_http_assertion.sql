@@ -0,0 +1,3 @@
+alter table http_saved_request add column assertions text not null default '[]';
+
+alter table http_saved_request add column captures text not null default '[]';
@@ -0,0 +1,4 @@
+create table if not exists hotkey_binding (
+    action text primary key not null,
+    hotkeys text not null default ''
+);
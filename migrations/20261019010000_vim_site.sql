@@ -0,0 +1,7 @@
+create table if not exists vim_site (
+    id integer primary key autoincrement,
+    host text not null,
+    by_site boolean not null default 0
+);
+
+create unique index if not exists vim_site_index on vim_site(host);
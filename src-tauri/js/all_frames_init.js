@@ -7,6 +7,81 @@
     }
   });
 
+  // 鼠标手势：按住右键拖动，每移动超过阈值记一笔方向，松开时交给后端匹配；画过手势时不弹出右键菜单
+  var GESTURE_THRESHOLD = 24;
+  var gesture = null;
+  var suppressContextMenu = false;
+
+  document.addEventListener(
+    "mousedown",
+    function (e) {
+      if (e.button === 2) {
+        gesture = { x: e.screenX, y: e.screenY, strokes: [] };
+      }
+    },
+    true,
+  );
+
+  document.addEventListener(
+    "mousemove",
+    function (e) {
+      if (!gesture || (e.buttons & 2) === 0) {
+        return;
+      }
+
+      var dx = e.screenX - gesture.x;
+      var dy = e.screenY - gesture.y;
+      if (Math.abs(dx) < GESTURE_THRESHOLD && Math.abs(dy) < GESTURE_THRESHOLD) {
+        return;
+      }
+      var direction =
+        Math.abs(dx) > Math.abs(dy)
+          ? dx > 0
+            ? "Right"
+            : "Left"
+          : dy > 0
+            ? "Down"
+            : "Up";
+      if (gesture.strokes[gesture.strokes.length - 1] !== direction) {
+        gesture.strokes.push(direction);
+      }
+      gesture.x = e.screenX;
+      gesture.y = e.screenY;
+    },
+    true,
+  );
+
+  // 鼠标侧键交给后端按绑定处理，阻止浏览器默认的前进后退
+  var MOUSE_BUTTONS = { 3: "Back", 4: "Forward" };
+
+  document.addEventListener(
+    "mouseup",
+    function (e) {
+      if (e.button === 2 && gesture) {
+        suppressContextMenu = gesture.strokes.length > 0;
+        if (suppressContextMenu) {
+          mouseGesture(gesture.strokes);
+        }
+        gesture = null;
+      } else if (MOUSE_BUTTONS[e.button]) {
+        e.preventDefault();
+        mouseButton(MOUSE_BUTTONS[e.button]);
+      }
+    },
+    true,
+  );
+
+  document.addEventListener(
+    "contextmenu",
+    function (e) {
+      if (suppressContextMenu) {
+        e.preventDefault();
+        suppressContextMenu = false;
+      }
+    },
+    true,
+  );
+
   document.addEventListener(
     "DOMContentLoaded",
     function () {
@@ -94,6 +169,14 @@
     webviewIpcInvoke("leave_picture_in_picture");
   }
 
+  function mouseGesture(strokes) {
+    webviewIpcInvoke("mouse_gesture", { strokes });
+  }
+
+  function mouseButton(button) {
+    webviewIpcInvoke("mouse_button", { button });
+  }
+
   function focusLink(url) {
     webviewIpcInvoke("focus_link", { url });
   }
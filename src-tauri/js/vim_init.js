@@ -0,0 +1,368 @@
+(function () {
+  // Vim 风格键盘浏览，由后端按站点开关。普通模式下的单键由后端快捷键处理，这里只阻止页面收到这些按键；
+  // 可编辑元素获得焦点时自动进入插入模式，链接提示与查找框显示期间按键留在页面中处理
+  var HINT_CHARS = "sadfjklewcmpgh";
+  var SCROLL_STEP = 60;
+  var CLICKABLE_SELECTOR = [
+    "a[href]",
+    "button",
+    "input",
+    "select",
+    "textarea",
+    "summary",
+    "label",
+    "[role=button]",
+    "[role=link]",
+    "[role=checkbox]",
+    "[role=tab]",
+    "[role=menuitem]",
+    "[onclick]",
+    "[contenteditable=''],[contenteditable=true]",
+    "[tabindex]:not([tabindex='-1'])",
+  ].join(",");
+  var NON_TEXT_INPUTS = [
+    "button",
+    "checkbox",
+    "color",
+    "file",
+    "hidden",
+    "image",
+    "radio",
+    "range",
+    "reset",
+    "submit",
+  ];
+
+  var enabled = false;
+  var mode = "normal";
+  // 按 i 进入的插入模式，焦点离开可编辑元素时不自动退出
+  var manualInsert = false;
+  var hints = null;
+  var findBar = null;
+  var query = "";
+
+  window.__WHITE_HOLE_VIM__ = {
+    enable: function () {
+      enabled = true;
+      manualInsert = false;
+      mode = "";
+      setMode(isEditable(document.activeElement) ? "insert" : "normal");
+    },
+    disable: function () {
+      enabled = false;
+      removeHints();
+      closeFind();
+      mode = "normal";
+    },
+    scroll: function (direction) {
+      scrollTarget().scrollBy(0, direction * SCROLL_STEP);
+    },
+    scrollToEdge: function (bottom) {
+      var target = scrollTarget();
+      target.scrollTo(target.scrollLeft, bottom ? target.scrollHeight : 0);
+    },
+    hints: showHints,
+    find: openFind,
+    findNext: function (backwards) {
+      if (query) {
+        window.find(query, false, backwards, true);
+      }
+    },
+    insert: function () {
+      manualInsert = true;
+      setMode("insert");
+    },
+  };
+
+  document.addEventListener(
+    "keydown",
+    function (e) {
+      if (!enabled || e.isComposing) {
+        return;
+      }
+
+      if (mode === "hint") {
+        hintKeydown(e);
+      } else if (mode === "insert") {
+        if (e.key === "Escape") {
+          manualInsert = false;
+          if (isEditable(document.activeElement)) {
+            document.activeElement.blur();
+          }
+          setMode("normal");
+        }
+      } else if (
+        !e.ctrlKey &&
+        !e.altKey &&
+        !e.metaKey &&
+        e.key.length === 1 &&
+        e.key !== " "
+      ) {
+        e.preventDefault();
+        e.stopImmediatePropagation();
+      }
+    },
+    true,
+  );
+
+  document.addEventListener(
+    "focusin",
+    function (e) {
+      if (enabled && mode === "normal" && isEditable(e.target)) {
+        setMode("insert");
+      }
+    },
+    true,
+  );
+
+  document.addEventListener(
+    "focusout",
+    function () {
+      // 等焦点落到新元素上再判断
+      setTimeout(function () {
+        if (
+          enabled &&
+          mode === "insert" &&
+          !manualInsert &&
+          !isEditable(document.activeElement)
+        ) {
+          setMode("normal");
+        }
+      });
+    },
+    true,
+  );
+
+  function setMode(newMode) {
+    if (mode === newMode) {
+      return;
+    }
+
+    mode = newMode;
+    if (enabled) {
+      webviewIpcInvoke("vim_mode_changed", { mode });
+    }
+  }
+
+  // iframe 中的输入无法在这里判断，获得焦点时按插入模式处理
+  function isEditable(element) {
+    if (!element) {
+      return false;
+    }
+    if (element.isContentEditable) {
+      return true;
+    }
+    switch (element.tagName) {
+      case "TEXTAREA":
+      case "SELECT":
+      case "IFRAME":
+        return true;
+      case "INPUT":
+        return NON_TEXT_INPUTS.indexOf(element.type) < 0;
+      default:
+        return false;
+    }
+  }
+
+  // 页面本身不能滚动时（如单页应用），滚动视口中心所在的可滚动容器
+  function scrollTarget() {
+    var root = document.scrollingElement || document.documentElement;
+    if (root.scrollHeight > root.clientHeight) {
+      return root;
+    }
+
+    var element = document.elementFromPoint(
+      window.innerWidth / 2,
+      window.innerHeight / 2,
+    );
+    for (; element && element !== document.body; element = element.parentElement) {
+      var overflow = getComputedStyle(element).overflowY;
+      if (
+        (overflow === "auto" || overflow === "scroll") &&
+        element.scrollHeight > element.clientHeight
+      ) {
+        return element;
+      }
+    }
+    return root;
+  }
+
+  function showHints(newTab) {
+    removeHints();
+    var elements = clickableElements(newTab);
+    if (elements.length === 0) {
+      return;
+    }
+
+    var container = document.createElement("div");
+    container.style.cssText =
+      "position:fixed;inset:0;pointer-events:none;z-index:2147483647";
+    var labels = hintLabels(elements.length);
+    var items = elements.map(function (element, i) {
+      var rect = element.getClientRects()[0];
+      var marker = document.createElement("span");
+      marker.textContent = labels[i].toUpperCase();
+      marker.style.cssText =
+        "position:fixed;padding:0 3px;border:1px solid #A16207;border-radius:3px;" +
+        "background:#FACC15;color:#1D232A;font:bold 12px/16px monospace;" +
+        "left:" +
+        Math.max(rect.left, 0) +
+        "px;top:" +
+        Math.max(rect.top, 0) +
+        "px";
+      container.appendChild(marker);
+      return { label: labels[i], element: element, marker: marker };
+    });
+    document.documentElement.appendChild(container);
+
+    hints = { container: container, items: items, typed: "", newTab: newTab };
+    setMode("hint");
+  }
+
+  function removeHints() {
+    if (hints) {
+      hints.container.remove();
+      hints = null;
+    }
+  }
+
+  function hintKeydown(e) {
+    e.preventDefault();
+    e.stopImmediatePropagation();
+
+    var key = e.key.toLowerCase();
+    if (e.key === "Escape") {
+      removeHints();
+      setMode("normal");
+      return;
+    } else if (e.key === "Backspace") {
+      hints.typed = hints.typed.slice(0, -1);
+    } else if (key.length === 1 && HINT_CHARS.indexOf(key) >= 0) {
+      hints.typed += key;
+    } else {
+      return;
+    }
+
+    var typed = hints.typed;
+    var matched = hints.items.filter(function (item) {
+      var match = item.label.startsWith(typed);
+      item.marker.style.display = match ? "" : "none";
+      return match;
+    });
+    if (matched.length === 0) {
+      removeHints();
+      setMode("normal");
+    } else if (matched.length === 1 && matched[0].label === typed) {
+      var newTab = hints.newTab;
+      removeHints();
+      if (newTab) {
+        // 后端只在链接提示模式下打开链接，并由它切回普通模式
+        mode = "normal";
+        vimOpenLink(matched[0].element.href);
+      } else {
+        setMode("normal");
+        selectHint(matched[0].element);
+      }
+    }
+  }
+
+  // 与鼠标点击一致，可编辑元素获得焦点后进入插入模式
+  function selectHint(element) {
+    element.focus();
+    if (!isEditable(element)) {
+      element.click();
+    }
+  }
+
+  function clickableElements(linksOnly) {
+    var selector = linksOnly ? "a[href]" : CLICKABLE_SELECTOR;
+    return Array.prototype.filter.call(
+      document.querySelectorAll(selector),
+      function (element) {
+        if (element.disabled || (linksOnly && !element.href.startsWith("http"))) {
+          return false;
+        }
+
+        var rect = element.getClientRects()[0];
+        if (
+          !rect ||
+          rect.width === 0 ||
+          rect.height === 0 ||
+          rect.bottom < 0 ||
+          rect.right < 0 ||
+          rect.top > window.innerHeight ||
+          rect.left > window.innerWidth
+        ) {
+          return false;
+        }
+        return getComputedStyle(element).visibility !== "hidden";
+      },
+    );
+  }
+
+  // 所有提示等长，避免一个提示是另一个的前缀
+  function hintLabels(count) {
+    var length = 1;
+    for (var total = HINT_CHARS.length; total < count; total *= HINT_CHARS.length) {
+      length++;
+    }
+
+    var labels = [];
+    for (var i = 0; i < count; i++) {
+      var label = "";
+      for (var n = i, j = 0; j < length; j++) {
+        label = HINT_CHARS[n % HINT_CHARS.length] + label;
+        n = Math.floor(n / HINT_CHARS.length);
+      }
+      labels.push(label);
+    }
+    return labels;
+  }
+
+  // 查找框获得焦点时处于插入模式，回车查找并关闭，之后用 n、N 跳到下一个、上一个结果
+  function openFind() {
+    closeFind();
+    findBar = document.createElement("input");
+    findBar.value = query;
+    findBar.placeholder = "查找";
+    findBar.style.cssText =
+      "position:fixed;left:8px;bottom:8px;z-index:2147483647;width:240px;padding:2px 6px;" +
+      "border:1px solid #A16207;border-radius:3px;background:#FFFFFF;color:#18181B;" +
+      "font:14px/20px sans-serif;outline:none";
+    findBar.addEventListener("keydown", function (e) {
+      if (e.key !== "Enter") {
+        return;
+      }
+
+      e.preventDefault();
+      query = findBar.value;
+      closeFind();
+      if (query) {
+        window.getSelection().removeAllRanges();
+        window.find(query, false, false, true);
+      }
+    });
+    findBar.addEventListener("blur", closeFind);
+    document.documentElement.appendChild(findBar);
+    findBar.focus();
+    findBar.select();
+  }
+
+  function closeFind() {
+    if (findBar) {
+      var bar = findBar;
+      findBar = null;
+      bar.remove();
+    }
+  }
+
+  function webviewIpcInvoke(cmd, payload = {}) {
+    window.__TAURI_INTERNALS__.invoke(cmd, payload, {
+      donotUseCustomProtocol: true,
+    });
+  }
+
+  function vimOpenLink(url) {
+    webviewIpcInvoke("vim_open_link", { url });
+  }
+})();
@@ -47,6 +47,151 @@
     );
   }
 
+  // 抓取页面发出的 fetch、XHR 请求，开关由后端注入，同源跳转后从 sessionStorage 恢复
+  try {
+    window.__WHITE_HOLE_CAPTURE__ =
+      sessionStorage.getItem("__white_hole_capture") === "1";
+  } catch (e) {
+    window.__WHITE_HOLE_CAPTURE__ = false;
+  }
+
+  window.fetch = (function (f) {
+    return function fetch(input, init) {
+      if (window.__WHITE_HOLE_CAPTURE__) {
+        captureFetch(input, init).catch(function () {});
+      }
+      return f.apply(this, arguments);
+    };
+  })(window.fetch);
+
+  XMLHttpRequest.prototype.open = (function (f) {
+    return function open(method, url) {
+      this.__whiteHoleRequest = {
+        method: method,
+        url: new URL(url, window.location.href).href,
+        headers: [],
+      };
+      return f.apply(this, arguments);
+    };
+  })(XMLHttpRequest.prototype.open);
+
+  XMLHttpRequest.prototype.setRequestHeader = (function (f) {
+    return function setRequestHeader(key, value) {
+      if (this.__whiteHoleRequest) {
+        this.__whiteHoleRequest.headers.push({ key: key, value: String(value) });
+      }
+      return f.apply(this, arguments);
+    };
+  })(XMLHttpRequest.prototype.setRequestHeader);
+
+  XMLHttpRequest.prototype.send = (function (f) {
+    return function send(body) {
+      var request = this.__whiteHoleRequest;
+      if (window.__WHITE_HOLE_CAPTURE__ && request) {
+        serializeBody(body)
+          .then(function (body) {
+            captureRequest(request.method, request.url, request.headers, body, "xhr");
+          })
+          .catch(function () {});
+      }
+      return f.apply(this, arguments);
+    };
+  })(XMLHttpRequest.prototype.send);
+
+  function captureFetch(input, init) {
+    init = init || {};
+    var isRequest = input instanceof Request;
+    var url = new URL(isRequest ? input.url : String(input), window.location.href)
+      .href;
+    var method = init.method || (isRequest ? input.method : "GET");
+    var headers = new Headers(isRequest ? input.headers : undefined);
+    new Headers(init.headers).forEach(function (value, key) {
+      headers.set(key, value);
+    });
+    var body =
+      init.body !== undefined
+        ? serializeBody(init.body)
+        : isRequest && input.body
+          ? input.clone().text()
+          : Promise.resolve(null);
+    return body.then(function (body) {
+      var list = [];
+      headers.forEach(function (value, key) {
+        list.push({ key: key, value: value });
+      });
+      captureRequest(method, url, list, body, "fetch");
+    });
+  }
+
+  function captureRequest(method, url, headers, body, initiator) {
+    var contentType = headers.find(function (header) {
+      return header.key.toLowerCase() === "content-type";
+    });
+    if (
+      typeof body === "string" &&
+      contentType &&
+      contentType.value.indexOf("json") >= 0
+    ) {
+      body = { json: body };
+    }
+    webviewIpcInvoke("capture_request", {
+      request: {
+        method: method,
+        url: url,
+        headers: headers,
+        body: body,
+        initiator: initiator,
+      },
+    });
+  }
+
+  // 转换为后端 FetchBody 的格式，表单中的文件无法重放，只保留普通字段
+  function serializeBody(body) {
+    if (body === undefined || body === null) {
+      return Promise.resolve(null);
+    }
+    if (typeof body === "string") {
+      return Promise.resolve(body);
+    }
+    if (body instanceof URLSearchParams) {
+      return Promise.resolve({ form: formFields(body) });
+    }
+    if (body instanceof FormData) {
+      return Promise.resolve({ multipart: formFields(body) });
+    }
+    if (body instanceof Blob) {
+      return body.arrayBuffer().then(toBase64);
+    }
+    if (body instanceof ArrayBuffer || ArrayBuffer.isView(body)) {
+      return Promise.resolve(toBase64(body));
+    }
+    if (body instanceof Document) {
+      return Promise.resolve(new XMLSerializer().serializeToString(body));
+    }
+    return Promise.resolve(String(body));
+  }
+
+  function formFields(entries) {
+    var fields = [];
+    entries.forEach(function (value, key) {
+      if (typeof value === "string") {
+        fields.push({ key: key, value: value });
+      }
+    });
+    return fields;
+  }
+
+  function toBase64(buffer) {
+    var bytes = ArrayBuffer.isView(buffer)
+      ? new Uint8Array(buffer.buffer, buffer.byteOffset, buffer.byteLength)
+      : new Uint8Array(buffer);
+    var binary = "";
+    for (var i = 0; i < bytes.length; i += 0x8000) {
+      binary += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+    }
+    return { base64: btoa(binary) };
+  }
+
   function webviewIpcInvoke(cmd, payload = {}) {
     window.__TAURI_INTERNALS__.invoke(cmd, payload, {
       donotUseCustomProtocol: true,
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{environment::capture, request::Response};
+
+/// 响应断言，保存在集合请求中，发送后或运行集合时校验
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Assertion {
+    Status {
+        equals: u16,
+    },
+    Header {
+        name: String,
+        contains: String,
+    },
+    /// `equals` 为空时只要求 JSONPath 有匹配值
+    JsonPath {
+        path: String,
+        equals: Option<String>,
+    },
+    ResponseTime {
+        under: i32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssertionResult {
+    pub assertion: Assertion,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl Assertion {
+    pub fn check(&self, response: &Response) -> AssertionResult {
+        let (passed, message) = match self {
+            Assertion::Status { equals } => (
+                response.status == *equals,
+                format!("状态码为 {}，期望 {equals}", response.status),
+            ),
+            Assertion::Header { name, contains } => {
+                let values: Vec<&str> = response
+                    .headers
+                    .iter()
+                    .filter(|header| header.key.eq_ignore_ascii_case(name))
+                    .map(|header| header.value.as_str())
+                    .collect();
+                if values.is_empty() {
+                    (false, format!("响应头 {name} 不存在"))
+                } else {
+                    (
+                        values.iter().any(|value| value.contains(contains.as_str())),
+                        format!(
+                            "响应头 {name} 为 {}，期望包含 {contains}",
+                            values.join(", ")
+                        ),
+                    )
+                }
+            }
+            Assertion::JsonPath { path, equals } => match capture(&response.body, path) {
+                Ok(value) => match equals {
+                    Some(equals) => (
+                        value == *equals,
+                        format!("{path} 为 {value}，期望 {equals}"),
+                    ),
+                    None => (true, format!("{path} 为 {value}")),
+                },
+                Err(e) => (false, format!("{path}：{e}")),
+            },
+            Assertion::ResponseTime { under } => (
+                response.elapsed_time < *under,
+                format!("耗时 {} ms，期望小于 {under} ms", response.elapsed_time),
+            ),
+        };
+        AssertionResult {
+            assertion: self.clone(),
+            passed,
+            message,
+        }
+    }
+}
+
+pub fn check_all(assertions: &[Assertion], response: &Response) -> Vec<AssertionResult> {
+    assertions
+        .iter()
+        .map(|assertion| assertion.check(response))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::request::HttpHeader;
+
+    #[test]
+    fn test_check() {
+        let response = Response {
+            done_date: OffsetDateTime::UNIX_EPOCH,
+            status: 201,
+            headers: vec![HttpHeader {
+                key: "Content-Type".to_string(),
+                value: "application/json; charset=utf-8".to_string(),
+            }],
+            body: r#"{"data":{"id":7,"name":"白洞"}}"#.as_bytes().to_vec(),
+            elapsed_time: 120,
+        };
+        let assertions = [
+            Assertion::Status { equals: 201 },
+            Assertion::Header {
+                name: "content-type".to_string(),
+                contains: "json".to_string(),
+            },
+            Assertion::JsonPath {
+                path: "$.data.name".to_string(),
+                equals: Some("白洞".to_string()),
+            },
+            Assertion::JsonPath {
+                path: "$.data.id".to_string(),
+                equals: None,
+            },
+            Assertion::ResponseTime { under: 100 },
+            Assertion::JsonPath {
+                path: "$.data.missing".to_string(),
+                equals: None,
+            },
+            Assertion::Header {
+                name: "etag".to_string(),
+                contains: String::new(),
+            },
+        ];
+
+        let passed: Vec<bool> = check_all(&assertions, &response)
+            .iter()
+            .map(|result| result.passed)
+            .collect();
+        assert_eq!(passed, [true, true, true, true, false, false, false]);
+    }
+}
@@ -0,0 +1,494 @@
+use std::time::{Duration, Instant};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use reqwest::Client;
+use scc::HashMap;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, types::Json};
+
+use crate::{
+    error::AuthError,
+    request::{self, Authorization, FetchBody, FetchOptions, FormField, HttpHeader},
+};
+
+/// 令牌提前失效的余量，避免请求途中过期
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// 认证方式；返回给前端时隐藏私密字段，保存时私密字段为空表示保持原值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Auth {
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    Bearer {
+        token: Option<String>,
+    },
+    ApiKey {
+        key: String,
+        value: Option<String>,
+        location: ApiKeyLocation,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        grant: OAuth2Grant,
+        token_url: String,
+        client_id: String,
+        client_secret: Option<String>,
+        /// 仅 password 授权使用
+        #[serde(default)]
+        username: String,
+        password: Option<String>,
+        #[serde(default)]
+        scope: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuth2Grant {
+    ClientCredentials,
+    Password,
+}
+
+impl Auth {
+    fn mask(&mut self) {
+        match self {
+            Auth::Basic { password, .. } => *password = None,
+            Auth::Bearer { token } => *token = None,
+            Auth::ApiKey { value, .. } => *value = None,
+            Auth::OAuth2 {
+                client_secret,
+                password,
+                ..
+            } => {
+                *client_secret = None;
+                *password = None;
+            }
+        }
+    }
+
+    /// 认证方式未变时，沿用未填写的私密字段
+    fn keep_secrets(&mut self, old: Auth) {
+        match (self, old) {
+            (Auth::Basic { password, .. }, Auth::Basic { password: old, .. }) => {
+                keep(password, old)
+            }
+            (Auth::Bearer { token }, Auth::Bearer { token: old }) => keep(token, old),
+            (Auth::ApiKey { value, .. }, Auth::ApiKey { value: old, .. }) => keep(value, old),
+            (
+                Auth::OAuth2 {
+                    client_secret,
+                    password,
+                    ..
+                },
+                Auth::OAuth2 {
+                    client_secret: old_secret,
+                    password: old_password,
+                    ..
+                },
+            ) => {
+                keep(client_secret, old_secret);
+                keep(password, old_password);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn keep(value: &mut Option<String>, old: Option<String>) {
+    if value.is_none() {
+        *value = old;
+    }
+}
+
+/// 认证凭据，与保存的请求分开存放，请求只引用 id
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Credential {
+    pub id: Option<i64>,
+    pub name: String,
+    pub auth: Json<Auth>,
+}
+
+pub async fn query_credentials(pool: &SqlitePool) -> Result<Vec<Credential>, sqlx::Error> {
+    let mut credentials = sqlx::query_as!(
+        Credential,
+        r#"select id as "id?", name, auth as "auth: Json<Auth>" from http_credential order by name"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for credential in credentials.iter_mut() {
+        credential.auth.mask();
+    }
+
+    Ok(credentials)
+}
+
+/// 含私密字段，仅供后端使用
+pub async fn get_credential(pool: &SqlitePool, id: i64) -> Result<Option<Credential>, sqlx::Error> {
+    sqlx::query_as!(
+        Credential,
+        r#"select id as "id?", name, auth as "auth: Json<Auth>" from http_credential where id = ?"#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn save_credential(
+    pool: &SqlitePool,
+    credential: Credential,
+) -> Result<i64, sqlx::Error> {
+    let Credential { id, name, mut auth } = credential;
+
+    if let Some(id) = id {
+        if let Some(old) = get_credential(pool, id).await? {
+            auth.keep_secrets(old.auth.0);
+        }
+        sqlx::query!(
+            "update http_credential set name = ?, auth = ? where id = ?",
+            name,
+            auth,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(id)
+    } else {
+        sqlx::query!(
+            "insert into http_credential (name, auth) values (?, ?)",
+            name,
+            auth
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.last_insert_rowid())
+    }
+}
+
+/// 引用该凭据的请求改为不认证
+pub async fn delete_credential(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_credential where id = ?", id)
+        .execute(pool)
+        .await?;
+    sqlx::query!(
+        "update http_saved_request set credential_id = null where credential_id = ?",
+        id
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// OAuth2 令牌缓存，按凭据 id 保存到过期为止
+#[derive(Default)]
+pub struct TokenCache(HashMap<i64, Token>);
+
+#[derive(Clone)]
+struct Token {
+    access_token: String,
+    /// 为空表示服务端未给出有效期
+    expires_at: Option<Instant>,
+}
+
+impl Token {
+    fn is_valid(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| Instant::now() < expires_at)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl TokenCache {
+    /// 将凭据解析为要注入请求的认证信息，OAuth2 令牌过期时重新获取
+    pub async fn authorize(
+        &self,
+        client: &Client,
+        credential: &Credential,
+    ) -> Result<Authorization, AuthError> {
+        let authorization = match &credential.auth.0 {
+            Auth::Basic { username, password } => {
+                let password = password.as_deref().unwrap_or_default();
+                Authorization::Header {
+                    key: "authorization".to_string(),
+                    value: format!(
+                        "Basic {}",
+                        STANDARD.encode(format!("{username}:{password}"))
+                    ),
+                }
+            }
+            Auth::Bearer { token } => bearer(token.as_deref().unwrap_or_default()),
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => {
+                let (key, value) = (key.clone(), value.clone().unwrap_or_default());
+                match location {
+                    ApiKeyLocation::Header => Authorization::Header { key, value },
+                    ApiKeyLocation::Query => Authorization::Query { key, value },
+                }
+            }
+            Auth::OAuth2 { .. } => {
+                let id = credential.id.unwrap_or_default();
+                let cached = self
+                    .0
+                    .read_async(&id, |_, token| token.clone())
+                    .await
+                    .filter(Token::is_valid);
+                let token = match cached {
+                    Some(token) => token,
+                    None => {
+                        let token = fetch_token(client, &credential.auth.0).await?;
+                        self.0.upsert_async(id, token.clone()).await;
+                        token
+                    }
+                };
+                bearer(&token.access_token)
+            }
+        };
+        Ok(authorization)
+    }
+
+    /// 凭据修改或删除后丢弃缓存的令牌
+    pub async fn invalidate(&self, id: i64) {
+        self.0.remove_async(&id).await;
+    }
+}
+
+fn bearer(token: &str) -> Authorization {
+    Authorization::Header {
+        key: "authorization".to_string(),
+        value: format!("Bearer {token}"),
+    }
+}
+
+async fn fetch_token(client: &Client, auth: &Auth) -> Result<Token, AuthError> {
+    let Auth::OAuth2 {
+        grant,
+        token_url,
+        client_id,
+        client_secret,
+        username,
+        password,
+        scope,
+    } = auth
+    else {
+        return Err(AuthError::NoAccessToken);
+    };
+
+    let field = |key: &str, value: &str| FormField {
+        key: key.to_string(),
+        value: value.to_string(),
+        file: false,
+    };
+    let mut form = vec![
+        field(
+            "grant_type",
+            match grant {
+                OAuth2Grant::ClientCredentials => "client_credentials",
+                OAuth2Grant::Password => "password",
+            },
+        ),
+        field("client_id", client_id),
+    ];
+    if let Some(client_secret) = client_secret {
+        form.push(field("client_secret", client_secret));
+    }
+    if *grant == OAuth2Grant::Password {
+        form.push(field("username", username));
+        form.push(field("password", password.as_deref().unwrap_or_default()));
+    }
+    if !scope.is_empty() {
+        form.push(field("scope", scope));
+    }
+
+    let options = FetchOptions {
+        method: Some("POST".to_string()),
+        headers: Some(vec![HttpHeader {
+            key: "accept".to_string(),
+            value: "application/json".to_string(),
+        }]),
+        body: Some(FetchBody::Form { form }),
+        ..Default::default()
+    };
+    let requested_at = Instant::now();
+    let response = request::fetch(client, token_url, Some(options), false).await?;
+    if !(200..300).contains(&response.status) {
+        return Err(AuthError::TokenStatus {
+            status: response.status,
+        });
+    }
+
+    let TokenResponse {
+        access_token,
+        expires_in,
+    } = serde_json::from_slice(&response.body).map_err(|_| AuthError::NoAccessToken)?;
+    Ok(Token {
+        access_token,
+        expires_at: expires_in.map(|expires_in| {
+            requested_at + Duration::from_secs(expires_in).saturating_sub(EXPIRY_MARGIN)
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::async_runtime;
+
+    use super::*;
+    use crate::request::tests::{ok, serve};
+
+    fn oauth2(token_url: &str) -> Auth {
+        Auth::OAuth2 {
+            grant: OAuth2Grant::ClientCredentials,
+            token_url: token_url.to_string(),
+            client_id: "white-hole".to_string(),
+            client_secret: Some("secret".to_string()),
+            username: String::new(),
+            password: None,
+            scope: "read".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_credential() {
+        async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let id = save_credential(
+                &pool,
+                Credential {
+                    id: None,
+                    name: "api".to_string(),
+                    auth: Json(Auth::Basic {
+                        username: "user".to_string(),
+                        password: Some("pass".to_string()),
+                    }),
+                },
+            )
+            .await
+            .unwrap();
+
+            let credentials = query_credentials(&pool).await.unwrap();
+            assert_eq!(
+                credentials[0].auth.0,
+                Auth::Basic {
+                    username: "user".to_string(),
+                    password: None,
+                }
+            );
+
+            // 未填写密码时保持原值
+            let mut credential = credentials[0].clone();
+            credential.name = "renamed".to_string();
+            save_credential(&pool, credential).await.unwrap();
+            let credential = get_credential(&pool, id).await.unwrap().unwrap();
+            assert_eq!(credential.name, "renamed");
+
+            let authorization = TokenCache::default()
+                .authorize(&Client::new(), &credential)
+                .await
+                .unwrap();
+            assert_eq!(
+                authorization,
+                Authorization::Header {
+                    key: "authorization".to_string(),
+                    value: "Basic dXNlcjpwYXNz".to_string(),
+                }
+            );
+
+            delete_credential(&pool, id).await.unwrap();
+            assert!(get_credential(&pool, id).await.unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_oauth2() {
+        async_runtime::block_on(async {
+            let (url, server) = serve(ok(r#"{"access_token":"abc","expires_in":3600}"#));
+            let credential = Credential {
+                id: Some(1),
+                name: "oauth2".to_string(),
+                auth: Json(oauth2(&url)),
+            };
+            let cache = TokenCache::default();
+            let client = Client::new();
+
+            let expected = bearer("abc");
+            assert_eq!(
+                cache.authorize(&client, &credential).await.unwrap(),
+                expected
+            );
+            let (_, body) = server.join().unwrap();
+            assert_eq!(
+                String::from_utf8(body).unwrap(),
+                "grant_type=client_credentials&client_id=white-hole&client_secret=secret&scope=read"
+            );
+
+            // 令牌未过期时不再请求
+            assert_eq!(
+                cache.authorize(&client, &credential).await.unwrap(),
+                expected
+            );
+
+            cache.invalidate(1).await;
+            let (url, server) = serve(
+                "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            );
+            let credential = Credential {
+                auth: Json(oauth2(&url)),
+                ..credential
+            };
+            let result = cache.authorize(&client, &credential).await;
+            assert!(matches!(
+                result,
+                Err(AuthError::TokenStatus { status: 401 })
+            ));
+            server.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_authorization() {
+        async_runtime::block_on(async {
+            let (url, server) = serve(ok("done"));
+            let options = FetchOptions {
+                headers: Some(vec![HttpHeader {
+                    key: "X-Api-Key".to_string(),
+                    value: "manual".to_string(),
+                }]),
+                authorization: Some(Authorization::Header {
+                    key: "x-api-key".to_string(),
+                    value: "injected".to_string(),
+                }),
+                ..Default::default()
+            };
+            request::fetch(&Client::new(), &url, Some(options), false)
+                .await
+                .unwrap();
+            let (headers, _) = server.join().unwrap();
+            let keys: Vec<_> = headers
+                .iter()
+                .filter(|header| header.starts_with("x-api-key:"))
+                .collect();
+            assert_eq!(keys, ["x-api-key: injected"]);
+        });
+    }
+}
@@ -1,17 +1,33 @@
 use crate::{
     IsMainView,
+    auth::{self, Credential, TokenCache},
+    capture::{CaptureBuffer, CapturedRequest, PageRequest},
+    collection::{self, Collection, SavedRequest},
     darkreader::{self, delete_blacklist, save_blacklist},
     database::Database,
+    environment::{self, Environment, Variable},
     error::*,
+    har::{self, Har, HarSource},
+    hotkey_binding::{self, HotkeyBinding},
+    http_client::{HttpClient, HttpClientConfig, HttpClientExt as _},
     icon::{get_cached_icon, get_icon_data_url},
     log::{NavigationLog, QueryLogResponse, get_id, get_url, query_log, save_log, update_log_star},
     page::PageToken,
     public_suffix::get_public_suffix_cached,
+    request::{self, FetchOptions, FetchRegistry, Response},
+    request_log::{self, QueryRequestLogResponse},
+    runner::{RequestResult, RunReport},
+    site::get_site,
     state::{Boolean, BrowserState},
     tab::{Tab, TabIndex, TabMap},
     task,
-    url::parse_keyword,
+    url::{display_url, parse_keyword},
+    vim::{self, VimCommand, VimMode},
+    websocket::WebSocketRegistry,
 };
+use std::{collections::HashMap, fmt::Display};
+
+use ::hotkey::{Code, Direction, HotkeyManagerExt as _, KeySequence, KeyboardLayout};
 use log::error;
 use tauri::{
     App, Emitter as _, LogicalPosition, Manager, State, Url, Webview, WebviewBuilder, WebviewUrl,
@@ -26,6 +42,13 @@ const WIDTH: f64 = 800.;
 const HEIGHT: f64 = 600.;
 const FOCUS_LINK_TITLE: &str = "点击链接：";
 const LOADING_TITLE: &str = "正在加载……";
+const KEY_SEQUENCE_TITLE: &str = "等待下一个按键：";
+/// 快捷键作用域：主视图获得焦点、标签页获得焦点、全屏
+const MAINVIEW_SCOPE: &str = "mainview";
+const TAB_SCOPE: &str = "tab";
+const FULLSCREEN_SCOPE: &str = "fullscreen";
+/// 开启了 Vim 模式的标签页处于普通模式
+const VIM_SCOPE: &str = "vim";
 
 pub struct Browser {
     db: Database,
@@ -36,6 +59,8 @@ pub struct Browser {
     is_focused: Boolean,
     incognito: Boolean,
     last_focus_changed: Mutex<Instant>,
+    tokens: TokenCache,
+    captures: CaptureBuffer,
 }
 
 impl Browser {
@@ -60,6 +85,20 @@ impl Browser {
             )?;
 
             let db = Database::new(app).await?;
+            if let Err(e) = hotkey_binding::load_bindings(&db.storage(), app.hotkey()).await {
+                error!("读取快捷键绑定失败：{e}");
+            }
+            app.hotkey().on_pending(|app_handle, pending| {
+                async_runtime::spawn(async move {
+                    let browser = app_handle.browser();
+                    if let Err(e) = browser.key_sequence_pending(pending).await {
+                        error!("显示快捷键序列失败：{e}");
+                    }
+                });
+            });
+            app.manage(HttpClient::new(&db.get().await).await?);
+            app.manage(FetchRegistry::default());
+            app.manage(WebSocketRegistry::default());
 
             let state = Browser {
                 db,
@@ -70,10 +109,12 @@ impl Browser {
                 is_focused: Boolean::default(),
                 incognito: Boolean::default(),
                 last_focus_changed: Mutex::new(Instant::now()),
+                tokens: TokenCache::default(),
+                captures: CaptureBuffer::default(),
             };
             app.manage(state);
 
-            task::setup()?;
+            task::setup(app.handle().clone())?;
 
             Ok(())
         })
@@ -96,10 +137,6 @@ impl Browser {
     }
 
     pub async fn close_tab(&self) -> Result<(), TabError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         self.tabs.close(&label).await?;
         self.label.clear().await;
@@ -160,10 +197,6 @@ impl Browser {
     }
 
     pub async fn next_tab(&self) -> Result<(), TabError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         if let Some(next_label) = self.tabs.next(&label).await {
             self.switch_tab(&next_label).await?;
@@ -174,10 +207,6 @@ impl Browser {
     }
 
     pub async fn near_tab(&self) -> Result<(), TabError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         if let Some(near_label) = self.tabs.near(&label).await {
             self.switch_tab(&near_label).await?;
@@ -212,9 +241,13 @@ impl Browser {
         icon_url: String,
     ) -> Result<(), StateError> {
         self.tabs.set_icon(label, icon_url).await;
+        if let Err(e) = self.tabs.restore_capture(label).await {
+            error!("恢复请求抓取失败：{e}");
+        }
 
         let mut state = self.get_state(Some(label)).await?;
         self.darkreader_auto_switch(label, &mut state).await;
+        self.vim_auto_switch(label, &mut state).await;
 
         if self.is_current_tab(label).await {
             self.state_changed(Some(state.clone())).await?;
@@ -357,10 +390,6 @@ impl Browser {
     }
 
     pub async fn back(&self) -> Result<(), StateError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         if label.is_empty() {
             return Ok(());
@@ -374,10 +403,6 @@ impl Browser {
     }
 
     pub async fn forward(&self) -> Result<(), StateError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         if label.is_empty() {
             return Ok(());
@@ -391,6 +416,7 @@ impl Browser {
     }
 
     pub async fn go(&self, index: usize) -> Result<(), StateError> {
+        // 只由 go 命令调用，没有热键作用域兜底，搜索视图遮住页面时不跳转
         if self.is_focused.get().await {
             return Ok(());
         }
@@ -408,10 +434,6 @@ impl Browser {
     }
 
     pub async fn reload(&self) -> Result<(), StateError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         let label = self.label.get().await;
         if label.is_empty() {
             return Ok(());
@@ -439,10 +461,6 @@ impl Browser {
     }
 
     pub async fn fullscreen(&self) -> Result<(), FrameworkError> {
-        if self.is_focused.get().await {
-            return Ok(());
-        }
-
         self.fullscreen_changed(!self.window.is_fullscreen()?).await
     }
 
@@ -455,10 +473,384 @@ impl Browser {
     pub async fn query_navigation_log(
         &self,
         keyword: String,
+        group_by_site: bool,
         page_token: PageToken,
     ) -> Result<QueryLogResponse, DatabaseError> {
         let pool = self.db.get().await;
-        Ok(query_log(&pool, &keyword, page_token).await?)
+        Ok(query_log(&pool, &keyword, group_by_site, page_token).await?)
+    }
+
+    pub async fn query_request_log(
+        &self,
+        keyword: String,
+        page_token: PageToken,
+    ) -> Result<QueryRequestLogResponse, DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(request_log::query_request_log(&pool, &keyword, page_token).await?)
+    }
+
+    pub async fn save_request_log(
+        &self,
+        url: &str,
+        options: Option<&FetchOptions>,
+        result: &Result<Response, impl Display>,
+    ) {
+        let pool = self.db.get().await;
+        if let Err(e) = request_log::save_request_log(&pool, url, options, result).await {
+            error!("保存请求记录失败：{e}");
+        }
+    }
+
+    pub async fn delete_request_log(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(request_log::delete_request_log(&pool, id).await?)
+    }
+
+    pub async fn replay_request_log(
+        &self,
+        id: i64,
+        fetch_registry: &FetchRegistry,
+        label: &str,
+        request_id: Option<String>,
+    ) -> Result<Response, ReplayError> {
+        let pool = self.db.get().await;
+        let log = request_log::get_request_log(&pool, id).await?;
+        Ok(self
+            .fetch_and_log(
+                &log.url,
+                log.to_options(),
+                fetch_registry,
+                label,
+                request_id,
+            )
+            .await?)
+    }
+
+    pub async fn query_collections(&self) -> Result<Vec<Collection>, DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(collection::query_collections(&pool).await?)
+    }
+
+    pub async fn save_collection(
+        &self,
+        id: Option<i64>,
+        parent_id: Option<i64>,
+        name: String,
+    ) -> Result<i64, DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(collection::save_collection(&pool, id, parent_id, &name).await?)
+    }
+
+    pub async fn delete_collection(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(collection::delete_collection(&pool, id).await?)
+    }
+
+    pub async fn save_request(&self, request: SavedRequest) -> Result<i64, DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(collection::save_request(&pool, request).await?)
+    }
+
+    pub async fn delete_saved_request(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.get().await;
+        Ok(collection::delete_saved_request(&pool, id).await?)
+    }
+
+    pub async fn replay_saved_request(
+        &self,
+        id: i64,
+        fetch_registry: &FetchRegistry,
+        label: &str,
+        request_id: Option<String>,
+    ) -> Result<Response, ReplayError> {
+        let pool = self.db.get().await;
+        let request = collection::get_saved_request(&pool, id).await?;
+        Ok(self
+            .fetch_and_log(
+                &request.url,
+                request.to_options(),
+                fetch_registry,
+                label,
+                request_id,
+            )
+            .await?)
+    }
+
+    pub async fn query_environments(&self) -> Result<Vec<Environment>, DatabaseError> {
+        // 环境与客户端配置一样不受无痕模式影响
+        let pool = self.db.storage();
+        Ok(environment::query_environments(&pool).await?)
+    }
+
+    pub async fn save_environment(
+        &self,
+        id: Option<i64>,
+        name: String,
+    ) -> Result<i64, DatabaseError> {
+        let pool = self.db.storage();
+        Ok(environment::save_environment(&pool, id, &name).await?)
+    }
+
+    pub async fn delete_environment(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.storage();
+        Ok(environment::delete_environment(&pool, id).await?)
+    }
+
+    pub async fn set_active_environment(&self, id: Option<i64>) -> Result<(), DatabaseError> {
+        let pool = self.db.storage();
+        Ok(environment::set_active_environment(&pool, id).await?)
+    }
+
+    pub async fn save_variable(&self, variable: Variable) -> Result<i64, DatabaseError> {
+        let pool = self.db.storage();
+        Ok(environment::save_variable(&pool, variable).await?)
+    }
+
+    pub async fn delete_variable(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.storage();
+        Ok(environment::delete_variable(&pool, id).await?)
+    }
+
+    /// 从响应体中提取值，保存为激活环境的变量
+    pub async fn capture_variable(
+        &self,
+        key: String,
+        path: String,
+        body: Vec<u8>,
+    ) -> Result<String, EnvironmentError> {
+        let pool = self.db.storage();
+        let environment_id = environment::get_active_environment_id(&pool)
+            .await?
+            .ok_or(EnvironmentError::NoActiveEnvironment)?;
+        let value = environment::capture(&body, &path)?;
+        environment::save_captured_variable(&pool, environment_id, &key, &value).await?;
+        Ok(value)
+    }
+
+    /// 用激活环境的变量替换请求中的 `{{var}}`
+    pub async fn render_request(
+        &self,
+        url: &str,
+        options: Option<FetchOptions>,
+    ) -> (String, Option<FetchOptions>) {
+        let pool = self.db.storage();
+        let variables = environment::get_active_variables(&pool)
+            .await
+            .inspect_err(|e| error!("读取环境变量失败：{e}"))
+            .unwrap_or_default();
+        environment::render_request(url, options, &variables)
+    }
+
+    pub async fn query_credentials(&self) -> Result<Vec<Credential>, DatabaseError> {
+        let pool = self.db.storage();
+        Ok(auth::query_credentials(&pool).await?)
+    }
+
+    pub async fn save_credential(&self, credential: Credential) -> Result<i64, DatabaseError> {
+        let pool = self.db.storage();
+        let id = auth::save_credential(&pool, credential).await?;
+        self.tokens.invalidate(id).await;
+        Ok(id)
+    }
+
+    pub async fn delete_credential(&self, id: i64) -> Result<(), DatabaseError> {
+        let pool = self.db.storage();
+        auth::delete_credential(&pool, id).await?;
+        self.tokens.invalidate(id).await;
+        Ok(())
+    }
+
+    /// 按请求引用的凭据生成认证信息，密钥不经过前端
+    pub async fn authorize(
+        &self,
+        options: Option<FetchOptions>,
+    ) -> Result<Option<FetchOptions>, AuthError> {
+        let Some(mut options) = options else {
+            return Ok(None);
+        };
+        let Some(id) = options.credential_id else {
+            return Ok(Some(options));
+        };
+
+        let pool = self.db.storage();
+        let credential = auth::get_credential(&pool, id)
+            .await?
+            .ok_or(AuthError::NoCredential)?;
+        let client = self.window.http_client().get().await;
+        options.authorization = Some(self.tokens.authorize(&client, &credential).await?);
+        Ok(Some(options))
+    }
+
+    pub fn list_hotkeys(&self) -> Vec<HotkeyBinding> {
+        let hotkey = self.window.hotkey();
+        hotkey.list().into_iter().map(HotkeyBinding::from).collect()
+    }
+
+    /// 替换动作的快捷键，空列表表示解绑
+    pub async fn set_hotkey_binding(
+        &self,
+        action: String,
+        hotkeys: Vec<String>,
+    ) -> Result<(), HotkeyError> {
+        let hotkeys = hotkeys
+            .iter()
+            .map(|hotkey| hotkey.parse())
+            .collect::<Result<Vec<KeySequence>, _>>()?;
+        self.window.hotkey().rebind(&action, hotkeys.clone())?;
+
+        let pool = self.db.storage();
+        hotkey_binding::save_binding(&pool, &action, &hotkeys).await?;
+        Ok(())
+    }
+
+    pub async fn reset_hotkey_binding(&self, action: String) -> Result<(), HotkeyError> {
+        self.window.hotkey().reset(&action)?;
+
+        let pool = self.db.storage();
+        hotkey_binding::delete_binding(&pool, &action).await?;
+        Ok(())
+    }
+
+    /// 设置界面录制快捷键时暂停触发
+    pub fn set_hotkey_enabled(&self, enabled: bool) {
+        let hotkey = self.window.hotkey();
+        hotkey.set_enabled(enabled);
+        if enabled {
+            hotkey.clear_pressed();
+        }
+    }
+
+    /// 前端读取的键盘布局，按键位置（如 `KeyW`）到该位置输入的字符，无法识别的项忽略；
+    /// 读取不到时保留默认的 QWERTY 布局
+    pub fn set_keyboard_layout(&self, layout: HashMap<String, String>) {
+        let layout: Vec<(Code, char)> = layout
+            .iter()
+            .filter_map(|(code, key)| {
+                let code = code.parse().ok()?;
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some((code, c)),
+                    _ => None,
+                }
+            })
+            .collect();
+        if !layout.is_empty() {
+            self.window.hotkey().set_layout(KeyboardLayout::new(layout));
+        }
+    }
+
+    /// 页面脚本上报的鼠标侧键，如 `Back`
+    pub async fn mouse_button(&self, label: &str, button: String) -> Result<(), HotkeyError> {
+        // 后台标签页不能触发当前标签页的绑定
+        if !self.is_current_tab(label).await {
+            return Ok(());
+        }
+
+        let button = button.parse()?;
+        self.window.hotkey().handle_mouse_button(button);
+        Ok(())
+    }
+
+    /// 页面脚本识别出的鼠标手势笔画，如 `["Down", "Right"]`
+    pub async fn mouse_gesture(
+        &self,
+        label: &str,
+        strokes: Vec<String>,
+    ) -> Result<(), HotkeyError> {
+        if !self.is_current_tab(label).await {
+            return Ok(());
+        }
+
+        let strokes = strokes
+            .iter()
+            .map(|stroke| stroke.parse())
+            .collect::<Result<Vec<Direction>, _>>()?;
+        self.window.hotkey().handle_gesture(&strokes);
+        Ok(())
+    }
+
+    /// 保存到下载目录，返回实际保存的路径
+    pub async fn save_response_body(
+        &self,
+        file_name: String,
+        body: Vec<u8>,
+    ) -> Result<String, SaveBodyError> {
+        let dir = self.window.path().download_dir()?;
+        let path = request::save_body(&dir, &file_name, &body).await?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    /// 导出为 HAR 文件并保存到下载目录，返回实际保存的路径
+    pub async fn export_har(&self, source: HarSource) -> Result<String, HarError> {
+        let file_name = match source {
+            HarSource::Request { .. } => "request.har",
+            HarSource::Collection { .. } => "collection.har",
+            HarSource::History => "history.har",
+        };
+        let pool = self.db.get().await;
+        let har = Har::new(har::query_entries(&pool, source).await?);
+        let body = serde_json::to_vec_pretty(&har)?;
+
+        let dir = self.window.path().download_dir()?;
+        let path = request::save_body(&dir, file_name, &body).await?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    /// 从 HAR 文件导入请求到集合，返回导入数量
+    pub async fn import_har(&self, collection_id: i64, path: String) -> Result<usize, HarError> {
+        let content = tokio::fs::read(path).await?;
+        let har: Har = serde_json::from_slice(&content)?;
+        let pool = self.db.get().await;
+        Ok(har::import(&pool, collection_id, &har).await?)
+    }
+
+    /// 依次发送集合及其子文件夹中的请求，提取的变量只在本次运行中传递
+    pub async fn run_collection(&self, id: i64) -> Result<RunReport, RunError> {
+        let pool = self.db.get().await;
+        let name = collection::query_collections(&pool)
+            .await?
+            .into_iter()
+            .find(|collection| collection.id == id)
+            .map(|collection| collection.name)
+            .unwrap_or_default();
+        let requests = collection::query_collection_requests(&pool, id).await?;
+        let mut variables = environment::get_active_variables(&self.db.storage())
+            .await
+            .inspect_err(|e| error!("读取环境变量失败：{e}"))
+            .unwrap_or_default();
+
+        let client = self.window.http_client().get().await;
+        let start = Instant::now();
+        let mut results = Vec::with_capacity(requests.len());
+        for saved in requests {
+            let options = saved.to_options();
+            let (url, rendered_options) =
+                environment::render_request(&saved.url, Some(options.clone()), &variables);
+            let result = match self.authorize(rendered_options).await {
+                // 集合中的请求由用户在 HTTP 客户端中保存，允许上传本地文件
+                Ok(rendered_options) => request::fetch(&client, &url, rendered_options, true)
+                    .await
+                    .map_err(AuthError::from),
+                Err(e) => Err(e),
+            };
+            self.save_request_log(&saved.url, Some(&options), &result)
+                .await;
+            results.push(RequestResult::new(&saved, &result, &mut variables));
+        }
+        Ok(RunReport::new(name, results, start.elapsed()))
+    }
+
+    pub async fn get_http_client_config(&self) -> HttpClientConfig {
+        self.window.http_client().config().await
+    }
+
+    pub async fn set_http_client_config(
+        &self,
+        config: HttpClientConfig,
+    ) -> Result<(), HttpClientError> {
+        // 客户端配置不受无痕模式影响
+        let pool = self.db.storage();
+        self.window.http_client().set_config(&pool, config).await
     }
 
     pub async fn update_star(&self, id: i64) -> Result<(), DatabaseError> {
@@ -497,6 +889,7 @@ impl Browser {
                 LogicalPosition::new(0., Webview::TITLE_HEIGHT)
             })
             .await;
+        self.hotkey_scopes_changed().await?;
         Ok(())
     }
 
@@ -518,6 +911,21 @@ impl Browser {
         self.state_changed(None).await
     }
 
+    /// 快捷键序列按下前缀后，在标题栏提示已按下的键
+    pub async fn key_sequence_pending(
+        &self,
+        pending: Option<KeySequence>,
+    ) -> Result<(), StateError> {
+        let Some(pending) = pending else {
+            return self.state_changed(None).await;
+        };
+
+        let mut state = self.get_state(None).await?;
+        state.title = KEY_SEQUENCE_TITLE.to_string();
+        state.url = self.window.hotkey().label(&pending);
+        self.state_changed(Some(state)).await
+    }
+
     pub async fn click_link(&self, url: String) -> Result<(), StateError> {
         let mut state = self.get_state(None).await?;
         state.url = url;
@@ -539,18 +947,135 @@ impl Browser {
             let pool = self.db.get().await;
             let host = host.to_string();
             async_runtime::spawn(async move {
+                let site = get_site(&pool, &host).await;
                 if enable {
-                    if let Err(e) = delete_blacklist(&pool, &host).await {
+                    if let Err(e) = delete_blacklist(&pool, &host, &site).await {
                         error!("删除 darkreader 黑名单 {host} 失败: {e}");
                     }
-                } else if let Err(e) = save_blacklist(&pool, &host).await {
-                    error!("保存 darkreader 黑名单 {host} 失败: {e}");
+                } else {
+                    // 在可注册域名本身关闭时，整个站点都关闭
+                    let by_site = host == site;
+                    if let Err(e) = save_blacklist(&pool, &host, by_site).await {
+                        error!("保存 darkreader 黑名单 {host} 失败: {e}");
+                    }
                 }
             });
         }
         self.state_changed(Some(state)).await
     }
 
+    pub async fn vim(&self) -> Result<(), StateError> {
+        let label = self.label.get().await;
+        if label.is_empty() {
+            return Ok(());
+        }
+
+        let enable = self.tabs.vim(&label).await?;
+        let state = self.get_state(None).await?;
+        if let Ok(url) = Url::parse(&state.url)
+            && let Some(host) = url.host_str()
+        {
+            let pool = self.db.get().await;
+            let host = host.to_string();
+            async_runtime::spawn(async move {
+                let site = get_site(&pool, &host).await;
+                if enable {
+                    // 在可注册域名本身开启时，整个站点都开启
+                    let by_site = host == site;
+                    if let Err(e) = vim::save_site(&pool, &host, by_site).await {
+                        error!("保存 Vim 模式站点 {host} 失败: {e}");
+                    }
+                } else if let Err(e) = vim::delete_site(&pool, &host, &site).await {
+                    error!("删除 Vim 模式站点 {host} 失败: {e}");
+                }
+            });
+        }
+        self.state_changed(Some(state)).await
+    }
+
+    /// 页面脚本上报的 Vim 模式，普通模式下才启用 Vim 快捷键
+    pub async fn vim_mode_changed(&self, label: &str, mode: VimMode) -> Result<(), FrameworkError> {
+        self.tabs.set_vim_mode(label, mode).await;
+        if self.is_current_tab(label).await {
+            self.hotkey_scopes_changed().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn vim_command(&self, command: VimCommand) -> Result<(), FrameworkError> {
+        if self.is_focused.get().await {
+            return Ok(());
+        }
+
+        let label = self.label.get().await;
+        if label.is_empty() {
+            return Ok(());
+        }
+
+        self.tabs.vim_command(&label, command).await
+    }
+
+    /// 链接提示选中的链接在新标签页打开，并回到普通模式。
+    /// 只接受正在显示链接提示的标签页和 http、https 链接，避免页面借此打开任意地址
+    pub async fn vim_open_link(&self, label: &str, url: String) -> Result<(), TabError> {
+        if !self.tabs.take_vim_hint(label).await {
+            return Ok(());
+        }
+        if self.is_current_tab(label).await {
+            self.hotkey_scopes_changed().await?;
+        }
+
+        let url = Url::parse(&url)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Ok(());
+        }
+        self.open_tab_by_url(&url, true).await
+    }
+
+    /// 开关当前标签页的请求抓取
+    pub async fn capture(&self) -> Result<(), StateError> {
+        let label = self.label.get().await;
+        if label.is_empty() {
+            return Ok(());
+        }
+
+        self.tabs.capture(&label).await?;
+        self.state_changed(None).await
+    }
+
+    /// 只记录开启了抓取的标签页上报的请求，并推送给 HTTP 客户端
+    pub async fn capture_request(
+        &self,
+        label: &str,
+        page_url: String,
+        request: PageRequest,
+    ) -> Result<(), FrameworkError> {
+        if !self.tabs.is_capturing(label).await {
+            return Ok(());
+        }
+
+        let captured = self.captures.push(page_url, request).await;
+        self.window
+            .emit_to(Webview::MAINVIEW_LABEL, "request-captured", captured)?;
+        Ok(())
+    }
+
+    pub async fn query_captured_requests(&self) -> Vec<CapturedRequest> {
+        self.captures.list().await
+    }
+
+    pub async fn clear_captured_requests(&self) {
+        self.captures.clear().await;
+    }
+
+    /// 进入主视图并切换快捷键速查表
+    pub async fn cheat_sheet(&self) -> Result<(), StateError> {
+        self.focus().await?;
+        self.window
+            .emit_to(Webview::MAINVIEW_LABEL, "cheat-sheet", ())?;
+        Ok(())
+    }
+
     pub async fn devtools(&self) {
         let label = self.label.get().await;
         if label.is_empty() {
@@ -612,9 +1137,35 @@ impl Browser {
         Ok(save_log(&pool, log).await?)
     }
 
+    /// 与 `fetch` 命令一样登记到 `FetchRegistry`，可按 request id 取消
+    async fn fetch_and_log(
+        &self,
+        url: &str,
+        options: FetchOptions,
+        fetch_registry: &FetchRegistry,
+        label: &str,
+        request_id: Option<String>,
+    ) -> Result<Response, AuthError> {
+        let client = self.window.http_client().get().await;
+        let (rendered_url, rendered_options) =
+            self.render_request(url, Some(options.clone())).await;
+        let result = match self.authorize(rendered_options).await {
+            Ok(rendered_options) => fetch_registry
+                .run(label, request_id, None, async move {
+                    request::fetch(&client, &rendered_url, rendered_options, true).await
+                })
+                .await
+                .map_err(AuthError::from),
+            Err(e) => Err(e),
+        };
+        self.save_request_log(url, Some(&options), &result).await;
+        result
+    }
+
     async fn get_icon_data_url(&self, icon_url: &str) -> Result<String, IconError> {
         let pool = self.db.get().await;
-        get_icon_data_url(&pool, icon_url).await
+        let client = self.window.http_client().get().await;
+        get_icon_data_url(&pool, &client, icon_url).await
     }
 
     async fn get_cached_icon(&self, url: &str) -> Option<String> {
@@ -656,17 +1207,42 @@ impl Browser {
             state.title = LOADING_TITLE.to_string();
         }
 
+        let pool = self.db.get().await;
+        let public_suffix = get_public_suffix_cached(&pool).await.ok();
+        state.display_url = display_url(public_suffix.as_ref(), &state.url);
+
+        self.hotkey_scopes_changed().await?;
         self.window
             .emit_to(Webview::MAINVIEW_LABEL, "state-changed", state)?;
         Ok(())
     }
 
+    /// 根据焦点、全屏与 Vim 模式切换快捷键作用域
+    async fn hotkey_scopes_changed(&self) -> Result<(), FrameworkError> {
+        let label = self.label.get().await;
+        let is_mainview = self.is_focused.get().await || label.is_empty();
+        let mut scopes = vec![if is_mainview {
+            MAINVIEW_SCOPE
+        } else {
+            TAB_SCOPE
+        }];
+        if self.window.is_fullscreen()? {
+            scopes.push(FULLSCREEN_SCOPE);
+        }
+        if !is_mainview && self.tabs.is_vim_normal(&label).await {
+            scopes.push(VIM_SCOPE);
+        }
+        self.window.hotkey().set_scopes(scopes);
+        Ok(())
+    }
+
     async fn darkreader_auto_switch(&self, label: &str, state: &mut BrowserState) {
         let enable = if let Ok(url) = Url::parse(&state.url)
             && let Some(host) = url.host_str()
         {
             let pool = self.db.get().await;
-            darkreader::switch(&pool, host).await
+            let site = get_site(&pool, host).await;
+            darkreader::switch(&pool, host, &site).await
         } else {
             true
         };
@@ -677,6 +1253,25 @@ impl Browser {
             state.darkreader = enable;
         }
     }
+
+    /// 每次页面加载完成都重新注入，页面脚本随之上报当前模式
+    async fn vim_auto_switch(&self, label: &str, state: &mut BrowserState) {
+        let enable = if let Ok(url) = Url::parse(&state.url)
+            && let Some(host) = url.host_str()
+        {
+            let pool = self.db.get().await;
+            let site = get_site(&pool, host).await;
+            vim::switch(&pool, host, &site).await
+        } else {
+            false
+        };
+
+        if let Err(e) = self.tabs.set_vim(label, enable).await {
+            error!("切换 Vim 模式失败：{e}");
+        } else {
+            state.vim = enable;
+        }
+    }
 }
 
 pub trait BrowserExt {
@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::Mutex;
+use time::OffsetDateTime;
+
+use crate::request::{FetchBody, FormField, HttpHeader};
+
+/// 页面脚本据此决定是否上报请求；sessionStorage 让同源跳转后的新页面从一开始就记录
+pub const CAPTURE_ENABLE_SCRIPT: &str = r#"
+window.__WHITE_HOLE_CAPTURE__ = true;
+try { sessionStorage.setItem("__white_hole_capture", "1"); } catch (e) {}
+"#;
+pub const CAPTURE_DISABLE_SCRIPT: &str = r#"
+window.__WHITE_HOLE_CAPTURE__ = false;
+try { sessionStorage.removeItem("__white_hole_capture"); } catch (e) {}
+"#;
+
+/// 缓冲区最多保留的请求数，超出时丢弃最早的请求
+const MAX_CAPTURE_COUNT: usize = 500;
+
+/// 页面通过 fetch 或 XHR 发出的请求，由 `webview_init.js` 上报
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PageRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    /// `fetch` 或 `xhr`
+    pub initiator: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapturedRequest {
+    pub id: u64,
+    pub page_url: String,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    pub initiator: String,
+    /// URL、请求头或请求体含 `{{`，重新发送时会被当作变量填入环境中的值
+    pub templated: bool,
+    #[serde(with = "time::serde::iso8601")]
+    pub capture_time: OffsetDateTime,
+}
+
+/// 抓取到的请求，所有标签页共用，新的在前
+#[derive(Default)]
+pub struct CaptureBuffer(Mutex<(u64, VecDeque<CapturedRequest>)>);
+
+impl CaptureBuffer {
+    pub async fn push(&self, page_url: String, request: PageRequest) -> CapturedRequest {
+        let mut buffer = self.0.lock().await;
+        let (seq, requests) = &mut *buffer;
+        *seq += 1;
+        if requests.len() >= MAX_CAPTURE_COUNT {
+            requests.pop_back();
+        }
+        // 请求体来自页面，不能让页面指定本地文件，重新发送时上传出去
+        let body = request.body.map(|body| match body {
+            FetchBody::Form { form } => FetchBody::Form {
+                form: strip_files(form),
+            },
+            FetchBody::Multipart { multipart } => FetchBody::Multipart {
+                multipart: strip_files(multipart),
+            },
+            body => body,
+        });
+        let templated = is_templated(&request.url, &request.headers, body.as_ref());
+        let captured = CapturedRequest {
+            id: *seq,
+            page_url,
+            method: request.method.to_uppercase(),
+            url: request.url,
+            headers: request.headers,
+            body,
+            initiator: request.initiator,
+            templated,
+            capture_time: OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc()),
+        };
+        requests.push_front(captured.clone());
+        captured
+    }
+
+    pub async fn list(&self) -> Vec<CapturedRequest> {
+        self.0.lock().await.1.iter().cloned().collect()
+    }
+
+    pub async fn clear(&self) {
+        self.0.lock().await.1.clear();
+    }
+}
+
+fn strip_files(fields: Vec<FormField>) -> Vec<FormField> {
+    fields
+        .into_iter()
+        .map(|field| FormField {
+            file: false,
+            ..field
+        })
+        .collect()
+}
+
+/// 与 `environment::render_request` 替换变量的范围一致
+fn is_templated(url: &str, headers: &[HttpHeader], body: Option<&FetchBody>) -> bool {
+    let fields_templated = |fields: &[FormField]| {
+        fields
+            .iter()
+            .any(|field| field.key.contains("{{") || field.value.contains("{{"))
+    };
+    url.contains("{{")
+        || headers
+            .iter()
+            .any(|header| header.key.contains("{{") || header.value.contains("{{"))
+        || match body {
+            Some(FetchBody::Text(text)) => text.contains("{{"),
+            Some(FetchBody::Json { json }) => json.contains("{{"),
+            Some(FetchBody::Form { form }) => fields_templated(form),
+            Some(FetchBody::Multipart { multipart }) => fields_templated(multipart),
+            _ => false,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::async_runtime;
+
+    use super::*;
+
+    #[test]
+    fn test_capture_buffer() {
+        async_runtime::block_on(async {
+            let request: PageRequest = serde_json::from_str(
+                r#"{"method":"post","url":"https://example.com/api","headers":[{"key":"x-token","value":"1"}],"body":{"form":[{"key":"q","value":"白洞"}]},"initiator":"xhr"}"#,
+            )
+            .unwrap();
+            assert!(matches!(request.body, Some(FetchBody::Form { .. })));
+
+            let buffer = CaptureBuffer::default();
+            for _ in 0..MAX_CAPTURE_COUNT + 1 {
+                buffer
+                    .push("https://example.com/".to_string(), request.clone())
+                    .await;
+            }
+            let requests = buffer.list().await;
+            assert_eq!(requests.len(), MAX_CAPTURE_COUNT);
+            assert_eq!(requests[0].id, MAX_CAPTURE_COUNT as u64 + 1);
+            assert_eq!(requests[0].method, "POST");
+
+            buffer.clear().await;
+            assert!(buffer.list().await.is_empty());
+        });
+    }
+    #[test]
+    fn test_capture_sanitize() {
+        async_runtime::block_on(async {
+            let request: PageRequest = serde_json::from_str(
+                r#"{"method":"POST","url":"https://example.com/upload","body":{"multipart":[{"key":"f","value":"/etc/passwd","file":true}]},"initiator":"fetch"}"#,
+            )
+            .unwrap();
+            let buffer = CaptureBuffer::default();
+            let captured = buffer
+                .push("https://example.com/".to_string(), request.clone())
+                .await;
+            let Some(FetchBody::Multipart { multipart }) = &captured.body else {
+                panic!("请求体应为 multipart");
+            };
+            assert!(!multipart[0].file);
+            assert_eq!(multipart[0].value, "/etc/passwd");
+            assert!(!captured.templated);
+
+            let captured = buffer
+                .push(
+                    "https://example.com/".to_string(),
+                    PageRequest {
+                        url: "https://example.com/?t={{token}}".to_string(),
+                        ..request
+                    },
+                )
+                .await;
+            assert!(captured.templated);
+        });
+    }
+}
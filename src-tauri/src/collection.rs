@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, types::Json};
+use time::OffsetDateTime;
+
+use crate::{
+    assertion::Assertion,
+    environment::Capture,
+    request::{FetchBody, FetchOptions, HttpHeader},
+    request_log::to_options,
+};
+
+/// 请求集合，`parent_id` 不为空时为子文件夹
+#[derive(Clone, Serialize)]
+pub struct Collection {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub requests: Vec<SavedRequest>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedRequest {
+    pub id: Option<i64>,
+    pub collection_id: i64,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub headers: Json<Vec<HttpHeader>>,
+    pub body: Option<Json<FetchBody>>,
+    pub credential_id: Option<i64>,
+    /// 运行集合时校验响应的断言
+    #[serde(default)]
+    pub assertions: Json<Vec<Assertion>>,
+    /// 运行集合时从响应中提取、供后续请求使用的变量
+    #[serde(default)]
+    pub captures: Json<Vec<Capture>>,
+    pub update_time: Option<OffsetDateTime>,
+}
+
+impl SavedRequest {
+    pub fn to_options(&self) -> FetchOptions {
+        to_options(&self.method, &self.headers, &self.body, self.credential_id)
+    }
+}
+
+/// 返回所有集合及其中保存的请求，前端按 `parent_id` 组装层级
+pub async fn query_collections(pool: &SqlitePool) -> Result<Vec<Collection>, sqlx::Error> {
+    let mut collections: Vec<Collection> =
+        sqlx::query!(r#"select id as "id!", parent_id, name from http_collection order by name"#)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|record| Collection {
+                id: record.id,
+                parent_id: record.parent_id,
+                name: record.name,
+                requests: Vec::new(),
+            })
+            .collect();
+
+    let requests = sqlx::query_as!(
+        SavedRequest,
+        r#"select id as "id?", collection_id, name, method, url, headers as "headers: Json<Vec<HttpHeader>>", body as "body: Json<FetchBody>", credential_id, assertions as "assertions: Json<Vec<Assertion>>", captures as "captures: Json<Vec<Capture>>", update_time as "update_time?: OffsetDateTime" from http_saved_request order by name"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for request in requests {
+        if let Some(collection) = collections
+            .iter_mut()
+            .find(|collection| collection.id == request.collection_id)
+        {
+            collection.requests.push(request);
+        }
+    }
+
+    Ok(collections)
+}
+
+/// 集合及其子文件夹中的全部请求，先本层请求后子文件夹，按名称排序
+pub async fn query_collection_requests(
+    pool: &SqlitePool,
+    id: i64,
+) -> Result<Vec<SavedRequest>, sqlx::Error> {
+    let mut collections = query_collections(pool).await?;
+    let mut requests = Vec::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if let Some(collection) = collections
+            .iter_mut()
+            .find(|collection| collection.id == id)
+        {
+            requests.append(&mut collection.requests);
+        }
+        // 逆序入栈，保证子文件夹按名称顺序出栈
+        stack.extend(
+            collections
+                .iter()
+                .rev()
+                .filter(|collection| collection.parent_id == Some(id))
+                .map(|collection| collection.id),
+        );
+    }
+    Ok(requests)
+}
+
+pub async fn save_collection(
+    pool: &SqlitePool,
+    id: Option<i64>,
+    parent_id: Option<i64>,
+    name: &str,
+) -> Result<i64, sqlx::Error> {
+    if let Some(id) = id {
+        sqlx::query!(
+            "update http_collection set parent_id = ?, name = ? where id = ?",
+            parent_id,
+            name,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(id)
+    } else {
+        sqlx::query!(
+            "insert into http_collection (parent_id, name) values (?, ?)",
+            parent_id,
+            name
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.last_insert_rowid())
+    }
+}
+
+/// 子文件夹及其中的请求一并删除
+pub async fn delete_collection(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_collection where id = ?", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_saved_request(pool: &SqlitePool, id: i64) -> Result<SavedRequest, sqlx::Error> {
+    sqlx::query_as!(
+        SavedRequest,
+        r#"select id as "id?", collection_id, name, method, url, headers as "headers: Json<Vec<HttpHeader>>", body as "body: Json<FetchBody>", credential_id, assertions as "assertions: Json<Vec<Assertion>>", captures as "captures: Json<Vec<Capture>>", update_time as "update_time?: OffsetDateTime" from http_saved_request where id = ?"#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+}
+
+pub async fn save_request(pool: &SqlitePool, request: SavedRequest) -> Result<i64, sqlx::Error> {
+    let SavedRequest {
+        id,
+        collection_id,
+        name,
+        method,
+        url,
+        headers,
+        body,
+        credential_id,
+        assertions,
+        captures,
+        ..
+    } = request;
+
+    if let Some(id) = id {
+        sqlx::query!(
+            "update http_saved_request set collection_id = ?, name = ?, method = ?, url = ?, headers = ?, body = ?, credential_id = ?, assertions = ?, captures = ?, update_time = datetime('now', 'localtime') where id = ?",
+            collection_id,
+            name,
+            method,
+            url,
+            headers,
+            body,
+            credential_id,
+            assertions,
+            captures,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(id)
+    } else {
+        sqlx::query!(
+            "insert into http_saved_request (collection_id, name, method, url, headers, body, credential_id, assertions, captures, update_time) values (?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now', 'localtime'))",
+            collection_id,
+            name,
+            method,
+            url,
+            headers,
+            body,
+            credential_id,
+            assertions,
+            captures
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.last_insert_rowid())
+    }
+}
+
+pub async fn delete_saved_request(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_saved_request where id = ?", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use tauri::async_runtime;
+
+    use super::*;
+
+    #[test]
+    fn test_collections() {
+        async_runtime::block_on(async {
+            let options = SqliteConnectOptions::new()
+                .in_memory(true)
+                .foreign_keys(true);
+            let pool = SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect_with(options)
+                .await
+                .unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let root = save_collection(&pool, None, None, "api").await.unwrap();
+            let folder = save_collection(&pool, None, Some(root), "user")
+                .await
+                .unwrap();
+            let request = SavedRequest {
+                id: None,
+                collection_id: folder,
+                name: "login".to_string(),
+                method: "POST".to_string(),
+                url: "https://example.com/login".to_string(),
+                headers: Json(vec![HttpHeader {
+                    key: "content-type".to_string(),
+                    value: "application/json".to_string(),
+                }]),
+                body: Some(Json(FetchBody::Text("{}".to_string()))),
+                credential_id: None,
+                assertions: Json(vec![Assertion::Status { equals: 200 }]),
+                captures: Json(vec![Capture {
+                    key: "token".to_string(),
+                    path: "$.token".to_string(),
+                }]),
+                update_time: None,
+            };
+            let id = save_request(&pool, request).await.unwrap();
+
+            let saved = get_saved_request(&pool, id).await.unwrap();
+            let options = saved.to_options();
+            assert_eq!(options.method.as_deref(), Some("POST"));
+            assert_eq!(options.body, Some(FetchBody::Text("{}".to_string())));
+            assert_eq!(saved.assertions.0, [Assertion::Status { equals: 200 }]);
+            assert_eq!(saved.captures.0[0].key, "token");
+
+            let collections = query_collections(&pool).await.unwrap();
+            assert_eq!(collections.len(), 2);
+            let user = collections.iter().find(|c| c.id == folder).unwrap();
+            assert_eq!(user.parent_id, Some(root));
+            assert_eq!(user.requests.len(), 1);
+            let requests = query_collection_requests(&pool, root).await.unwrap();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].name, "login");
+
+            // 删除集合时级联删除子文件夹和请求
+            delete_collection(&pool, root).await.unwrap();
+            assert!(query_collections(&pool).await.unwrap().is_empty());
+            assert!(get_saved_request(&pool, id).await.is_err());
+        });
+    }
+}
@@ -1,14 +1,34 @@
+use std::{collections::HashMap, sync::Arc};
+
 use log::{error, info};
-use tauri::{State, Webview, Window, command};
+use tauri::{State, Webview, Window, command, ipc::Channel};
+use tokio::sync::Semaphore;
 
 use crate::{
     IsMainView as _,
+    assertion::{self, Assertion, AssertionResult},
+    auth::Credential,
     browser::Browser,
-    error::{DatabaseError, FetchError, FrameworkError, StateError, TabError},
+    capture::{CapturedRequest, PageRequest},
+    collection::{Collection, SavedRequest},
+    environment::{Environment, Variable},
+    error::{
+        AuthError, DatabaseError, EnvironmentError, FetchError, FrameworkError, HarError,
+        HotkeyError, HttpClientError, ReplayError, RunError, SaveBodyError, StateError,
+        StreamError, TabError,
+    },
+    har::HarSource,
+    hotkey_binding::HotkeyBinding,
+    http_client::{HttpClient, HttpClientConfig},
     log::QueryLogResponse,
     page::PageToken,
-    request::{self, FetchOptions, Response},
+    request::{self, FETCH_WINDOW, FetchEvent, FetchOptions, FetchRegistry, Response},
+    request_log::QueryRequestLogResponse,
+    runner::RunReport,
+    sse::{self, SseEvent},
     state::BrowserState,
+    vim::VimMode,
+    websocket::{WebSocketEvent, WebSocketMessage, WebSocketRegistry},
 };
 
 #[command]
@@ -205,13 +225,16 @@ pub async fn query_navigation_log(
     browser: State<'_, Browser>,
     mainview: Webview,
     keyword: String,
+    group_by_site: Option<bool>,
     page_token: PageToken,
 ) -> Result<QueryLogResponse, DatabaseError> {
     if !mainview.is_main() {
         return Ok(QueryLogResponse::default());
     }
 
-    browser.query_navigation_log(keyword, page_token).await
+    browser
+        .query_navigation_log(keyword, group_by_site.unwrap_or_default(), page_token)
+        .await
 }
 
 #[command]
@@ -369,6 +392,700 @@ pub async fn darkreader(browser: State<'_, Browser>, mainview: Webview) -> Resul
 }
 
 #[command]
-pub async fn fetch(url: String, options: Option<FetchOptions>) -> Result<Response, FetchError> {
-    request::fetch(&url, options).await
+pub async fn capture(browser: State<'_, Browser>, mainview: Webview) -> Result<(), StateError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.capture().await
+}
+
+#[command]
+pub async fn vim(browser: State<'_, Browser>, mainview: Webview) -> Result<(), StateError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.vim().await
+}
+
+#[command]
+pub async fn vim_mode_changed(
+    browser: State<'_, Browser>,
+    webview: Webview,
+    mode: VimMode,
+) -> Result<(), FrameworkError> {
+    if webview.is_main() {
+        return Ok(());
+    }
+
+    browser.vim_mode_changed(webview.label(), mode).await
+}
+
+#[command]
+pub async fn vim_open_link(
+    browser: State<'_, Browser>,
+    webview: Webview,
+    url: String,
+) -> Result<(), TabError> {
+    if webview.is_main() {
+        return Ok(());
+    }
+
+    browser.vim_open_link(webview.label(), url).await
+}
+
+#[command]
+pub async fn capture_request(
+    browser: State<'_, Browser>,
+    webview: Webview,
+    request: PageRequest,
+) -> Result<(), FrameworkError> {
+    if webview.is_main() {
+        return Ok(());
+    }
+
+    let page_url = webview.url()?.to_string();
+    browser
+        .capture_request(webview.label(), page_url, request)
+        .await
+}
+
+#[command]
+pub async fn query_captured_requests(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<Vec<CapturedRequest>, StateError> {
+    if !mainview.is_main() {
+        return Err(StateError::NoMainView);
+    }
+
+    Ok(browser.query_captured_requests().await)
+}
+
+#[command]
+pub async fn clear_captured_requests(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<(), StateError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.clear_captured_requests().await;
+    Ok(())
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn fetch(
+    browser: State<'_, Browser>,
+    http_client: State<'_, HttpClient>,
+    fetch_registry: State<'_, FetchRegistry>,
+    webview: Webview,
+    url: String,
+    options: Option<FetchOptions>,
+    request_id: Option<String>,
+) -> Result<Response, AuthError> {
+    let client = http_client.get().await;
+    // 只有 HTTP 客户端可以上传本地文件，标签页不能借 multipart 读取本机文件
+    let allow_files = webview.is_main();
+    // 环境变量与认证凭据只作用于 HTTP 客户端，记录的是替换前的请求，避免密钥落盘
+    let (rendered_url, rendered_options) = if webview.is_main() {
+        let (rendered_url, rendered_options) = browser.render_request(&url, options.clone()).await;
+        (rendered_url, browser.authorize(rendered_options).await)
+    } else {
+        (url.clone(), Ok(options.clone()))
+    };
+    let result = match rendered_options {
+        Ok(rendered_options) => fetch_registry
+            .run(webview.label(), request_id, None, async move {
+                request::fetch(&client, &rendered_url, rendered_options, allow_files).await
+            })
+            .await
+            .map_err(AuthError::from),
+        Err(e) => Err(e),
+    };
+
+    // 只记录 HTTP 客户端发起的请求，标签页内 DarkReader 的请求不记录
+    if webview.is_main() {
+        browser
+            .save_request_log(&url, options.as_ref(), &result)
+            .await;
+    }
+
+    result
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn fetch_stream(
+    http_client: State<'_, HttpClient>,
+    fetch_registry: State<'_, FetchRegistry>,
+    webview: Webview,
+    url: String,
+    options: Option<FetchOptions>,
+    request_id: String,
+    window: Option<usize>,
+    on_event: Channel<FetchEvent>,
+) -> Result<(), FetchError> {
+    let client = http_client.get().await;
+    let allow_files = webview.is_main();
+    let acks = Arc::new(Semaphore::new(window.unwrap_or(FETCH_WINDOW).max(1)));
+    fetch_registry
+        .run(
+            webview.label(),
+            Some(request_id),
+            Some(acks.clone()),
+            async move {
+                request::fetch_stream(&client, &url, options, allow_files, &acks, |event| {
+                    Ok(on_event.send(event)?)
+                })
+                .await
+            },
+        )
+        .await
+}
+
+/// 订阅 SSE，通过 `cancel_fetch` 取消
+#[command(rename_all = "snake_case")]
+pub async fn subscribe_sse(
+    browser: State<'_, Browser>,
+    http_client: State<'_, HttpClient>,
+    fetch_registry: State<'_, FetchRegistry>,
+    mainview: Webview,
+    url: String,
+    options: Option<FetchOptions>,
+    request_id: String,
+    on_event: Channel<SseEvent>,
+) -> Result<(), StreamError> {
+    if !mainview.is_main() {
+        return Err(StreamError::NoMainView);
+    }
+
+    let (url, options) = browser.render_request(&url, options).await;
+    let options = browser.authorize(options).await?;
+    let client = http_client.get_stream().await;
+    Ok(fetch_registry
+        .run(mainview.label(), Some(request_id), None, async move {
+            sse::subscribe(&client, &url, options, |event| Ok(on_event.send(event)?)).await
+        })
+        .await?)
+}
+
+/// 建立 WebSocket 连接，连接关闭后返回
+#[command(rename_all = "snake_case")]
+pub async fn connect_websocket(
+    browser: State<'_, Browser>,
+    websocket_registry: State<'_, WebSocketRegistry>,
+    mainview: Webview,
+    url: String,
+    options: Option<FetchOptions>,
+    connection_id: String,
+    on_event: Channel<WebSocketEvent>,
+) -> Result<(), StreamError> {
+    if !mainview.is_main() {
+        return Err(StreamError::NoMainView);
+    }
+
+    let (url, options) = browser.render_request(&url, options).await;
+    let options = browser.authorize(options).await?;
+    websocket_registry
+        .connect(connection_id, &url, options, |event| {
+            Ok(on_event.send(event).map_err(FetchError::from)?)
+        })
+        .await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn send_websocket(
+    websocket_registry: State<'_, WebSocketRegistry>,
+    mainview: Webview,
+    connection_id: String,
+    message: WebSocketMessage,
+) -> Result<(), StreamError> {
+    if !mainview.is_main() {
+        return Err(StreamError::NoMainView);
+    }
+
+    websocket_registry.send(&connection_id, message).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn close_websocket(
+    websocket_registry: State<'_, WebSocketRegistry>,
+    mainview: Webview,
+    connection_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<(), StreamError> {
+    if !mainview.is_main() {
+        return Err(StreamError::NoMainView);
+    }
+
+    websocket_registry.close(&connection_id, code, reason).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn ack_fetch(
+    fetch_registry: State<'_, FetchRegistry>,
+    webview: Webview,
+    request_id: String,
+    count: Option<usize>,
+) -> Result<(), FetchError> {
+    fetch_registry
+        .ack(webview.label(), &request_id, count.unwrap_or(1))
+        .await;
+    Ok(())
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn cancel_fetch(
+    fetch_registry: State<'_, FetchRegistry>,
+    webview: Webview,
+    request_id: String,
+) -> Result<bool, FetchError> {
+    Ok(fetch_registry.cancel(webview.label(), &request_id).await)
+}
+
+#[command]
+pub async fn get_http_client_config(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<HttpClientConfig, StateError> {
+    if !mainview.is_main() {
+        return Err(StateError::NoMainView);
+    }
+
+    Ok(browser.get_http_client_config().await)
+}
+
+#[command]
+pub async fn set_http_client_config(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    config: HttpClientConfig,
+) -> Result<(), HttpClientError> {
+    if !mainview.is_main() {
+        return Err(HttpClientError::NoMainView);
+    }
+
+    browser.set_http_client_config(config).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn query_request_log(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    keyword: String,
+    page_token: PageToken,
+) -> Result<QueryRequestLogResponse, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(QueryRequestLogResponse::default());
+    }
+
+    browser.query_request_log(keyword, page_token).await
+}
+
+#[command]
+pub async fn delete_request_log(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_request_log(id).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn replay_request_log(
+    browser: State<'_, Browser>,
+    fetch_registry: State<'_, FetchRegistry>,
+    mainview: Webview,
+    id: i64,
+    request_id: Option<String>,
+) -> Result<Response, ReplayError> {
+    if !mainview.is_main() {
+        return Err(ReplayError::NoMainView);
+    }
+
+    browser
+        .replay_request_log(id, &fetch_registry, mainview.label(), request_id)
+        .await
+}
+
+#[command]
+pub async fn query_collections(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<Vec<Collection>, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(Vec::new());
+    }
+
+    browser.query_collections().await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn save_collection(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: Option<i64>,
+    parent_id: Option<i64>,
+    name: String,
+) -> Result<i64, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(-1);
+    }
+
+    browser.save_collection(id, parent_id, name).await
+}
+
+#[command]
+pub async fn delete_collection(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_collection(id).await
+}
+
+#[command]
+pub async fn save_request(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    request: SavedRequest,
+) -> Result<i64, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(-1);
+    }
+
+    browser.save_request(request).await
+}
+
+#[command]
+pub async fn delete_saved_request(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_saved_request(id).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn replay_saved_request(
+    browser: State<'_, Browser>,
+    fetch_registry: State<'_, FetchRegistry>,
+    mainview: Webview,
+    id: i64,
+    request_id: Option<String>,
+) -> Result<Response, ReplayError> {
+    if !mainview.is_main() {
+        return Err(ReplayError::NoMainView);
+    }
+
+    browser
+        .replay_saved_request(id, &fetch_registry, mainview.label(), request_id)
+        .await
+}
+
+#[command]
+pub async fn query_environments(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<Vec<Environment>, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(Vec::new());
+    }
+
+    browser.query_environments().await
+}
+
+#[command]
+pub async fn save_environment(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: Option<i64>,
+    name: String,
+) -> Result<i64, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(-1);
+    }
+
+    browser.save_environment(id, name).await
+}
+
+#[command]
+pub async fn delete_environment(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_environment(id).await
+}
+
+#[command]
+pub async fn set_active_environment(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: Option<i64>,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.set_active_environment(id).await
+}
+
+#[command]
+pub async fn save_variable(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    variable: Variable,
+) -> Result<i64, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(-1);
+    }
+
+    browser.save_variable(variable).await
+}
+
+#[command]
+pub async fn delete_variable(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_variable(id).await
+}
+
+#[command]
+pub async fn capture_variable(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    key: String,
+    path: String,
+    body: Vec<u8>,
+) -> Result<String, EnvironmentError> {
+    if !mainview.is_main() {
+        return Err(EnvironmentError::NoMainView);
+    }
+
+    browser.capture_variable(key, path, body).await
+}
+
+#[command]
+pub async fn query_credentials(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<Vec<Credential>, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(Vec::new());
+    }
+
+    browser.query_credentials().await
+}
+
+#[command]
+pub async fn save_credential(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    credential: Credential,
+) -> Result<i64, DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(-1);
+    }
+
+    browser.save_credential(credential).await
+}
+
+#[command]
+pub async fn delete_credential(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<(), DatabaseError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.delete_credential(id).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn save_response_body(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    file_name: String,
+    body: Vec<u8>,
+) -> Result<String, SaveBodyError> {
+    if !mainview.is_main() {
+        return Err(SaveBodyError::NoMainView);
+    }
+
+    browser.save_response_body(file_name, body).await
+}
+
+#[command]
+pub async fn export_har(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    source: HarSource,
+) -> Result<String, HarError> {
+    if !mainview.is_main() {
+        return Err(HarError::NoMainView);
+    }
+
+    browser.export_har(source).await
+}
+
+#[command(rename_all = "snake_case")]
+pub async fn import_har(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    collection_id: i64,
+    path: String,
+) -> Result<usize, HarError> {
+    if !mainview.is_main() {
+        return Err(HarError::NoMainView);
+    }
+
+    browser.import_har(collection_id, path).await
+}
+
+#[command]
+pub async fn run_collection(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    id: i64,
+) -> Result<RunReport, RunError> {
+    if !mainview.is_main() {
+        return Err(RunError::NoMainView);
+    }
+
+    browser.run_collection(id).await
+}
+
+#[command]
+pub async fn check_assertions(
+    mainview: Webview,
+    assertions: Vec<Assertion>,
+    response: Response,
+) -> Result<Vec<AssertionResult>, RunError> {
+    if !mainview.is_main() {
+        return Err(RunError::NoMainView);
+    }
+
+    Ok(assertion::check_all(&assertions, &response))
+}
+
+#[command]
+pub async fn list_hotkeys(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+) -> Result<Vec<HotkeyBinding>, HotkeyError> {
+    if !mainview.is_main() {
+        return Err(HotkeyError::NoMainView);
+    }
+
+    Ok(browser.list_hotkeys())
+}
+
+#[command]
+pub async fn set_hotkey_binding(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    action: String,
+    hotkeys: Vec<String>,
+) -> Result<(), HotkeyError> {
+    if !mainview.is_main() {
+        return Err(HotkeyError::NoMainView);
+    }
+
+    browser.set_hotkey_binding(action, hotkeys).await
+}
+
+#[command]
+pub async fn reset_hotkey_binding(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    action: String,
+) -> Result<(), HotkeyError> {
+    if !mainview.is_main() {
+        return Err(HotkeyError::NoMainView);
+    }
+
+    browser.reset_hotkey_binding(action).await
+}
+
+#[command]
+pub async fn mouse_button(
+    browser: State<'_, Browser>,
+    webview: Webview,
+    button: String,
+) -> Result<(), HotkeyError> {
+    if webview.is_main() {
+        return Ok(());
+    }
+
+    browser.mouse_button(webview.label(), button).await
+}
+
+#[command]
+pub async fn mouse_gesture(
+    browser: State<'_, Browser>,
+    webview: Webview,
+    strokes: Vec<String>,
+) -> Result<(), HotkeyError> {
+    if webview.is_main() {
+        return Ok(());
+    }
+
+    info!("{} webview mouse gesture: {strokes:?}", webview.label());
+    browser.mouse_gesture(webview.label(), strokes).await
+}
+
+#[command]
+pub async fn set_keyboard_layout(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    layout: HashMap<String, String>,
+) -> Result<(), HotkeyError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.set_keyboard_layout(layout);
+    Ok(())
+}
+
+#[command]
+pub async fn set_hotkey_enabled(
+    browser: State<'_, Browser>,
+    mainview: Webview,
+    enabled: bool,
+) -> Result<(), HotkeyError> {
+    if !mainview.is_main() {
+        return Ok(());
+    }
+
+    browser.set_hotkey_enabled(enabled);
+    Ok(())
 }
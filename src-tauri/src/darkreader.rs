@@ -3,13 +3,14 @@ use sqlx::SqlitePool;
 pub const DARKREADER_DISABLE_SCRIPT: &str = r#"DarkReader.auto(false)"#;
 pub const DARKREADER_ENABLE_SCRIPT: &str = r#"
 // 设置自定义 fetch 方法，使用 Tauri invoke 调用后端 fetch 以绕过 CORS 限制
-DarkReader.setFetchMethod((url, options = {}) => {
+DarkReader.setFetchMethod((url) => {
+    const options = { max_size: 16 * 1024 * 1024 };
     return window.__TAURI_INTERNALS__.invoke('fetch', { url, options }, { donotUseCustomProtocol: true })
         .then(resp => {
-            return new Response(resp.body, {
+            // body 为字节数组，headers 为 { key, value } 列表
+            return new Response(new Uint8Array(resp.body), {
                 status: resp.status,
-                statusText: resp.statusText,
-                headers: resp.headers
+                headers: resp.headers.map(header => [header.key, header.value])
             });
         });
 });
@@ -23,25 +24,47 @@ DarkReader.auto({
   sepia: 10,
 })"#;
 
-pub async fn switch(pool: &SqlitePool, host: &str) -> bool {
-    sqlx::query!("select id from darkreader_blacklist where host = ?", host)
-        .fetch_optional(pool)
-        .await
-        .ok()
-        .flatten()
-        .is_none()
+/// host 本身或其所属站点在黑名单中时关闭 darkreader
+pub async fn switch(pool: &SqlitePool, host: &str, site: &str) -> bool {
+    sqlx::query!(
+        "select id from darkreader_blacklist where (by_site = 0 and host = ?) or (by_site = 1 and host = ?)",
+        host,
+        site
+    )
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .is_none()
 }
 
-pub async fn save_blacklist(pool: &SqlitePool, host: &str) -> Result<i64, sqlx::Error> {
-    sqlx::query!("insert into darkreader_blacklist (host) values (?)", host)
-        .execute(pool)
-        .await
-        .map(|result| result.last_insert_rowid())
+pub async fn save_blacklist(
+    pool: &SqlitePool,
+    host: &str,
+    by_site: bool,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query!(
+        "insert into darkreader_blacklist (host, by_site) values (?, ?)",
+        host,
+        by_site
+    )
+    .execute(pool)
+    .await
+    .map(|result| result.last_insert_rowid())
 }
 
-pub async fn delete_blacklist(pool: &SqlitePool, host: &str) -> Result<u64, sqlx::Error> {
-    sqlx::query!("delete from darkreader_blacklist where host = ?", host)
-        .execute(pool)
-        .await
-        .map(|result| result.rows_affected())
+/// 同时删除 host 及其所属站点的黑名单
+pub async fn delete_blacklist(
+    pool: &SqlitePool,
+    host: &str,
+    site: &str,
+) -> Result<u64, sqlx::Error> {
+    sqlx::query!(
+        "delete from darkreader_blacklist where (by_site = 0 and host = ?) or (by_site = 1 and host = ?)",
+        host,
+        site
+    )
+    .execute(pool)
+    .await
+    .map(|result| result.rows_affected())
 }
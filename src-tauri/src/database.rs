@@ -69,6 +69,10 @@ impl Database {
         guard.as_ref().unwrap_or(&self.storage).clone()
     }
 
+    pub fn storage(&self) -> Arc<SqlitePool> {
+        self.storage.clone()
+    }
+
     pub async fn migrate_memory(&self) -> Result<(), DatabaseError> {
         let pool = SqlitePool::connect("sqlite::memory:").await?;
         sqlx::migrate!("../migrations").run(&pool).await?;
@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use sqlx::SqlitePool;
+
+use crate::{
+    error::EnvironmentError,
+    request::{FetchBody, FetchOptions, FormField, HttpHeader},
+};
+
+/// HTTP 客户端环境，同一时间最多一个处于激活状态
+#[derive(Clone, Serialize)]
+pub struct Environment {
+    pub id: i64,
+    pub name: String,
+    pub active: bool,
+    pub variables: Vec<Variable>,
+}
+
+/// 环境变量；返回给前端时隐藏私密变量的值，保存时 `value` 为空表示保持原值
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Variable {
+    pub id: Option<i64>,
+    pub environment_id: i64,
+    pub key: String,
+    pub value: Option<String>,
+    pub secret: bool,
+}
+
+/// 运行集合时用 JSONPath 从响应体中提取值，写入变量 `key`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    pub key: String,
+    pub path: String,
+}
+
+pub async fn query_environments(pool: &SqlitePool) -> Result<Vec<Environment>, sqlx::Error> {
+    let mut environments: Vec<Environment> =
+        sqlx::query!(r#"select id as "id!", name, active from http_environment order by name"#)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|record| Environment {
+                id: record.id,
+                name: record.name,
+                active: record.active,
+                variables: Vec::new(),
+            })
+            .collect();
+
+    let variables = sqlx::query!(
+        r#"select id as "id!", environment_id, key, value, secret from http_variable order by key"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for record in variables {
+        if let Some(environment) = environments
+            .iter_mut()
+            .find(|environment| environment.id == record.environment_id)
+        {
+            environment.variables.push(Variable {
+                id: Some(record.id),
+                environment_id: record.environment_id,
+                key: record.key,
+                value: (!record.secret).then_some(record.value),
+                secret: record.secret,
+            });
+        }
+    }
+
+    Ok(environments)
+}
+
+pub async fn save_environment(
+    pool: &SqlitePool,
+    id: Option<i64>,
+    name: &str,
+) -> Result<i64, sqlx::Error> {
+    if let Some(id) = id {
+        sqlx::query!(
+            "update http_environment set name = ? where id = ?",
+            name,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(id)
+    } else {
+        sqlx::query!("insert into http_environment (name) values (?)", name)
+            .execute(pool)
+            .await
+            .map(|result| result.last_insert_rowid())
+    }
+}
+
+pub async fn delete_environment(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_environment where id = ?", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// `id` 为空时取消激活
+pub async fn set_active_environment(pool: &SqlitePool, id: Option<i64>) -> Result<(), sqlx::Error> {
+    sqlx::query!("update http_environment set active = (id is ?)", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn save_variable(pool: &SqlitePool, variable: Variable) -> Result<i64, sqlx::Error> {
+    let Variable {
+        id,
+        environment_id,
+        key,
+        value,
+        secret,
+    } = variable;
+
+    match (id, value) {
+        (Some(id), Some(value)) => {
+            sqlx::query!(
+                "update http_variable set key = ?, value = ?, secret = ? where id = ?",
+                key,
+                value,
+                secret,
+                id
+            )
+            .execute(pool)
+            .await?;
+            Ok(id)
+        }
+        (Some(id), None) => {
+            sqlx::query!(
+                "update http_variable set key = ?, secret = ? where id = ?",
+                key,
+                secret,
+                id
+            )
+            .execute(pool)
+            .await?;
+            Ok(id)
+        }
+        (None, value) => {
+            let value = value.unwrap_or_default();
+            sqlx::query!(
+                "insert into http_variable (environment_id, key, value, secret) values (?, ?, ?, ?) on conflict(environment_id, key) do update set value = ?, secret = ?",
+                environment_id,
+                key,
+                value,
+                secret,
+                value,
+                secret
+            )
+            .execute(pool)
+            .await?;
+            Ok(sqlx::query!(
+                r#"select id as "id!" from http_variable where environment_id = ? and key = ?"#,
+                environment_id,
+                key
+            )
+            .fetch_one(pool)
+            .await?
+            .id)
+        }
+    }
+}
+
+/// 保存从响应中提取的值，已有同名变量时只更新值，保留其私密标记
+pub async fn save_captured_variable(
+    pool: &SqlitePool,
+    environment_id: i64,
+    key: &str,
+    value: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "insert into http_variable (environment_id, key, value, secret) values (?, ?, ?, 0) on conflict(environment_id, key) do update set value = excluded.value",
+        environment_id,
+        key,
+        value
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_variable(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_variable where id = ?", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_active_environment_id(pool: &SqlitePool) -> Result<Option<i64>, sqlx::Error> {
+    Ok(
+        sqlx::query!(r#"select id as "id!" from http_environment where active = 1"#)
+            .fetch_optional(pool)
+            .await?
+            .map(|record| record.id),
+    )
+}
+
+/// 激活环境的全部变量（含私密变量），用于替换请求模板
+pub async fn get_active_variables(
+    pool: &SqlitePool,
+) -> Result<HashMap<String, String>, sqlx::Error> {
+    Ok(sqlx::query!(
+        "select a.key, a.value from http_variable a, http_environment b where a.environment_id = b.id and b.active = 1"
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| (record.key, record.value))
+    .collect())
+}
+
+/// 替换 `{{var}}`，未定义的变量保持原样
+pub fn render(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + end;
+        result.push_str(&rest[..start]);
+        match variables.get(rest[start + 2..end].trim()) {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..end + 2]),
+        }
+        rest = &rest[end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 替换 URL、请求头和文本、JSON、表单请求体中的变量
+pub fn render_request(
+    url: &str,
+    options: Option<FetchOptions>,
+    variables: &HashMap<String, String>,
+) -> (String, Option<FetchOptions>) {
+    if variables.is_empty() {
+        return (url.to_string(), options);
+    }
+
+    let options = options.map(|options| FetchOptions {
+        headers: options.headers.map(|headers| {
+            headers
+                .into_iter()
+                .map(|HttpHeader { key, value }| HttpHeader {
+                    key: render(&key, variables),
+                    value: render(&value, variables),
+                })
+                .collect()
+        }),
+        body: options.body.map(|body| match body {
+            FetchBody::Text(text) => FetchBody::Text(render(&text, variables)),
+            FetchBody::Json { json } => FetchBody::Json {
+                json: render(&json, variables),
+            },
+            FetchBody::Form { form } => FetchBody::Form {
+                form: render_fields(form, variables),
+            },
+            FetchBody::Multipart { multipart } => FetchBody::Multipart {
+                multipart: render_fields(multipart, variables),
+            },
+            body => body,
+        }),
+        ..options
+    });
+
+    (render(url, variables), options)
+}
+
+fn render_fields(fields: Vec<FormField>, variables: &HashMap<String, String>) -> Vec<FormField> {
+    fields
+        .into_iter()
+        .map(|field| FormField {
+            key: render(&field.key, variables),
+            value: render(&field.value, variables),
+            ..field
+        })
+        .collect()
+}
+
+/// 从 JSON 响应体中取 JSONPath 匹配的第一个值，字符串不带引号
+pub fn capture(body: &[u8], path: &str) -> Result<String, EnvironmentError> {
+    let path = JsonPath::parse(path)?;
+    let body: Value = serde_json::from_slice(body)?;
+    match path.query(&body).first() {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(value) => Ok(value.to_string()),
+        None => Err(EnvironmentError::NoMatch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::async_runtime;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let variables = HashMap::from([
+            ("host".to_string(), "api.example.com".to_string()),
+            ("token".to_string(), "secret".to_string()),
+        ]);
+        assert_eq!(
+            render("https://{{host}}/users/{{ id }}", &variables),
+            "https://api.example.com/users/{{ id }}"
+        );
+        assert_eq!(render("{{token", &variables), "{{token");
+
+        let options = FetchOptions {
+            method: None,
+            headers: Some(vec![HttpHeader {
+                key: "authorization".to_string(),
+                value: "Bearer {{token}}".to_string(),
+            }]),
+            body: Some(FetchBody::Text(r#"{"token":"{{token}}"}"#.to_string())),
+            ..Default::default()
+        };
+        let (url, options) = render_request("{{host}}", Some(options), &variables);
+        let options = options.unwrap();
+        assert_eq!(url, "api.example.com");
+        assert_eq!(options.headers.unwrap()[0].value, "Bearer secret");
+        assert_eq!(
+            options.body,
+            Some(FetchBody::Text(r#"{"token":"secret"}"#.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let body = br#"{"data":{"token":"abc","count":2}}"#;
+        assert_eq!(capture(body, "$.data.token").unwrap(), "abc");
+        assert_eq!(capture(body, "$.data.count").unwrap(), "2");
+        assert!(capture(body, "$.data.missing").is_err());
+        assert!(capture(b"<html>", "$.data").is_err());
+    }
+
+    #[test]
+    fn test_environment() {
+        async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let dev = save_environment(&pool, None, "dev").await.unwrap();
+            let prod = save_environment(&pool, None, "prod").await.unwrap();
+            let token = save_variable(
+                &pool,
+                Variable {
+                    id: None,
+                    environment_id: dev,
+                    key: "token".to_string(),
+                    value: Some("dev-token".to_string()),
+                    secret: true,
+                },
+            )
+            .await
+            .unwrap();
+
+            set_active_environment(&pool, Some(dev)).await.unwrap();
+            assert_eq!(get_active_environment_id(&pool).await.unwrap(), Some(dev));
+            assert_eq!(
+                get_active_variables(&pool).await.unwrap().get("token"),
+                Some(&"dev-token".to_string())
+            );
+
+            // 私密变量不返回值，未修改值时保存不覆盖
+            let environments = query_environments(&pool).await.unwrap();
+            let variable = environments[0].variables[0].clone();
+            assert_eq!(variable.value, None);
+            save_variable(&pool, variable).await.unwrap();
+            assert_eq!(
+                get_active_variables(&pool).await.unwrap().get("token"),
+                Some(&"dev-token".to_string())
+            );
+
+            set_active_environment(&pool, Some(prod)).await.unwrap();
+            assert!(get_active_variables(&pool).await.unwrap().is_empty());
+            set_active_environment(&pool, None).await.unwrap();
+            assert_eq!(get_active_environment_id(&pool).await.unwrap(), None);
+
+            // 提取的值覆盖私密变量时保留私密标记
+            save_captured_variable(&pool, dev, "token", "captured")
+                .await
+                .unwrap();
+            save_captured_variable(&pool, dev, "user", "alice")
+                .await
+                .unwrap();
+            let variables = query_environments(&pool).await.unwrap()[0]
+                .variables
+                .clone();
+            let token_variable = variables.iter().find(|v| v.key == "token").unwrap();
+            assert!(token_variable.secret);
+            assert_eq!(token_variable.value, None);
+            let user_variable = variables.iter().find(|v| v.key == "user").unwrap();
+            assert!(!user_variable.secret);
+            assert_eq!(user_variable.value, Some("alice".to_string()));
+            set_active_environment(&pool, Some(dev)).await.unwrap();
+            assert_eq!(
+                get_active_variables(&pool).await.unwrap().get("token"),
+                Some(&"captured".to_string())
+            );
+
+            delete_variable(&pool, token).await.unwrap();
+            delete_environment(&pool, dev).await.unwrap();
+            assert_eq!(query_environments(&pool).await.unwrap().len(), 1);
+        });
+    }
+}
@@ -8,6 +8,8 @@ error_set! {
         Url(url::ParseError),
         #[display("无法解析的公共后缀: {0}")]
         PublicSuffix(publicsuffix::Error),
+        #[display("公共后缀列表无效")]
+        InvalidPublicSuffix,
     }
     FrameworkError := {
         #[display("Tauri 框架错误: {0}")]
@@ -22,7 +24,7 @@ error_set! {
     SetupError := {
         #[display("后台进程设置错误: {0}")]
         Task(delay_timer::error::TaskError),
-    } || DatabaseError || FrameworkError || ParseError
+    } || DatabaseError || FrameworkError || ParseError || HttpClientError
     TabError := StateError || FrameworkError || ParseError
     StateError := {
         #[display("无法获取主视图")]
@@ -41,8 +43,96 @@ error_set! {
         Fetch(reqwest::Error),
         #[display("计算耗时失败：{0}")]
         Time(time::error::IndeterminateOffset),
+        #[display("请求体 base64 解码失败：{0}")]
+        Base64(base64::DecodeError),
+        #[display("请求体不是有效的 JSON：{0}")]
+        Json(serde_json::Error),
+        #[display("读取上传文件失败：{0}")]
+        File(std::io::Error),
+        #[display("发送响应块失败：{0}")]
+        Channel(tauri::Error),
+        #[display("响应体超过大小限制")]
+        TooLarge,
+        #[display("请求已取消")]
+        Cancelled,
+        #[display("只有 HTTP 客户端可以上传本地文件")]
+        FileNotAllowed,
+        #[display("请求 id 已被进行中的请求使用：{request_id}")]
+        DuplicateRequest { request_id: String },
     }
-    SyncPublicSuffixError := FetchError || DatabaseError
+    HttpClientError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("创建 HTTP 客户端失败：{0}")]
+        Build(reqwest::Error),
+        #[display("读取证书失败：{0}")]
+        Certificate(std::io::Error),
+    } || DatabaseError
+    ReplayError := {
+        #[display("无法获取主视图")]
+        NoMainView
+    } || AuthError
+    AuthError := {
+        #[display("认证凭据不存在")]
+        NoCredential,
+        #[display("获取 OAuth2 令牌失败，状态码：{status}")]
+        TokenStatus { status: u16 },
+        #[display("OAuth2 令牌响应中没有 access_token")]
+        NoAccessToken,
+    } || FetchError || DatabaseError
+    SaveBodyError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("保存响应体失败：{0}")]
+        Io(std::io::Error),
+    } || FrameworkError
+    StreamError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("WebSocket 连接失败：{0}")]
+        WebSocket(tokio_tungstenite::tungstenite::Error),
+        #[display("无效的请求头：{0}")]
+        Header(tokio_tungstenite::tungstenite::http::Error),
+        #[display("WebSocket 未连接或已关闭")]
+        NotConnected,
+        #[display("连接 id 已被使用：{connection_id}")]
+        DuplicateConnection { connection_id: String },
+        #[display("无效的关闭码：{code}")]
+        InvalidCloseCode { code: u16 },
+    } || AuthError
+    HarError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("HAR 文件格式错误：{0}")]
+        Json(serde_json::Error),
+        #[display("读写 HAR 文件失败：{0}")]
+        File(std::io::Error),
+    } || DatabaseError || FrameworkError
+    RunError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+    } || DatabaseError
+    EnvironmentError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("无效的 JSONPath：{0}")]
+        JsonPath(serde_json_path::ParseError),
+        #[display("响应体不是有效的 JSON：{0}")]
+        Json(serde_json::Error),
+        #[display("JSONPath 未匹配到值")]
+        NoMatch,
+        #[display("未激活任何环境")]
+        NoActiveEnvironment,
+    } || DatabaseError
+    HotkeyError := {
+        #[display("无法获取主视图")]
+        NoMainView,
+        #[display("无法解析快捷键：{0}")]
+        Parse(::hotkey::HotkeyParseError),
+        #[display("{0}")]
+        Binding(::hotkey::BindingError),
+    } || DatabaseError
+    SyncPublicSuffixError := FetchError || DatabaseError || ParseError
     GetPublicSuffixError := ParseError || DatabaseError
 }
 
@@ -52,5 +142,14 @@ impl_serialize![
     FrameworkError,
     TabError,
     StateError,
-    FetchError
+    FetchError,
+    HttpClientError,
+    ReplayError,
+    AuthError,
+    SaveBodyError,
+    HarError,
+    RunError,
+    StreamError,
+    EnvironmentError,
+    HotkeyError
 ];
@@ -0,0 +1,635 @@
+use std::path::Path;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, types::Json};
+use time::OffsetDateTime;
+
+use crate::{
+    collection::{self, SavedRequest},
+    page::PageToken,
+    request::{FetchBody, FetchOptions, FormField, HttpHeader, Response},
+    request_log::{self, MAX_LOG_COUNT, RequestLog},
+};
+
+const HAR_VERSION: &str = "1.2";
+const HTTP_VERSION: &str = "HTTP/1.1";
+
+/// 导出 HAR 的来源：当前请求、集合（含子文件夹）或请求历史
+#[derive(Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HarSource {
+    Request {
+        url: String,
+        options: Option<Box<FetchOptions>>,
+        response: Option<Box<Response>>,
+    },
+    Collection {
+        id: i64,
+    },
+    History,
+}
+
+/// HTTP Archive 1.2，只包含 HTTP 客户端用到的字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_date_time: OffsetDateTime,
+    /// 总耗时，毫秒
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: serde_json::Map<String, serde_json::Value>,
+    pub timings: HarTimings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<HarNameValue>,
+    #[serde(default)]
+    pub headers: Vec<HarNameValue>,
+    #[serde(default)]
+    pub query_string: Vec<HarNameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<HarNameValue>,
+    #[serde(default)]
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(default, rename = "redirectURL")]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<HarParam>,
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarParam {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+/// 按来源收集 HAR 条目，集合包含所有子文件夹中的请求
+pub async fn query_entries(
+    pool: &SqlitePool,
+    source: HarSource,
+) -> Result<Vec<HarEntry>, sqlx::Error> {
+    match source {
+        HarSource::Request {
+            url,
+            options,
+            response,
+        } => {
+            let options = options.map(|options| *options).unwrap_or_default();
+            let entry = match response {
+                Some(response) => HarEntry::from_response(&url, &options, &response),
+                None => HarEntry::new(
+                    &url,
+                    &options,
+                    OffsetDateTime::now_utc(),
+                    HarResponse::empty(0),
+                    None,
+                ),
+            };
+            Ok(vec![entry])
+        }
+        HarSource::Collection { id } => Ok(collection::query_collection_requests(pool, id)
+            .await?
+            .iter()
+            .map(HarEntry::from_saved)
+            .collect()),
+        HarSource::History => {
+            let page_token = PageToken {
+                limit: MAX_LOG_COUNT as u32,
+                offset: 0,
+            };
+            let response = request_log::query_request_log(pool, "", page_token).await?;
+            Ok(response.logs.iter().map(HarEntry::from_log).collect())
+        }
+    }
+}
+
+/// 导入到指定集合，返回导入的请求数量
+pub async fn import(
+    pool: &SqlitePool,
+    collection_id: i64,
+    har: &Har,
+) -> Result<usize, sqlx::Error> {
+    for entry in &har.log.entries {
+        collection::save_request(pool, entry.to_saved(collection_id)).await?;
+    }
+    Ok(har.log.entries.len())
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+impl Har {
+    pub fn new(entries: Vec<HarEntry>) -> Self {
+        Self {
+            log: HarLog {
+                version: HAR_VERSION.to_string(),
+                creator: HarCreator {
+                    name: "white-hole".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries,
+            },
+        }
+    }
+}
+
+impl HarEntry {
+    /// 未发送或失败的请求 `status` 为 0
+    pub fn new(
+        url: &str,
+        options: &FetchOptions,
+        started_date_time: OffsetDateTime,
+        response: HarResponse,
+        elapsed_time: Option<i32>,
+    ) -> Self {
+        let time = elapsed_time.unwrap_or_default().max(0) as f64;
+        Self {
+            started_date_time,
+            time,
+            request: HarRequest::new(url, options),
+            response,
+            cache: Default::default(),
+            // 只记录了总耗时，全部计入等待时间
+            timings: HarTimings {
+                send: 0.,
+                wait: time,
+                receive: 0.,
+            },
+            comment: None,
+        }
+    }
+
+    pub fn from_response(url: &str, options: &FetchOptions, response: &Response) -> Self {
+        Self::new(
+            url,
+            options,
+            response.done_date,
+            HarResponse::from(response),
+            Some(response.elapsed_time),
+        )
+    }
+
+    pub fn from_saved(request: &SavedRequest) -> Self {
+        let mut entry = Self::new(
+            &request.url,
+            &request.to_options(),
+            request.update_time.unwrap_or_else(OffsetDateTime::now_utc),
+            HarResponse::empty(0),
+            None,
+        );
+        entry.comment = Some(request.name.clone());
+        entry
+    }
+
+    pub fn from_log(log: &RequestLog) -> Self {
+        let mut entry = Self::new(
+            &log.url,
+            &log.to_options(),
+            log.create_time,
+            HarResponse::empty(log.status.unwrap_or_default()),
+            log.elapsed_time,
+        );
+        entry.comment = log.error.clone();
+        entry
+    }
+
+    /// 转换为集合中保存的请求，以注释或 URL 命名
+    pub fn to_saved(&self, collection_id: i64) -> SavedRequest {
+        let HarRequest {
+            method,
+            url,
+            headers,
+            post_data,
+            ..
+        } = &self.request;
+        let headers: Vec<HttpHeader> = headers
+            .iter()
+            // 伪首部和长度由发送时重新生成
+            .filter(|header| {
+                !header.name.starts_with(':') && !header.name.eq_ignore_ascii_case("content-length")
+            })
+            .map(|header| HttpHeader {
+                key: header.name.clone(),
+                value: header.value.clone(),
+            })
+            .collect();
+
+        SavedRequest {
+            id: None,
+            collection_id,
+            name: self
+                .comment
+                .clone()
+                .filter(|comment| !comment.is_empty())
+                .unwrap_or_else(|| url.clone()),
+            method: method.to_uppercase(),
+            url: url.clone(),
+            headers: Json(headers),
+            body: post_data
+                .as_ref()
+                .map(|post_data| Json(post_data.to_body())),
+            credential_id: None,
+            assertions: Json(Vec::new()),
+            captures: Json(Vec::new()),
+            update_time: None,
+        }
+    }
+}
+
+impl HarRequest {
+    fn new(url: &str, options: &FetchOptions) -> Self {
+        let headers = options.headers.clone().unwrap_or_default();
+        let content_type = headers
+            .iter()
+            .find(|header| header.key.eq_ignore_ascii_case("content-type"))
+            .map(|header| header.value.clone());
+        let post_data = options
+            .body
+            .as_ref()
+            .map(|body| HarPostData::new(body, content_type));
+        let query_string = url::Url::parse(url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| HarNameValue {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            method: options.method.as_deref().unwrap_or("GET").to_uppercase(),
+            url: url.to_string(),
+            http_version: HTTP_VERSION.to_string(),
+            cookies: Vec::new(),
+            headers: headers.into_iter().map(HarNameValue::from).collect(),
+            query_string,
+            body_size: post_data
+                .as_ref()
+                .map_or(0, |post_data| post_data.text.len() as i64),
+            post_data,
+            headers_size: -1,
+        }
+    }
+}
+
+impl HarResponse {
+    fn empty(status: u16) -> Self {
+        Self {
+            status,
+            status_text: status_text(status),
+            http_version: HTTP_VERSION.to_string(),
+            cookies: Vec::new(),
+            headers: Vec::new(),
+            content: HarContent {
+                size: 0,
+                mime_type: String::new(),
+                text: None,
+                encoding: None,
+            },
+            redirect_url: String::new(),
+            headers_size: -1,
+            body_size: -1,
+        }
+    }
+}
+
+impl From<&Response> for HarResponse {
+    fn from(response: &Response) -> Self {
+        let header = |name: &str| {
+            response
+                .headers
+                .iter()
+                .find(|header| header.key.eq_ignore_ascii_case(name))
+                .map(|header| header.value.clone())
+                .unwrap_or_default()
+        };
+        // 非 UTF-8 的响应体按 base64 保存
+        let (text, encoding) = match std::str::from_utf8(&response.body) {
+            Ok(text) => (text.to_string(), None),
+            Err(_) => (STANDARD.encode(&response.body), Some("base64".to_string())),
+        };
+
+        Self {
+            status: response.status,
+            status_text: status_text(response.status),
+            http_version: HTTP_VERSION.to_string(),
+            cookies: Vec::new(),
+            headers: response
+                .headers
+                .iter()
+                .cloned()
+                .map(HarNameValue::from)
+                .collect(),
+            content: HarContent {
+                size: response.body.len() as i64,
+                mime_type: header("content-type"),
+                text: Some(text),
+                encoding,
+            },
+            redirect_url: header("location"),
+            headers_size: -1,
+            body_size: response.body.len() as i64,
+        }
+    }
+}
+
+impl HarPostData {
+    fn new(body: &FetchBody, content_type: Option<String>) -> Self {
+        let params = |fields: &[FormField]| {
+            fields
+                .iter()
+                .map(|field| HarParam {
+                    name: field.key.clone(),
+                    value: (!field.file).then(|| field.value.clone()),
+                    // 只导出文件名，HAR 用于分享，不能带出本地路径
+                    file_name: field.file.then(|| {
+                        Path::new(&field.value)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default()
+                    }),
+                })
+                .collect()
+        };
+        let (mime_type, params, text) = match body {
+            FetchBody::Text(text) => ("text/plain", Vec::new(), text.clone()),
+            // HAR 1.2 的请求体只能是文本，二进制内容有损转换
+            FetchBody::Bytes(bytes) => (
+                "application/octet-stream",
+                Vec::new(),
+                String::from_utf8_lossy(bytes).into_owned(),
+            ),
+            FetchBody::Base64 { base64 } => (
+                "application/octet-stream",
+                Vec::new(),
+                STANDARD
+                    .decode(base64)
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                    .unwrap_or_default(),
+            ),
+            FetchBody::Json { json } => ("application/json", Vec::new(), json.clone()),
+            FetchBody::Form { form } => (
+                "application/x-www-form-urlencoded",
+                params(form),
+                url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(form.iter().map(|field| (&field.key, &field.value)))
+                    .finish(),
+            ),
+            FetchBody::Multipart { multipart } => {
+                ("multipart/form-data", params(multipart), String::new())
+            }
+        };
+
+        Self {
+            mime_type: content_type.unwrap_or_else(|| mime_type.to_string()),
+            params,
+            text,
+        }
+    }
+
+    fn to_body(&self) -> FetchBody {
+        let mime_type = self.mime_type.to_lowercase();
+        // fileName 只是文件名，不能当作本地路径读取，按普通字段导入
+        let fields = || {
+            self.params
+                .iter()
+                .map(|param| FormField {
+                    key: param.name.clone(),
+                    value: param.value.clone().unwrap_or_default(),
+                    file: false,
+                })
+                .collect()
+        };
+
+        if mime_type.starts_with("multipart/form-data") && !self.params.is_empty() {
+            FetchBody::Multipart {
+                multipart: fields(),
+            }
+        } else if mime_type.starts_with("application/x-www-form-urlencoded") {
+            let form = if self.params.is_empty() {
+                url::form_urlencoded::parse(self.text.as_bytes())
+                    .map(|(key, value)| FormField {
+                        key: key.into_owned(),
+                        value: value.into_owned(),
+                        file: false,
+                    })
+                    .collect()
+            } else {
+                fields()
+            };
+            FetchBody::Form { form }
+        } else if mime_type.contains("json") {
+            FetchBody::Json {
+                json: self.text.clone(),
+            }
+        } else {
+            FetchBody::Text(self.text.clone())
+        }
+    }
+}
+
+impl From<HttpHeader> for HarNameValue {
+    fn from(HttpHeader { key, value }: HttpHeader) -> Self {
+        Self { name: key, value }
+    }
+}
+
+fn status_text(status: u16) -> String {
+    reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_har() {
+        let options = FetchOptions {
+            method: Some("post".to_string()),
+            headers: Some(vec![HttpHeader {
+                key: "X-Trace".to_string(),
+                value: "1".to_string(),
+            }]),
+            body: Some(FetchBody::Form {
+                form: vec![FormField {
+                    key: "q".to_string(),
+                    value: "white hole".to_string(),
+                    file: false,
+                }],
+            }),
+            ..Default::default()
+        };
+        let response = Response {
+            done_date: OffsetDateTime::UNIX_EPOCH,
+            status: 201,
+            headers: vec![HttpHeader {
+                key: "content-type".to_string(),
+                value: "application/octet-stream".to_string(),
+            }],
+            body: vec![0, 255],
+            elapsed_time: 42,
+        };
+        let har = Har::new(vec![HarEntry::from_response(
+            "https://example.com/api?page=2",
+            &options,
+            &response,
+        )]);
+
+        let json = serde_json::to_value(&har).unwrap();
+        let entry = &json["log"]["entries"][0];
+        assert_eq!(json["log"]["version"], "1.2");
+        assert_eq!(entry["startedDateTime"], "1970-01-01T00:00:00Z");
+        assert_eq!(entry["time"], 42.);
+        assert_eq!(entry["timings"]["wait"], 42.);
+        assert_eq!(entry["request"]["method"], "POST");
+        assert_eq!(entry["request"]["queryString"][0]["value"], "2");
+        assert_eq!(entry["request"]["postData"]["text"], "q=white+hole");
+        assert_eq!(entry["response"]["statusText"], "Created");
+        assert_eq!(entry["response"]["content"]["text"], "AP8=");
+        assert_eq!(entry["response"]["content"]["encoding"], "base64");
+
+        // 导入后还原为同样的请求
+        let har: Har = serde_json::from_value(json).unwrap();
+        let saved = har.log.entries[0].to_saved(1);
+        assert_eq!(saved.method, "POST");
+        assert_eq!(saved.url, "https://example.com/api?page=2");
+        assert_eq!(saved.headers.0, options.headers.unwrap());
+        assert_eq!(saved.body.map(|body| body.0), options.body);
+    }
+
+    #[test]
+    fn test_import() {
+        let har: Har = serde_json::from_str(
+            r#"{"log":{"version":"1.2","creator":{"name":"browser","version":"1"},"entries":[{
+                "startedDateTime":"2026-10-18T10:00:00.123+08:00","time":12.5,
+                "request":{"method":"POST","url":"https://example.com/upload","httpVersion":"HTTP/2",
+                    "headers":[{"name":":authority","value":"example.com"},{"name":"content-length","value":"10"}],
+                    "postData":{"mimeType":"multipart/form-data; boundary=x","params":[
+                        {"name":"name","value":"white hole"},{"name":"file","fileName":"a.txt"}]}},
+                "response":{"status":200,"content":{"size":0}},
+                "timings":{"send":1,"wait":10,"receive":1.5}}]}}"#,
+        )
+        .unwrap();
+
+        let saved = har.log.entries[0].to_saved(1);
+        assert_eq!(saved.name, "https://example.com/upload");
+        assert!(saved.headers.is_empty());
+        let Some(Json(FetchBody::Multipart { multipart })) = saved.body else {
+            panic!("应导入为 multipart");
+        };
+        assert!(!multipart[0].file);
+        assert_eq!(multipart[1].value, "");
+        assert!(!multipart[1].file);
+    }
+
+    #[test]
+    fn test_export_file_name() {
+        let body = FetchBody::Multipart {
+            multipart: vec![FormField {
+                key: "key".to_string(),
+                value: "/home/alice/secrets/a.pem".to_string(),
+                file: true,
+            }],
+        };
+        let post_data = HarPostData::new(&body, None);
+        assert_eq!(post_data.params[0].file_name.as_deref(), Some("a.pem"));
+        assert_eq!(post_data.params[0].value, None);
+    }
+}
@@ -1,10 +1,16 @@
-use hotkey::{Code, Modifiers, hotkey};
+use std::time::Duration;
+
+use hotkey::{Trigger, hotkey};
 use log::error;
 use tauri::AppHandle;
 
-use crate::browser::BrowserExt as _;
+use crate::{browser::BrowserExt as _, vim::VimCommand};
+
+/// Vim 模式下按住滚动键时的重复间隔
+const VIM_SCROLL_INTERVAL: Duration = Duration::from_millis(50);
 
-#[hotkey([(Modifiers::CONTROL, Code::KeyR), (Modifiers::empty(), Code::F5)])]
+/// 刷新页面
+#[hotkey("ctrl+r", "F5", scope = "tab", category = "导航")]
 async fn reload(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.reload().await {
@@ -12,7 +18,14 @@ async fn reload(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::ALT, Code::ArrowLeft)]
+/// 后退
+#[hotkey(
+    "alt+ArrowLeft",
+    "MouseBack",
+    "GestureLeft",
+    scope = "tab",
+    category = "导航"
+)]
 async fn back(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.back().await {
@@ -20,7 +33,14 @@ async fn back(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::ALT, Code::ArrowRight)]
+/// 前进
+#[hotkey(
+    "alt+ArrowRight",
+    "MouseForward",
+    "GestureRight",
+    scope = "tab",
+    category = "导航"
+)]
 async fn forward(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.forward().await {
@@ -28,7 +48,8 @@ async fn forward(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::CONTROL, Code::KeyL)]
+/// 打开搜索视图
+#[hotkey("ctrl+l", category = "视图")]
 async fn focus(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.focus().await {
@@ -39,7 +60,8 @@ async fn focus(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::empty(), Code::Escape)]
+/// 焦点离开搜索视图
+#[hotkey("Escape", scope = "mainview", category = "视图")]
 async fn blur(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.blur().await {
@@ -50,7 +72,13 @@ async fn blur(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::CONTROL, Code::KeyW)]
+/// 关闭标签页
+#[hotkey(
+    "ctrl+w",
+    "GestureDown GestureRight",
+    scope = "tab",
+    category = "标签页"
+)]
 async fn close_tab(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.close_tab().await {
@@ -61,7 +89,8 @@ async fn close_tab(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::CONTROL, Code::Tab)]
+/// 下一标签页
+#[hotkey("ctrl+tab", scope = "tab", category = "标签页")]
 async fn next_tab(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.next_tab().await {
@@ -72,7 +101,8 @@ async fn next_tab(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::CONTROL | Modifiers::SHIFT, Code::Tab)]
+/// 上一标签页
+#[hotkey("ctrl+shift+tab", scope = "tab", category = "标签页")]
 async fn near_tab(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.near_tab().await {
@@ -83,7 +113,8 @@ async fn near_tab(app_handle: AppHandle) {
     }
 }
 
-#[hotkey(Modifiers::empty(), Code::F11)]
+/// 全屏切换
+#[hotkey("F11", scope = "tab", category = "视图")]
 async fn fullscreen(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.fullscreen().await {
@@ -91,14 +122,16 @@ async fn fullscreen(app_handle: AppHandle) {
     }
 }
 
-/// ？使用惯用快捷键会触发 devtools 快捷键，所以只能自定义快捷键
-#[hotkey(Modifiers::CONTROL, Code::KeyD)]
+// ？使用惯用快捷键会触发 devtools 快捷键，所以只能自定义快捷键
+/// 开发者工具
+#[hotkey("ctrl+d", category = "工具")]
 async fn devtools(app_handle: AppHandle) {
     let browser = app_handle.browser();
     browser.devtools().await;
 }
 
-#[hotkey(Modifiers::CONTROL, Code::KeyI)]
+/// 无痕浏览
+#[hotkey("ctrl+i", category = "标签页")]
 async fn incognito(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.incognito().await {
@@ -107,10 +140,102 @@ async fn incognito(app_handle: AppHandle) {
     focus(app_handle).await;
 }
 
-#[hotkey(Modifiers::CONTROL, Code::KeyP)]
+/// 打印页面
+#[hotkey("ctrl+p", category = "工具")]
 async fn print(app_handle: AppHandle) {
     let browser = app_handle.browser();
     if let Err(e) = browser.print().await {
         error!("打印失败：{e}");
     }
 }
+
+/// 快捷键速查表
+#[hotkey("ctrl+/", category = "视图")]
+async fn cheat_sheet(app_handle: AppHandle) {
+    let browser = app_handle.browser();
+    if let Err(e) = browser.cheat_sheet().await {
+        error!("打开快捷键速查表失败：{e}");
+    }
+    if let Err(e) = browser.focus_changed().await {
+        error!("焦点变化失败：{e}");
+    }
+}
+
+/// Vim 模式：向下滚动
+#[hotkey("j", scope = "vim", trigger = Trigger::Repeat(VIM_SCROLL_INTERVAL), category = "Vim")]
+async fn vim_scroll_down(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::ScrollDown).await;
+}
+
+/// Vim 模式：向上滚动
+#[hotkey("k", scope = "vim", trigger = Trigger::Repeat(VIM_SCROLL_INTERVAL), category = "Vim")]
+async fn vim_scroll_up(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::ScrollUp).await;
+}
+
+/// Vim 模式：滚动到顶部
+#[hotkey("g g", scope = "vim", category = "Vim")]
+async fn vim_scroll_top(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::ScrollTop).await;
+}
+
+/// Vim 模式：滚动到底部
+#[hotkey("shift+g", scope = "vim", category = "Vim")]
+async fn vim_scroll_bottom(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::ScrollBottom).await;
+}
+
+/// Vim 模式：链接提示，输入提示字母点击元素
+#[hotkey("f", scope = "vim", category = "Vim")]
+async fn vim_hints(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::Hints).await;
+}
+
+/// Vim 模式：链接提示，在新标签页打开链接
+#[hotkey("shift+f", scope = "vim", category = "Vim")]
+async fn vim_hints_new_tab(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::HintsNewTab).await;
+}
+
+/// Vim 模式：页内查找
+#[hotkey("/", scope = "vim", category = "Vim")]
+async fn vim_find(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::Find).await;
+}
+
+/// Vim 模式：下一个查找结果
+#[hotkey("n", scope = "vim", category = "Vim")]
+async fn vim_find_next(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::FindNext).await;
+}
+
+/// Vim 模式：上一个查找结果
+#[hotkey("shift+n", scope = "vim", category = "Vim")]
+async fn vim_find_previous(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::FindPrevious).await;
+}
+
+/// Vim 模式：进入插入模式，按键交给页面直到按 Esc
+#[hotkey("i", scope = "vim", category = "Vim")]
+async fn vim_insert(app_handle: AppHandle) {
+    vim_command(&app_handle, VimCommand::Insert).await;
+}
+
+/// Vim 模式：后退
+#[hotkey("shift+h", scope = "vim", category = "Vim")]
+async fn vim_back(app_handle: AppHandle) {
+    back(app_handle).await;
+}
+
+/// Vim 模式：前进
+#[hotkey("shift+l", scope = "vim", category = "Vim")]
+async fn vim_forward(app_handle: AppHandle) {
+    forward(app_handle).await;
+}
+
+async fn vim_command(app_handle: &AppHandle, command: VimCommand) {
+    let browser = app_handle.browser();
+    if let Err(e) = browser.vim_command(command).await {
+        error!("执行 Vim 命令 {command:?} 失败：{e}");
+    }
+}
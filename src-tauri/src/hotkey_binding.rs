@@ -0,0 +1,137 @@
+use ::hotkey::{ActionBinding, HotkeyManager, KeySequence};
+use log::error;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::Runtime;
+
+/// 动作的描述、分类、当前快捷键和默认快捷键，快捷键为 `KeySequence` 的字符串形式，
+/// `labels`、`default_labels` 为当前键盘布局下给用户看的名称
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HotkeyBinding {
+    pub action: String,
+    pub scope: String,
+    pub description: String,
+    pub category: String,
+    pub hotkeys: Vec<String>,
+    pub defaults: Vec<String>,
+    pub labels: Vec<String>,
+    pub default_labels: Vec<String>,
+}
+
+impl From<ActionBinding> for HotkeyBinding {
+    fn from(binding: ActionBinding) -> Self {
+        Self {
+            action: binding.action,
+            scope: binding.scope,
+            description: binding.description,
+            category: binding.category,
+            hotkeys: binding.hotkeys.iter().map(KeySequence::to_string).collect(),
+            defaults: binding
+                .defaults
+                .iter()
+                .map(KeySequence::to_string)
+                .collect(),
+            labels: binding.labels,
+            default_labels: binding.default_labels,
+        }
+    }
+}
+
+/// 启动时用保存的自定义绑定覆盖默认快捷键，无效的记录只记日志。
+/// 所有记录一起替换，保存的互换绑定不受加载顺序影响
+pub async fn load_bindings<R: Runtime>(
+    pool: &SqlitePool,
+    manager: &HotkeyManager<R>,
+) -> Result<(), sqlx::Error> {
+    let records = sqlx::query!("select action, hotkeys from hotkey_binding")
+        .fetch_all(pool)
+        .await?;
+
+    let mut bindings = Vec::with_capacity(records.len());
+    for record in records {
+        let hotkeys: Result<Vec<KeySequence>, _> = record.hotkeys.lines().map(str::parse).collect();
+        match hotkeys {
+            Ok(hotkeys) => bindings.push((record.action, hotkeys)),
+            Err(e) => error!("加载快捷键 {} 失败：{e}", record.action),
+        }
+    }
+    for (action, e) in manager.rebind_all(bindings) {
+        error!("加载快捷键 {action} 失败：{e}");
+    }
+    Ok(())
+}
+
+/// 每行一个快捷键序列，空字符串表示已解绑
+pub async fn save_binding(
+    pool: &SqlitePool,
+    action: &str,
+    hotkeys: &[KeySequence],
+) -> Result<(), sqlx::Error> {
+    let hotkeys = hotkeys
+        .iter()
+        .map(KeySequence::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    sqlx::query!(
+        "insert or replace into hotkey_binding (action, hotkeys) values (?, ?)",
+        action,
+        hotkeys
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn delete_binding(pool: &SqlitePool, action: &str) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from hotkey_binding where action = ?", action)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ::hotkey::{Code, Hotkey, Modifiers};
+
+    use super::*;
+
+    #[test]
+    fn test_save_binding() {
+        tauri::async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let hotkeys = [
+                Hotkey::new(Modifiers::CONTROL | Modifiers::SHIFT, Code::Tab).into(),
+                Hotkey::new(Modifiers::META, Code::KeyW).into(),
+                KeySequence::new(vec![
+                    Hotkey::new(Modifiers::CONTROL, Code::KeyK),
+                    Hotkey::new(Modifiers::empty(), Code::F5),
+                ]),
+            ];
+            save_binding(&pool, "close_tab", &hotkeys).await.unwrap();
+            save_binding(&pool, "devtools", &[]).await.unwrap();
+
+            let records =
+                sqlx::query!("select action, hotkeys from hotkey_binding order by action")
+                    .fetch_all(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(records.len(), 2);
+            let saved: Vec<KeySequence> = records[0]
+                .hotkeys
+                .lines()
+                .map(|hotkey| hotkey.parse().unwrap())
+                .collect();
+            assert_eq!(saved, hotkeys);
+            assert_eq!(records[1].hotkeys.lines().count(), 0);
+
+            delete_binding(&pool, "devtools").await.unwrap();
+            let count = sqlx::query_scalar!("select count(*) from hotkey_binding")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(count, 1);
+        });
+    }
+}
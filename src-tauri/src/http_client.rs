@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use log::{error, info};
+use reqwest::{Certificate, Client, Proxy, redirect::Policy};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{Manager, State, Wry, async_runtime::RwLock};
+
+use crate::{error::HttpClientError, user_agent::get_user_agent};
+
+/// 后端共享 HTTP 客户端配置；超时单位为秒，0 表示不限制
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpClientConfig {
+    /// 空闲超时，即两次收到数据之间的最长等待时间；不限制总时长，流式响应和大文件下载不会被中途截断
+    pub timeout: u32,
+    pub connect_timeout: u32,
+    /// 最大重定向次数，0 表示不跟随重定向
+    pub max_redirects: u32,
+    /// 支持 http://、https://、socks5://、socks5h://；不作用于 WebSocket 连接
+    pub proxy: Option<String>,
+    /// 额外信任的 PEM 证书文件路径；不作用于 WebSocket 连接
+    pub ca_certificates: Vec<String>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: 30,
+            connect_timeout: 10,
+            max_redirects: 10,
+            proxy: None,
+            ca_certificates: Vec::new(),
+        }
+    }
+}
+
+/// 所有后端请求共用的客户端，配置变更时重建
+pub struct HttpClient {
+    config: RwLock<HttpClientConfig>,
+    client: RwLock<Client>,
+    stream_client: RwLock<Client>,
+}
+
+impl HttpClient {
+    pub async fn new(pool: &SqlitePool) -> Result<Self, HttpClientError> {
+        let config = get_config(pool).await.unwrap_or_else(|e| {
+            error!("读取 HTTP 客户端配置失败：{e}");
+            HttpClientConfig::default()
+        });
+
+        let (config, client) = match build_client(&config) {
+            Ok(client) => (config, client),
+            Err(e) => {
+                // 代理或证书失效时，回退默认配置，避免无法启动
+                error!("创建 HTTP 客户端失败：{e}");
+                let config = HttpClientConfig::default();
+                let client = build_client(&config)?;
+                (config, client)
+            }
+        };
+
+        let stream_client = build_stream_client(&config)?;
+        Ok(Self {
+            config: RwLock::new(config),
+            client: RwLock::new(client),
+            stream_client: RwLock::new(stream_client),
+        })
+    }
+
+    pub async fn get(&self) -> Client {
+        self.client.read().await.clone()
+    }
+
+    /// SSE 等长连接使用，服务端可能长时间不发送数据，不设空闲超时
+    pub async fn get_stream(&self) -> Client {
+        self.stream_client.read().await.clone()
+    }
+
+    pub async fn config(&self) -> HttpClientConfig {
+        self.config.read().await.clone()
+    }
+
+    pub async fn set_config(
+        &self,
+        pool: &SqlitePool,
+        config: HttpClientConfig,
+    ) -> Result<(), HttpClientError> {
+        // 先确认新配置可用，再保存
+        let client = build_client(&config)?;
+        let stream_client = build_stream_client(&config)?;
+        save_config(pool, &config).await?;
+
+        *self.client.write().await = client;
+        *self.stream_client.write().await = stream_client;
+        *self.config.write().await = config;
+        Ok(())
+    }
+}
+
+pub trait HttpClientExt {
+    fn http_client(&self) -> State<'_, HttpClient>;
+}
+
+impl<T: Manager<Wry>> HttpClientExt for T {
+    fn http_client(&self) -> State<'_, HttpClient> {
+        self.state::<HttpClient>()
+    }
+}
+
+pub fn build_client(config: &HttpClientConfig) -> Result<Client, HttpClientError> {
+    let user_agent = get_user_agent();
+    info!("User-Agent: {}", user_agent);
+
+    let mut builder = Client::builder()
+        .user_agent(user_agent)
+        .referer(false)
+        .redirect(if config.max_redirects == 0 {
+            Policy::none()
+        } else {
+            Policy::limited(config.max_redirects as usize)
+        });
+
+    if config.timeout > 0 {
+        builder = builder.read_timeout(Duration::from_secs(config.timeout.into()));
+    }
+    if config.connect_timeout > 0 {
+        builder = builder.connect_timeout(Duration::from_secs(config.connect_timeout.into()));
+    }
+    if let Some(proxy) = config.proxy.as_deref().filter(|proxy| !proxy.is_empty()) {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+    for path in &config.ca_certificates {
+        let pem = std::fs::read(path)?;
+        for certificate in Certificate::from_pem_bundle(&pem)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// 除空闲超时外与 `build_client` 相同
+pub fn build_stream_client(config: &HttpClientConfig) -> Result<Client, HttpClientError> {
+    build_client(&HttpClientConfig {
+        timeout: 0,
+        ..config.clone()
+    })
+}
+
+async fn get_config(pool: &SqlitePool) -> Result<HttpClientConfig, sqlx::Error> {
+    let record = sqlx::query!(
+        r#"select timeout as "timeout: u32", connect_timeout as "connect_timeout: u32", max_redirects as "max_redirects: u32", proxy, ca_certificates from http_client_config where id = 1"#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(HttpClientConfig {
+        timeout: record.timeout,
+        connect_timeout: record.connect_timeout,
+        max_redirects: record.max_redirects,
+        proxy: record.proxy,
+        ca_certificates: record
+            .ca_certificates
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect(),
+    })
+}
+
+async fn save_config(pool: &SqlitePool, config: &HttpClientConfig) -> Result<(), sqlx::Error> {
+    let ca_certificates = config.ca_certificates.join("\n");
+    sqlx::query!(
+        "insert or replace into http_client_config (id, timeout, connect_timeout, max_redirects, proxy, ca_certificates) values (1, ?, ?, ?, ?, ?)",
+        config.timeout,
+        config.connect_timeout,
+        config.max_redirects,
+        config.proxy,
+        ca_certificates
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read as _, Write as _},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    /// 本地 HTTP 替身：按间隔逐块发送分块响应体
+    fn serve_chunks(chunks: &'static [&'static str], interval: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+                )
+                .unwrap();
+            for chunk in chunks {
+                thread::sleep(interval);
+                let _ = write!(stream, "{:x}\r\n{chunk}\r\n", chunk.len());
+            }
+            let _ = stream.write_all(b"0\r\n\r\n");
+        });
+        url
+    }
+
+    #[test]
+    fn test_timeout_is_idle() {
+        tauri::async_runtime::block_on(async {
+            let client = build_client(&HttpClientConfig {
+                timeout: 1,
+                ..Default::default()
+            })
+            .unwrap();
+
+            // 总时长超过超时，但一直有数据，不会被截断
+            let url = serve_chunks(&["a", "b", "c", "d"], Duration::from_millis(400));
+            let body = client.get(url).send().await.unwrap().text().await.unwrap();
+            assert_eq!(body, "abcd");
+
+            // 超过超时没有数据时失败
+            let url = serve_chunks(&["a"], Duration::from_millis(1500));
+            let result = match client.get(url).send().await {
+                Ok(response) => response.text().await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            assert!(result.unwrap_err().is_timeout());
+        });
+    }
+
+    #[test]
+    fn test_stream_client() {
+        tauri::async_runtime::block_on(async {
+            let client = build_stream_client(&HttpClientConfig {
+                timeout: 1,
+                ..Default::default()
+            })
+            .unwrap();
+
+            // 两个事件之间的空闲时间超过超时，SSE 连接仍保持
+            let url = serve_chunks(&["data: a\n\n", "data: b\n\n"], Duration::from_millis(1500));
+            let events = std::sync::Mutex::new(Vec::new());
+            crate::sse::subscribe(&client, &url, None, |event| {
+                events.lock().unwrap().push(event);
+                Ok(())
+            })
+            .await
+            .unwrap();
+            let data: Vec<_> = events
+                .into_inner()
+                .unwrap()
+                .into_iter()
+                .filter_map(|event| match event {
+                    crate::sse::SseEvent::Message { data, .. } => Some(data),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(data, ["a", "b"]);
+        });
+    }
+
+    #[test]
+    fn test_config() {
+        tauri::async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let http_client = HttpClient::new(&pool).await.unwrap();
+            assert_eq!(http_client.config().await, HttpClientConfig::default());
+
+            let config = HttpClientConfig {
+                timeout: 0,
+                max_redirects: 0,
+                proxy: Some("socks5h://127.0.0.1:1080".to_string()),
+                ..Default::default()
+            };
+            http_client.set_config(&pool, config.clone()).await.unwrap();
+            assert_eq!(get_config(&pool).await.unwrap(), config);
+
+            // 无效配置不会覆盖已保存的配置
+            let invalid = HttpClientConfig {
+                ca_certificates: vec!["/nonexistent/ca.pem".to_string()],
+                ..Default::default()
+            };
+            assert!(http_client.set_config(&pool, invalid).await.is_err());
+            assert_eq!(http_client.config().await, config);
+            assert_eq!(get_config(&pool).await.unwrap(), config);
+        });
+    }
+}
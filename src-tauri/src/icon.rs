@@ -1,22 +1,22 @@
-use std::sync::OnceLock;
-
 use cached::proc_macro::cached;
 use get_data_url::GetDataUrl;
-use log::{error, info};
+use log::error;
 use reqwest::Client;
 use sqlx::{SqlitePool, sqlite::SqliteQueryResult};
 use tauri::async_runtime;
 
-use crate::{error::IconError, user_agent::get_user_agent};
-
-static GET_DATA_URL: OnceLock<GetDataUrl> = OnceLock::new();
+use crate::error::IconError;
 
 #[cached(
     key = "String",
     convert = r#"{ String::from(icon_url) }"#,
     result = true
 )]
-pub async fn get_icon_data_url(pool: &SqlitePool, icon_url: &str) -> Result<String, IconError> {
+pub async fn get_icon_data_url(
+    pool: &SqlitePool,
+    client: &Client,
+    icon_url: &str,
+) -> Result<String, IconError> {
     if let Ok(Some(record)) = sqlx::query!(
         "select data_url as 'data_url!' from icon_cached where url = ? and data_url like 'data:%' and update_time > datetime('now', '-1 month', 'localtime')",
         icon_url
@@ -29,21 +29,10 @@ pub async fn get_icon_data_url(pool: &SqlitePool, icon_url: &str) -> Result<Stri
 
     async_runtime::spawn({
         let pool = pool.clone();
+        let get_date_url = GetDataUrl::with_client(client.clone());
         let url = icon_url.to_owned();
 
         async move {
-            let get_date_url = GET_DATA_URL.get_or_init(|| {
-                let user_agent = get_user_agent();
-                info!("User-Agent: {}", user_agent);
-                let Ok(client) = Client::builder()
-                    .user_agent(user_agent)
-                    .referer(false)
-                    .build()
-                else {
-                    return GetDataUrl::new();
-                };
-                GetDataUrl::with_client(client)
-            });
             if let Ok(data_url) = get_date_url
                 .fetch(&url)
                 .await
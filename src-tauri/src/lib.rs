@@ -15,12 +15,20 @@ use crate::{
     user_agent::setup_user_agent,
 };
 
+mod assertion;
+mod auth;
 mod browser;
+mod capture;
+mod collection;
 mod command;
 mod darkreader;
 mod database;
+mod environment;
 mod error;
+mod har;
 mod hotkey;
+mod hotkey_binding;
+mod http_client;
 mod icon;
 mod log;
 mod macros;
@@ -29,12 +37,18 @@ mod page;
 mod prevent_default;
 mod public_suffix;
 mod request;
+mod request_log;
+mod runner;
+mod site;
+mod sse;
 mod state;
 mod tab;
 mod task;
 mod update;
 mod url;
 mod user_agent;
+mod vim;
+mod websocket;
 
 pub trait IsMainView {
     const TITLE_HEIGHT: f64 = 40.;
@@ -106,7 +120,54 @@ pub fn run() -> Result<(), SetupError> {
             blur_link,
             click_link,
             darkreader,
+            capture,
+            capture_request,
+            query_captured_requests,
+            clear_captured_requests,
             fetch,
+            fetch_stream,
+            subscribe_sse,
+            connect_websocket,
+            send_websocket,
+            close_websocket,
+            ack_fetch,
+            cancel_fetch,
+            query_request_log,
+            delete_request_log,
+            replay_request_log,
+            query_collections,
+            save_collection,
+            delete_collection,
+            save_request,
+            delete_saved_request,
+            replay_saved_request,
+            get_http_client_config,
+            set_http_client_config,
+            query_environments,
+            save_environment,
+            delete_environment,
+            set_active_environment,
+            save_variable,
+            delete_variable,
+            capture_variable,
+            query_credentials,
+            save_credential,
+            delete_credential,
+            save_response_body,
+            export_har,
+            import_har,
+            run_collection,
+            check_assertions,
+            list_hotkeys,
+            set_hotkey_binding,
+            reset_hotkey_binding,
+            set_hotkey_enabled,
+            set_keyboard_layout,
+            mouse_button,
+            mouse_gesture,
+            vim,
+            vim_mode_changed,
+            vim_open_link,
         ])
         .on_window_event(on_window_event)
         .on_device_event(on_device_event)
@@ -8,10 +8,15 @@ use time::OffsetDateTime;
 use crate::{
     icon::save_icon,
     page::{PageToken, Paginator as _},
+    public_suffix::get_public_suffix_cached,
+    site::site_of_url,
     state::BrowserState,
     url::encode,
 };
 
+/// 补全站点时每批处理的记录数
+const FILL_SITE_BATCH_SIZE: i64 = 500;
+
 #[derive(Clone, Default, Serialize)]
 pub struct QueryLogResponse {
     pub next_page_token: Option<PageToken>,
@@ -26,6 +31,7 @@ pub struct NavigationLog {
     pub star: bool,
     pub id: Option<i64>,
     pub last_time: Option<OffsetDateTime>,
+    pub site: Option<String>,
 }
 
 pub async fn save_log(
@@ -79,11 +85,18 @@ pub async fn save_log(
 
         id
     } else {
+        // 无 host 的 URL 记为空字符串，避免被 fill_site 反复处理
+        let site = get_public_suffix_cached(pool)
+            .await
+            .ok()
+            .and_then(|list| site_of_url(&list, &url))
+            .unwrap_or_default();
         let result = sqlx::query!(
-            "insert into navigation_log (url, title, icon_id, star, times, last_time) values (?, ?, ?, false, 0, datetime('now', 'localtime')) on conflict(url) do update set title = ?, icon_id = ?, times = times + 1",
+            "insert into navigation_log (url, title, icon_id, star, times, last_time, site) values (?, ?, ?, false, 0, datetime('now', 'localtime'), ?) on conflict(url) do update set title = ?, icon_id = ?, times = times + 1",
             url,
             title,
             icon_id,
+            site,
             title,
             icon_id,
         )
@@ -120,14 +133,19 @@ pub async fn get_id(pool: &SqlitePool, url: &str) -> Option<i64> {
     .map(|record| record.id)
 }
 
+/// `group_by_site` 为真时每个站点只返回最近访问的一条记录
 pub async fn query_log(
     pool: &SqlitePool,
     keyword: &str,
+    group_by_site: bool,
     page_token: PageToken,
 ) -> Result<QueryLogResponse, sqlx::Error> {
-    let mut query_builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(
-        "select a.id, a.url, a.title, b.data_url as icon_url, a.star, a.last_time from navigation_log a left outer join icon_cached b on a.icon_id = b.id where 1 = 1 ",
-    );
+    // SQLite 中与 max() 同时查询的裸列取自最大值所在行
+    let mut query_builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(if group_by_site {
+        "select a.id, a.url, a.title, b.data_url as icon_url, a.star, max(a.last_time) as last_time, a.site from navigation_log a left outer join icon_cached b on a.icon_id = b.id where 1 = 1 "
+    } else {
+        "select a.id, a.url, a.title, b.data_url as icon_url, a.star, a.last_time, a.site from navigation_log a left outer join icon_cached b on a.icon_id = b.id where 1 = 1 "
+    });
     let mut is_empty = true;
     for keyword in keyword.split_whitespace() {
         if keyword.is_empty() {
@@ -144,8 +162,12 @@ pub async fn query_log(
         is_empty = false;
     }
 
+    if group_by_site {
+        query_builder.push("group by coalesce(nullif(a.site, ''), a.url) ");
+    }
+
     if is_empty {
-        query_builder.push("order by a.last_time desc ");
+        query_builder.push("order by last_time desc ");
     } else {
         query_builder.push("order by a.star desc, a.times desc, length(a.url), a.last_time desc ");
     }
@@ -173,7 +195,7 @@ pub async fn query_log_by_id(
     ids: &[i64],
 ) -> Result<Vec<NavigationLog>, sqlx::Error> {
     let mut query_builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(
-        "select a.id, a.url, a.title, b.data_url as icon_url, a.star, a.last_time from navigation_log a left outer join icon_cached b on a.icon_id = b.id where a.id in (",
+        "select a.id, a.url, a.title, b.data_url as icon_url, a.star, a.last_time, a.site from navigation_log a left outer join icon_cached b on a.icon_id = b.id where a.id in (",
     );
     let mut separated = query_builder.separated(", ");
     for id in ids {
@@ -194,6 +216,38 @@ pub async fn clear_log(pool: &SqlitePool) -> Result<(), sqlx::Error> {
     Ok(())
 }
 
+/// 分批补全历史记录的站点直到全部补全，每批在一个事务中提交；无法计算站点的记录存为空字符串
+pub async fn fill_site(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let Ok(list) = get_public_suffix_cached(pool).await else {
+        return Ok(());
+    };
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let records = sqlx::query!(
+            r#"select id as "id!", url from navigation_log where site is null limit ?"#,
+            FILL_SITE_BATCH_SIZE
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        for record in records {
+            let site = site_of_url(&list, &record.url).unwrap_or_default();
+            sqlx::query!(
+                "update navigation_log set site = ? where id = ?",
+                site,
+                record.id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+    }
+}
+
 impl From<BrowserState> for NavigationLog {
     fn from(state: BrowserState) -> Self {
         Self {
@@ -204,3 +258,52 @@ impl From<BrowserState> for NavigationLog {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::public_suffix::CACHE_TEST_LOCK;
+
+    use super::*;
+
+    #[test]
+    fn test_fill_site() {
+        let _lock = CACHE_TEST_LOCK.lock().unwrap();
+        async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            // 超过一批的记录一次补全
+            let count = FILL_SITE_BATCH_SIZE * 2 + 1;
+            for i in 0..count {
+                let url = format!("https://s{i}.example.com/");
+                sqlx::query!(
+                    "insert into navigation_log (url, title, times, last_time) values (?, 'title', 1, datetime('now'))",
+                    url
+                )
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+            sqlx::query!("insert into navigation_log (url, title, times, last_time) values ('about:blank', 'blank', 1, datetime('now'))")
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            fill_site(&pool).await.unwrap();
+
+            let remaining =
+                sqlx::query_scalar!("select count(*) from navigation_log where site is null")
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(remaining, 0);
+            let grouped = sqlx::query_scalar!(
+                "select count(*) from navigation_log where site = 'example.com'"
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(grouped, count);
+        });
+    }
+}
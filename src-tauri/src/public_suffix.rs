@@ -1,51 +1,325 @@
 use std::time::Duration;
 
 use cached::proc_macro::once;
-use publicsuffix::List;
+use log::warn;
+use publicsuffix::{List, Psl as _};
+use reqwest::{
+    Client, StatusCode,
+    header::{ETAG, HeaderMap, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
 use sqlx::{SqlitePool, sqlite::SqliteQueryResult};
+use tokio::time::sleep;
 
-use crate::error::{GetPublicSuffixError, SyncPublicSuffixError};
+use crate::error::{GetPublicSuffixError, ParseError, SyncPublicSuffixError};
+
+const PUBLIC_SUFFIX_URL: &str = "https://publicsuffix.org/list/public_suffix_list.dat";
+/// 内置的公共后缀列表，数据库中没有可用副本时兜底
+const BUNDLED_PUBLIC_SUFFIX: &str = include_str!("../public_suffix_list.dat");
+const MAX_RETRIES: u32 = 3;
+
+/// 读写 [`get_public_suffix_cached`] 缓存的测试需串行执行
+#[cfg(test)]
+pub static CACHE_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+pub struct PublicSuffixRecord {
+    pub content: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
 
 #[once(time = 90000, sync_writes = true, result = true)]
 pub async fn get_public_suffix_cached(pool: &SqlitePool) -> Result<List, GetPublicSuffixError> {
-    let content = get_public_suffix(pool, false).await?;
-    Ok(content.parse()?)
+    if let Ok(record) = get_public_suffix(pool, false).await
+        && let Ok(list) = parse_public_suffix(&record.content)
+    {
+        return Ok(list);
+    }
+
+    Ok(parse_public_suffix(BUNDLED_PUBLIC_SUFFIX)?)
+}
+
+pub async fn sync_public_suffix(
+    pool: &SqlitePool,
+    client: &Client,
+) -> Result<(), SyncPublicSuffixError> {
+    sync_public_suffix_from(pool, client, PUBLIC_SUFFIX_URL).await
 }
 
-pub async fn sync_public_suffix(pool: &SqlitePool) -> Result<(), SyncPublicSuffixError> {
+async fn sync_public_suffix_from(
+    pool: &SqlitePool,
+    client: &Client,
+    url: &str,
+) -> Result<(), SyncPublicSuffixError> {
     if get_public_suffix(pool, true).await.is_ok() {
         return Ok(());
     }
 
-    let content: String = reqwest::get("https://publicsuffix.org/list/public_suffix_list.dat")
-        .await?
-        .text()
-        .await?;
+    // 带上次的校验信息做条件请求，列表未变化时服务端只返回 304
+    let mut validators = HeaderMap::new();
+    if let Ok(record) = get_public_suffix(pool, false).await {
+        if let Some(etag) = record.etag.and_then(|v| v.parse().ok()) {
+            validators.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = record.last_modified.and_then(|v| v.parse().ok()) {
+            validators.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let mut retries = 0;
+    let response = loop {
+        let result = client
+            .get(url)
+            .headers(validators.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(response) => break response,
+            Err(e) if retries < MAX_RETRIES && is_transient(&e) => {
+                retries += 1;
+                warn!("下载 public suffix 失败，第 {retries} 次重试：{e}");
+                sleep(Duration::from_secs(1 << retries)).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    };
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        touch_public_suffix(pool).await?;
+        return Ok(());
+    }
 
-    update_public_suffix(pool, &content).await?;
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let content = response.text().await?;
+
+    // 校验通过才替换已保存的副本
+    parse_public_suffix(&content)?;
+    update_public_suffix(pool, &content, etag, last_modified).await?;
+    // 缓存的可能是首次同步前的内置列表，清空后下次读取新保存的副本
+    *GET_PUBLIC_SUFFIX_CACHED.write().await = None;
 
     Ok(())
 }
 
-pub async fn get_public_suffix(pool: &SqlitePool, must_today: bool) -> Result<String, sqlx::Error> {
+pub async fn get_public_suffix(
+    pool: &SqlitePool,
+    must_today: bool,
+) -> Result<PublicSuffixRecord, sqlx::Error> {
     if must_today {
-        sqlx::query!(
-            "select content from public_suffix_list where create_time > date('now', 'localtime') limit 1"
+        sqlx::query_as!(
+            PublicSuffixRecord,
+            "select content, etag, last_modified from public_suffix_list where create_time > date('now', 'localtime') limit 1"
         )
         .fetch_one(pool)
         .await
-        .map(|row| row.content)
     } else {
-        sqlx::query!("select content from public_suffix_list limit 1")
-            .fetch_one(pool)
-            .await
-            .map(|row| row.content)
+        sqlx::query_as!(
+            PublicSuffixRecord,
+            "select content, etag, last_modified from public_suffix_list limit 1"
+        )
+        .fetch_one(pool)
+        .await
     }
 }
 
 pub async fn update_public_suffix(
     pool: &SqlitePool,
     content: &str,
+    etag: Option<String>,
+    last_modified: Option<String>,
 ) -> Result<SqliteQueryResult, sqlx::Error> {
-    sqlx::query!("delete from public_suffix_list;insert into public_suffix_list(content, create_time) values (?, datetime('now', 'localtime'))", content).execute(pool).await
+    sqlx::query!("delete from public_suffix_list;insert into public_suffix_list(content, etag, last_modified, create_time) values (?, ?, ?, datetime('now', 'localtime'))", content, etag, last_modified).execute(pool).await
+}
+
+async fn touch_public_suffix(pool: &SqlitePool) -> Result<SqliteQueryResult, sqlx::Error> {
+    sqlx::query!("update public_suffix_list set create_time = datetime('now', 'localtime')")
+        .execute(pool)
+        .await
+}
+
+fn parse_public_suffix(content: &str) -> Result<List, ParseError> {
+    let list: List = content.parse()?;
+    // 错误页面等内容也可能被解析成功，需确认常见后缀可被识别
+    if list
+        .suffix(b"example.com")
+        .and_then(|suffix| suffix.typ())
+        .is_none()
+    {
+        return Err(ParseError::InvalidPublicSuffix);
+    }
+
+    Ok(list)
+}
+
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.status().is_some_and(|status| status.is_server_error())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead as _, BufReader, Write as _},
+        net::TcpListener,
+        thread,
+    };
+
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tauri::async_runtime;
+
+    use super::*;
+
+    const LIST: &str = "// ===BEGIN ICANN DOMAINS===\ncom\nuk\nco.uk\n// ===END ICANN DOMAINS===\n";
+
+    /// 本地 HTTP 替身：依次返回给定响应，并记录收到的请求头
+    fn serve(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/public_suffix_list.dat",
+            listener.local_addr().unwrap()
+        );
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut headers = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(line.trim().to_lowercase());
+                }
+                requests.push(headers);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn ok(body: &str, etag: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nETag: {etag}\r\nLast-Modified: Sat, 17 Oct 2026 00:00:00 GMT\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    async fn memory_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        sqlx::query("delete from public_suffix_list")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn expire(pool: &SqlitePool) {
+        sqlx::query("update public_suffix_list set create_time = datetime('now', '-2 day')")
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_conditional_sync() {
+        async_runtime::block_on(async {
+            let pool = memory_pool().await;
+            let (url, server) = serve(vec![
+                ok(LIST, "\"v1\""),
+                "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string(),
+            ]);
+            let client = Client::new();
+
+            sync_public_suffix_from(&pool, &client, &url).await.unwrap();
+            // 当天已同步，不再发起请求
+            sync_public_suffix_from(&pool, &client, &url).await.unwrap();
+            expire(&pool).await;
+            sync_public_suffix_from(&pool, &client, &url).await.unwrap();
+
+            let requests = server.join().unwrap();
+            assert_eq!(requests.len(), 2);
+            assert!(requests[1].contains(&"if-none-match: \"v1\"".to_string()));
+            assert!(
+                requests[1]
+                    .iter()
+                    .any(|header| header.starts_with("if-modified-since:"))
+            );
+
+            let record = get_public_suffix(&pool, true).await.unwrap();
+            assert_eq!(record.content, LIST);
+            assert_eq!(record.etag.as_deref(), Some("\"v1\""));
+        });
+    }
+
+    #[test]
+    fn test_invalid_download_keeps_stored_copy() {
+        async_runtime::block_on(async {
+            let pool = memory_pool().await;
+            update_public_suffix(&pool, LIST, None, None).await.unwrap();
+            expire(&pool).await;
+
+            let (url, server) = serve(vec![ok("<html>captive portal</html>", "\"v2\"")]);
+            assert!(
+                sync_public_suffix_from(&pool, &Client::new(), &url)
+                    .await
+                    .is_err()
+            );
+            server.join().unwrap();
+
+            let record = get_public_suffix(&pool, false).await.unwrap();
+            assert_eq!(record.content, LIST);
+        });
+    }
+
+    #[test]
+    fn test_sync_invalidates_cache() {
+        let _lock = CACHE_TEST_LOCK.lock().unwrap();
+        async_runtime::block_on(async {
+            let pool = memory_pool().await;
+            let domain = |list: List| {
+                list.domain(b"a.b.github.io")
+                    .map(|d| String::from_utf8(d.as_bytes().to_vec()).unwrap())
+            };
+
+            // 首次同步前缓存的是内置列表，github.io 为私有后缀
+            *GET_PUBLIC_SUFFIX_CACHED.write().await = None;
+            let list = get_public_suffix_cached(&pool).await.unwrap();
+            assert_eq!(domain(list).as_deref(), Some("b.github.io"));
+
+            let (url, server) = serve(vec![ok(LIST, "\"v1\"")]);
+            sync_public_suffix_from(&pool, &Client::new(), &url)
+                .await
+                .unwrap();
+            server.join().unwrap();
+
+            let list = get_public_suffix_cached(&pool).await.unwrap();
+            assert_eq!(domain(list).as_deref(), Some("github.io"));
+        });
+    }
+
+    #[test]
+    fn test_bundled_fallback() {
+        let list = parse_public_suffix(BUNDLED_PUBLIC_SUFFIX).unwrap();
+        assert_eq!(
+            list.domain(b"gist.github.com")
+                .map(|d| d.as_bytes().to_vec()),
+            Some(b"github.com".to_vec())
+        );
+    }
 }
@@ -1,9 +1,23 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
 use reqwest::{Client, RequestBuilder};
+use scc::{HashMap, hash_map::Entry};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
+use tokio::{sync::Semaphore, task::AbortHandle};
 
 use crate::error::FetchError;
 
+/// 流式响应默认允许未确认的响应块数量
+pub const FETCH_WINDOW: usize = 8;
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct HttpHeader {
     pub key: String,
@@ -11,11 +25,93 @@ pub struct HttpHeader {
 }
 
 /// fetch 函数的可选配置项，支持 JSON 序列化
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FetchOptions {
     pub method: Option<String>,
     pub headers: Option<Vec<HttpHeader>>,
-    pub body: Option<String>,
+    pub body: Option<FetchBody>,
+    /// 响应体最大字节数，超出时中止请求
+    pub max_size: Option<u64>,
+    /// 引用的认证凭据，密钥与请求分开保存
+    #[serde(default)]
+    pub credential_id: Option<i64>,
+    /// 由凭据解析出的认证信息，只在后端注入
+    #[serde(skip)]
+    pub authorization: Option<Authorization>,
+}
+
+/// 发送前注入的认证信息，覆盖同名的请求头或查询参数
+#[derive(Debug, Clone, PartialEq)]
+pub enum Authorization {
+    Header { key: String, value: String },
+    Query { key: String, value: String },
+}
+
+/// 请求体：文本、字节数组、`{ "base64": "..." }`，或由后端编码的 JSON、表单
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FetchBody {
+    Text(String),
+    Bytes(Vec<u8>),
+    Base64 {
+        base64: String,
+    },
+    /// 发送前校验是否为合法 JSON
+    Json {
+        json: String,
+    },
+    /// application/x-www-form-urlencoded
+    Form {
+        form: Vec<FormField>,
+    },
+    /// multipart/form-data，文件字段由后端从磁盘读取
+    Multipart {
+        multipart: Vec<FormField>,
+    },
+}
+
+/// 表单字段，multipart 中 `file` 为真时 `value` 是本地文件路径
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub file: bool,
+}
+
+impl FetchBody {
+    /// 编码为请求体字节，并给出对应的 Content-Type。`allow_files` 为假时拒绝读取本地文件
+    pub async fn encode(self, allow_files: bool) -> Result<(Vec<u8>, Option<String>), FetchError> {
+        match self {
+            FetchBody::Text(text) => Ok((text.into_bytes(), None)),
+            FetchBody::Bytes(bytes) => Ok((bytes, None)),
+            FetchBody::Base64 { base64 } => Ok((STANDARD.decode(base64)?, None)),
+            FetchBody::Json { json } => {
+                serde_json::from_str::<serde::de::IgnoredAny>(&json)?;
+                Ok((json.into_bytes(), Some("application/json".to_string())))
+            }
+            FetchBody::Form { form } => {
+                let body = url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(form.iter().map(|field| (&field.key, &field.value)))
+                    .finish();
+                Ok((
+                    body.into_bytes(),
+                    Some("application/x-www-form-urlencoded".to_string()),
+                ))
+            }
+            FetchBody::Multipart { multipart } => {
+                let boundary = format!(
+                    "----WhiteHoleBoundary{:x}",
+                    OffsetDateTime::now_utc().unix_timestamp_nanos()
+                );
+                let body = encode_multipart(&multipart, &boundary, allow_files).await?;
+                Ok((
+                    body,
+                    Some(format!("multipart/form-data; boundary={boundary}")),
+                ))
+            }
+        }
+    }
 }
 
 /// 自定义的响应类型，完全独立于 reqwest，支持 JSON 序列化
@@ -29,9 +125,187 @@ pub struct Response {
     pub elapsed_time: i32,
 }
 
+/// 流式响应事件，依次为 head、若干 chunk、end
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum FetchEvent {
+    Head {
+        #[serde(with = "time::serde::iso8601")]
+        done_date: OffsetDateTime,
+        status: u16,
+        headers: Vec<HttpHeader>,
+    },
+    Chunk(Vec<u8>),
+    End {
+        elapsed_time: i32,
+    },
+}
+
+/// 进行中的请求，按发起的 webview label 与 request id 取消或确认已收到的响应块，
+/// 页面只能操作自己发起的请求
+#[derive(Default)]
+pub struct FetchRegistry(HashMap<(String, String), FetchTask>, AtomicU64);
+
+struct FetchTask {
+    seq: u64,
+    abort: AbortHandle,
+    acks: Option<Arc<Semaphore>>,
+}
+
+impl FetchRegistry {
+    pub async fn run<T, F>(
+        &self,
+        label: &str,
+        request_id: Option<String>,
+        acks: Option<Arc<Semaphore>>,
+        future: F,
+    ) -> Result<T, FetchError>
+    where
+        T: Send + 'static,
+        F: Future<Output = Result<T, FetchError>> + Send + 'static,
+    {
+        let Some(request_id) = request_id else {
+            return tauri::async_runtime::spawn(future)
+                .await
+                .unwrap_or(Err(FetchError::Cancelled));
+        };
+
+        // 同一 id 同时只能有一个请求，否则前一个请求无法再取消或确认
+        let key = (label.to_string(), request_id);
+        let (handle, seq) = match self.0.entry_async(key.clone()).await {
+            Entry::Occupied(_) => {
+                return Err(FetchError::DuplicateRequest { request_id: key.1 });
+            }
+            Entry::Vacant(entry) => {
+                let handle = tauri::async_runtime::spawn(future);
+                let abort = handle.inner().abort_handle();
+                let seq = self.1.fetch_add(1, Ordering::Relaxed);
+                entry.insert_entry(FetchTask { seq, abort, acks });
+                (handle, seq)
+            }
+        };
+
+        let result = handle.await;
+        // 取消后同一 id 可能已被新请求使用，只移除自己的记录
+        self.0.remove_if_async(&key, |task| task.seq == seq).await;
+
+        // 被 abort 的任务返回 JoinError
+        result.unwrap_or(Err(FetchError::Cancelled))
+    }
+
+    pub async fn cancel(&self, label: &str, request_id: &str) -> bool {
+        let key = (label.to_string(), request_id.to_string());
+        let Some((_, task)) = self.0.remove_async(&key).await else {
+            return false;
+        };
+
+        task.abort.abort();
+        true
+    }
+
+    pub async fn ack(&self, label: &str, request_id: &str, count: usize) {
+        let key = (label.to_string(), request_id.to_string());
+        self.0
+            .read_async(&key, |_, task| {
+                if let Some(acks) = &task.acks {
+                    acks.add_permits(count);
+                }
+            })
+            .await;
+    }
+}
+
 /// 核心 fetch 函数，返回一个支持序列化的 Response
-pub async fn fetch(url: &str, options: Option<FetchOptions>) -> Result<Response, FetchError> {
-    let client = Client::new();
+pub async fn fetch(
+    client: &Client,
+    url: &str,
+    options: Option<FetchOptions>,
+    allow_files: bool,
+) -> Result<Response, FetchError> {
+    let max_size = options.as_ref().and_then(|o| o.max_size);
+    let request_builder = build_request(client, url, options, allow_files).await?;
+
+    // 发送请求并获得原始 reqwest::Response
+    let done_date = OffsetDateTime::now_local()?;
+    let mut raw_response = request_builder.send().await?;
+    check_size(raw_response.content_length(), max_size)?;
+
+    // 提取所有数据，释放 reqwest::Response
+    let status = raw_response.status().as_u16();
+    let headers = to_headers(&raw_response);
+
+    // 分块读取完整响应体，超出大小限制时中止
+    let mut body = Vec::new();
+    while let Some(chunk) = raw_response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        check_size(Some(body.len() as u64), max_size)?;
+    }
+
+    Ok(Response {
+        status,
+        headers,
+        body,
+        done_date,
+        elapsed_time: (OffsetDateTime::now_local()? - done_date).whole_milliseconds() as i32,
+    })
+}
+
+/// 流式 fetch，每发送一个响应块消耗一个 `acks` 许可，由调用方确认后归还
+pub async fn fetch_stream(
+    client: &Client,
+    url: &str,
+    options: Option<FetchOptions>,
+    allow_files: bool,
+    acks: &Semaphore,
+    on_event: impl Fn(FetchEvent) -> Result<(), FetchError>,
+) -> Result<(), FetchError> {
+    let max_size = options.as_ref().and_then(|o| o.max_size);
+    let request_builder = build_request(client, url, options, allow_files).await?;
+
+    let done_date = OffsetDateTime::now_local()?;
+    let mut raw_response = request_builder.send().await?;
+    check_size(raw_response.content_length(), max_size)?;
+
+    on_event(FetchEvent::Head {
+        done_date,
+        status: raw_response.status().as_u16(),
+        headers: to_headers(&raw_response),
+    })?;
+
+    let mut size = 0;
+    while let Some(chunk) = raw_response.chunk().await? {
+        size += chunk.len() as u64;
+        check_size(Some(size), max_size)?;
+
+        acks.acquire()
+            .await
+            .map_err(|_| FetchError::Cancelled)?
+            .forget();
+        on_event(FetchEvent::Chunk(chunk.to_vec()))?;
+    }
+
+    on_event(FetchEvent::End {
+        elapsed_time: (OffsetDateTime::now_local()? - done_date).whole_milliseconds() as i32,
+    })
+}
+
+/// multipart 的文件字段会读取本地文件，只有主视图发起的请求才应允许 `allow_files`，
+/// 否则任意页面都能借此读取本机文件
+pub async fn build_request(
+    client: &Client,
+    url: &str,
+    options: Option<FetchOptions>,
+    allow_files: bool,
+) -> Result<RequestBuilder, FetchError> {
+    // 认证信息在查询参数中时，替换 URL 中的同名参数
+    let authorized_url;
+    let url = match options.as_ref().and_then(|o| o.authorization.as_ref()) {
+        Some(Authorization::Query { key, value }) => {
+            authorized_url = with_query(url, key, value);
+            authorized_url.as_str()
+        }
+        _ => url,
+    };
 
     // 构建请求
     let mut request_builder: RequestBuilder = match options.as_ref().and_then(|o| o.method.as_ref())
@@ -48,28 +322,143 @@ pub async fn fetch(url: &str, options: Option<FetchOptions>) -> Result<Response,
         None => client.get(url),
     };
 
+    let Some(opts) = options else {
+        return Ok(request_builder);
+    };
+
+    // 编码请求体，multipart 的分隔符必须与 Content-Type 一致，忽略用户填写的值
+    let is_multipart = matches!(opts.body, Some(FetchBody::Multipart { .. }));
+    let (body, content_type) = match opts.body {
+        Some(body) => {
+            let (body, content_type) = body.encode(allow_files).await?;
+            (Some(body), content_type)
+        }
+        None => (None, None),
+    };
+
     // 添加请求头
-    if let Some(opts) = &options {
-        if let Some(headers) = &opts.headers {
-            for HttpHeader { key: name, value } in headers {
-                request_builder = request_builder.header(name.as_str(), value);
-            }
+    let auth_header = match &opts.authorization {
+        Some(Authorization::Header { key, value }) => Some((key, value)),
+        _ => None,
+    };
+    let mut has_content_type = false;
+    for HttpHeader { key: name, value } in opts.headers.iter().flatten() {
+        if auth_header.is_some_and(|(key, _)| name.eq_ignore_ascii_case(key)) {
+            continue;
         }
-        // 添加请求体
-        if let Some(body) = &opts.body {
-            request_builder = request_builder.body(body.clone());
+        if name.eq_ignore_ascii_case("content-type") {
+            if is_multipart {
+                continue;
+            }
+            has_content_type = true;
         }
+        request_builder = request_builder.header(name.as_str(), value);
+    }
+    if let Some(content_type) = content_type
+        && !has_content_type
+    {
+        request_builder = request_builder.header("content-type", content_type);
+    }
+    if let Some((key, value)) = auth_header {
+        request_builder = request_builder.header(key.as_str(), value);
     }
 
-    // 发送请求并获得原始 reqwest::Response
-    let done_date = OffsetDateTime::now_local()?;
-    let raw_response = request_builder.send().await?;
+    // 添加请求体
+    if let Some(body) = body {
+        request_builder = request_builder.body(body);
+    }
 
-    // 提取所有数据，释放 reqwest::Response
-    let status = raw_response.status().as_u16();
+    Ok(request_builder)
+}
+
+/// 保存响应体到目录，只取文件名部分；同名文件已存在时追加序号
+pub async fn save_body(
+    dir: &Path,
+    file_name: &str,
+    body: &[u8],
+) -> Result<PathBuf, std::io::Error> {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("response"));
+    let stem = file_name
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let extension = file_name
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut path = dir.join(&file_name);
+    let mut index = 1;
+    while tokio::fs::try_exists(&path).await? {
+        path = dir.join(format!("{stem} ({index}){extension}"));
+        index += 1;
+    }
+
+    tokio::fs::write(&path, body).await?;
+    Ok(path)
+}
+
+async fn encode_multipart(
+    fields: &[FormField],
+    boundary: &str,
+    allow_files: bool,
+) -> Result<Vec<u8>, FetchError> {
+    if !allow_files && fields.iter().any(|field| field.file) {
+        return Err(FetchError::FileNotAllowed);
+    }
+
+    let mut body = Vec::new();
+    for FormField { key, value, file } in fields {
+        let name = key.replace('"', "%22");
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        if *file {
+            let filename = Path::new(value)
+                .file_name()
+                .map(|name| name.to_string_lossy().replace('"', "%22"))
+                .unwrap_or_default();
+            body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+                )
+                .as_bytes(),
+            );
+            body.extend_from_slice(&tokio::fs::read(value).await?);
+        } else {
+            body.extend_from_slice(
+                format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}")
+                    .as_bytes(),
+            );
+        }
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    Ok(body)
+}
+
+/// 无法解析的 URL 保持原样，由 reqwest 报告错误
+pub fn with_query(url: &str, key: &str, value: &str) -> String {
+    let Ok(mut url) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != key)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(key, value);
+    url.to_string()
+}
 
+pub fn to_headers(raw_response: &reqwest::Response) -> Vec<HttpHeader> {
     // 将头信息转换为简单的键值对列表（保留多值）
-    let headers = raw_response
+    raw_response
         .headers()
         .iter()
         .flat_map(|(key, value)| {
@@ -80,16 +469,322 @@ pub async fn fetch(url: &str, options: Option<FetchOptions>) -> Result<Response,
                 value: value_str,
             })
         })
-        .collect();
+        .collect()
+}
 
-    // 读取完整响应体
-    let body = raw_response.bytes().await?.to_vec();
+fn check_size(size: Option<u64>, max_size: Option<u64>) -> Result<(), FetchError> {
+    match (size, max_size) {
+        (Some(size), Some(max_size)) if size > max_size => Err(FetchError::TooLarge),
+        _ => Ok(()),
+    }
+}
 
-    Ok(Response {
-        status,
-        headers,
-        body,
-        done_date,
-        elapsed_time: (OffsetDateTime::now_local()? - done_date).whole_milliseconds() as i32,
-    })
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead as _, BufReader, Read as _, Write as _},
+        net::TcpListener,
+        sync::Mutex,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use tauri::async_runtime;
+
+    use super::*;
+
+    /// 收到的请求头和请求体
+    pub(crate) type Received = (Vec<String>, Vec<u8>);
+
+    /// 返回固定响应，并回传收到的请求
+    pub(crate) fn serve(response: String) -> (String, JoinHandle<Received>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim().to_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(length) = line.strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                headers.push(line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream.write_all(response.as_bytes()).unwrap();
+            (headers, body)
+        });
+        (url, handle)
+    }
+
+    pub(crate) fn ok(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn field(key: &str, value: &str, file: bool) -> FormField {
+        FormField {
+            key: key.to_string(),
+            value: value.to_string(),
+            file,
+        }
+    }
+
+    fn options(body: Option<FetchBody>, max_size: Option<u64>) -> Option<FetchOptions> {
+        Some(FetchOptions {
+            method: Some("POST".to_string()),
+            headers: None,
+            body,
+            max_size,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_binary_body() {
+        let body: FetchBody = serde_json::from_str("[0,255,128]").unwrap();
+        assert_eq!(body, FetchBody::Bytes(vec![0, 255, 128]));
+
+        async_runtime::block_on(async {
+            let body: FetchBody = serde_json::from_str(r#"{"base64":"AP+A"}"#).unwrap();
+            assert_eq!(body.encode(false).await.unwrap(), (vec![0, 255, 128], None));
+
+            let (url, server) = serve(ok("done"));
+            let body = Some(FetchBody::Bytes(vec![0, 159, 146, 150]));
+            let response = fetch(&Client::new(), &url, options(body, None), false)
+                .await
+                .unwrap();
+            assert_eq!(response.body, b"done");
+            assert_eq!(server.join().unwrap().1, vec![0, 159, 146, 150]);
+        });
+    }
+
+    #[test]
+    fn test_typed_body() {
+        let body: FetchBody =
+            serde_json::from_str(r#"{"form":[{"key":"a","value":"1"}]}"#).unwrap();
+        assert!(matches!(body, FetchBody::Form { .. }));
+
+        async_runtime::block_on(async {
+            let json = FetchBody::Json {
+                json: "{invalid".to_string(),
+            };
+            assert!(json.encode(false).await.is_err());
+
+            let form = FetchBody::Form {
+                form: vec![
+                    field("name", "white hole", false),
+                    field("q", "a&b=c", false),
+                ],
+            };
+            let (body, content_type) = form.encode(false).await.unwrap();
+            assert_eq!(body, b"name=white+hole&q=a%26b%3Dc");
+            assert_eq!(
+                content_type.as_deref(),
+                Some("application/x-www-form-urlencoded")
+            );
+
+            let path = std::env::temp_dir().join("white-hole-multipart.txt");
+            std::fs::write(&path, "file content").unwrap();
+            let (url, server) = serve(ok("done"));
+            let mut options = options(
+                Some(FetchBody::Multipart {
+                    multipart: vec![
+                        field("name", "white hole", false),
+                        field("upload", &path.to_string_lossy(), true),
+                    ],
+                }),
+                None,
+            );
+            // multipart 忽略用户填写的 Content-Type
+            options.as_mut().unwrap().headers = Some(vec![HttpHeader {
+                key: "Content-Type".to_string(),
+                value: "multipart/form-data".to_string(),
+            }]);
+            // 未允许时不读取文件
+            assert!(matches!(
+                fetch(
+                    &Client::new(),
+                    "http://127.0.0.1:1/",
+                    options.clone(),
+                    false
+                )
+                .await,
+                Err(FetchError::FileNotAllowed)
+            ));
+            fetch(&Client::new(), &url, options, true).await.unwrap();
+            let (headers, body) = server.join().unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            let content_type: Vec<_> = headers
+                .iter()
+                .filter(|header| header.starts_with("content-type:"))
+                .collect();
+            assert_eq!(content_type.len(), 1);
+            let boundary = content_type[0].split_once("boundary=").unwrap().1;
+            let body = String::from_utf8(body).unwrap().to_lowercase();
+            assert!(body.starts_with(&format!("--{boundary}\r\n")));
+            assert!(body.contains("name=\"name\"\r\n\r\nwhite hole\r\n"));
+            assert!(body.contains("filename=\"white-hole-multipart.txt\""));
+            assert!(body.contains("\r\n\r\nfile content\r\n"));
+            assert!(body.ends_with(&format!("--{boundary}--\r\n")));
+        });
+    }
+
+    #[test]
+    fn test_with_query() {
+        assert_eq!(
+            with_query("https://example.com/a?key=old&b=1", "key", "new value"),
+            "https://example.com/a?b=1&key=new+value"
+        );
+        assert_eq!(with_query("not a url", "key", "value"), "not a url");
+    }
+
+    #[test]
+    fn test_save_body() {
+        async_runtime::block_on(async {
+            let dir = std::env::temp_dir().join("white-hole-save-body");
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let first = save_body(&dir, "../report.json", b"{}").await.unwrap();
+            assert_eq!(first, dir.join("report.json"));
+            let second = save_body(&dir, "report.json", b"[]").await.unwrap();
+            assert_eq!(second, dir.join("report (1).json"));
+            assert_eq!(std::fs::read(&second).unwrap(), b"[]");
+
+            std::fs::remove_dir_all(&dir).unwrap();
+        });
+    }
+
+    #[test]
+    fn test_max_size() {
+        async_runtime::block_on(async {
+            let (url, server) = serve(ok("0123456789"));
+            let result = fetch(&Client::new(), &url, options(None, Some(4)), false).await;
+            assert!(matches!(result, Err(FetchError::TooLarge)));
+            server.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn test_stream() {
+        async_runtime::block_on(async {
+            let (url, server) = serve(ok("streaming body"));
+            let events = Mutex::new(Vec::new());
+            let acks = Semaphore::new(FETCH_WINDOW);
+            fetch_stream(&Client::new(), &url, None, false, &acks, |event| {
+                events.lock().unwrap().push(event);
+                Ok(())
+            })
+            .await
+            .unwrap();
+            server.join().unwrap();
+
+            let events = events.into_inner().unwrap();
+            assert!(matches!(
+                events.first(),
+                Some(FetchEvent::Head { status: 200, .. })
+            ));
+            assert!(matches!(events.last(), Some(FetchEvent::End { .. })));
+            let body: Vec<u8> = events
+                .iter()
+                .filter_map(|event| match event {
+                    FetchEvent::Chunk(chunk) => Some(chunk.clone()),
+                    _ => None,
+                })
+                .flatten()
+                .collect();
+            assert_eq!(body, b"streaming body");
+        });
+    }
+
+    #[test]
+    fn test_cancel() {
+        async_runtime::block_on(async {
+            let registry = Arc::new(FetchRegistry::default());
+            async_runtime::spawn({
+                let registry = registry.clone();
+                async move {
+                    // 等待任务登记后取消，其他 webview 不能取消
+                    let key = ("main".to_string(), "cancel".to_string());
+                    while !registry.0.contains_async(&key).await {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                    assert!(!registry.cancel("tab", "cancel").await);
+                    assert!(registry.cancel("main", "cancel").await);
+                }
+            });
+
+            let result = registry
+                .run("main", Some("cancel".to_string()), None, async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok(())
+                })
+                .await;
+            assert!(matches!(result, Err(FetchError::Cancelled)));
+        });
+    }
+
+    #[test]
+    fn test_duplicate_request_id() {
+        async_runtime::block_on(async {
+            let registry = Arc::new(FetchRegistry::default());
+            let key = ("main".to_string(), "dup".to_string());
+            let first = async_runtime::spawn({
+                let registry = registry.clone();
+                async move {
+                    registry
+                        .run("main", Some("dup".to_string()), None, async {
+                            tokio::time::sleep(Duration::from_secs(60)).await;
+                            Ok(())
+                        })
+                        .await
+                }
+            });
+            while !registry.0.contains_async(&key).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+
+            let result = registry
+                .run("main", Some("dup".to_string()), None, async { Ok(()) })
+                .await;
+            assert!(matches!(result, Err(FetchError::DuplicateRequest { .. })));
+            // 不同 webview 的 id 互不影响
+            let result = registry
+                .run("tab", Some("dup".to_string()), None, async { Ok(()) })
+                .await;
+            assert!(result.is_ok());
+
+            // 取消后立即复用 id，前一个任务结束时不能移除新任务的记录
+            assert!(registry.cancel("main", "dup").await);
+            let second = async_runtime::spawn({
+                let registry = registry.clone();
+                async move {
+                    registry
+                        .run("main", Some("dup".to_string()), None, async {
+                            tokio::time::sleep(Duration::from_secs(60)).await;
+                            Ok(())
+                        })
+                        .await
+                }
+            });
+            assert!(matches!(first.await.unwrap(), Err(FetchError::Cancelled)));
+            while !registry.0.contains_async(&key).await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            assert!(registry.cancel("main", "dup").await);
+            assert!(matches!(second.await.unwrap(), Err(FetchError::Cancelled)));
+        });
+    }
 }
@@ -0,0 +1,235 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool, types::Json};
+use time::OffsetDateTime;
+
+use crate::{
+    page::{PageToken, Paginator as _},
+    request::{FetchBody, FetchOptions, HttpHeader, Response},
+};
+
+/// 请求体超过该大小时不记录
+const MAX_LOG_BODY_SIZE: usize = 1024 * 1024;
+/// 保留的请求记录条数
+pub const MAX_LOG_COUNT: i64 = 1000;
+
+#[derive(Clone, Default, Serialize)]
+pub struct QueryRequestLogResponse {
+    pub next_page_token: Option<PageToken>,
+    pub logs: Vec<RequestLog>,
+}
+
+/// HTTP 客户端执行过的请求
+#[derive(Clone, Serialize, FromRow)]
+pub struct RequestLog {
+    pub id: i64,
+    pub method: String,
+    pub url: String,
+    pub headers: Json<Vec<HttpHeader>>,
+    pub body: Option<Json<FetchBody>>,
+    pub status: Option<u16>,
+    pub elapsed_time: Option<i32>,
+    pub error: Option<String>,
+    pub credential_id: Option<i64>,
+    pub create_time: OffsetDateTime,
+}
+
+impl RequestLog {
+    pub fn to_options(&self) -> FetchOptions {
+        to_options(&self.method, &self.headers, &self.body, self.credential_id)
+    }
+}
+
+pub fn to_options(
+    method: &str,
+    headers: &[HttpHeader],
+    body: &Option<Json<FetchBody>>,
+    credential_id: Option<i64>,
+) -> FetchOptions {
+    FetchOptions {
+        method: Some(method.to_string()),
+        headers: Some(headers.to_vec()),
+        body: body.as_ref().map(|body| body.0.clone()),
+        credential_id,
+        ..Default::default()
+    }
+}
+
+pub async fn save_request_log<E: Display>(
+    pool: &SqlitePool,
+    url: &str,
+    options: Option<&FetchOptions>,
+    result: &Result<Response, E>,
+) -> Result<i64, sqlx::Error> {
+    let method = options
+        .and_then(|o| o.method.as_deref())
+        .unwrap_or("GET")
+        .to_uppercase();
+    let headers = Json(options.and_then(|o| o.headers.clone()).unwrap_or_default());
+    let body = options
+        .and_then(|o| o.body.clone())
+        .filter(|body| body_size(body) <= MAX_LOG_BODY_SIZE)
+        .map(Json);
+    let credential_id = options.and_then(|o| o.credential_id);
+    let (status, elapsed_time, error) = match result {
+        Ok(response) => (Some(response.status), Some(response.elapsed_time), None),
+        Err(e) => (None, None, Some(e.to_string())),
+    };
+
+    sqlx::query!(
+        "insert into http_request_log (method, url, headers, body, status, elapsed_time, error, credential_id, create_time) values (?, ?, ?, ?, ?, ?, ?, ?, datetime('now', 'localtime'))",
+        method,
+        url,
+        headers,
+        body,
+        status,
+        elapsed_time,
+        error,
+        credential_id
+    )
+    .execute(pool)
+    .await
+    .map(|result| result.last_insert_rowid())
+}
+
+pub async fn get_request_log(pool: &SqlitePool, id: i64) -> Result<RequestLog, sqlx::Error> {
+    sqlx::query_as("select id, method, url, headers, body, status, elapsed_time, error, credential_id, create_time from http_request_log where id = ?")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+}
+
+pub async fn query_request_log(
+    pool: &SqlitePool,
+    keyword: &str,
+    page_token: PageToken,
+) -> Result<QueryRequestLogResponse, sqlx::Error> {
+    let mut query_builder: QueryBuilder<'_, Sqlite> = QueryBuilder::new(
+        "select id, method, url, headers, body, status, elapsed_time, error, credential_id, create_time from http_request_log where 1 = 1 ",
+    );
+    for keyword in keyword.split_whitespace() {
+        query_builder
+            .push("and (url like ")
+            .push_bind(format!("%{keyword}%"))
+            .push(" or method = ")
+            .push_bind(keyword.to_uppercase())
+            .push(") ");
+    }
+    query_builder.push("order by id desc ");
+    query_builder.push(page_token.as_limit_sql());
+
+    let mut logs = query_builder.build_query_as().fetch_all(pool).await?;
+
+    Ok(QueryRequestLogResponse {
+        next_page_token: page_token.next_page(&mut logs),
+        logs,
+    })
+}
+
+pub async fn delete_request_log(pool: &SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("delete from http_request_log where id = ?", id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn clear_request_log(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "delete from http_request_log where id not in (select id from http_request_log order by id desc limit ?)",
+        MAX_LOG_COUNT
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+fn body_size(body: &FetchBody) -> usize {
+    match body {
+        FetchBody::Text(text) => text.len(),
+        FetchBody::Bytes(bytes) => bytes.len(),
+        FetchBody::Base64 { base64 } => base64.len(),
+        FetchBody::Json { json } => json.len(),
+        FetchBody::Form { form: fields } | FetchBody::Multipart { multipart: fields } => fields
+            .iter()
+            .map(|field| field.key.len() + field.value.len())
+            .sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::async_runtime;
+
+    use super::*;
+    use crate::error::FetchError;
+
+    #[test]
+    fn test_request_log() {
+        async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            let options = FetchOptions {
+                method: Some("post".to_string()),
+                headers: None,
+                body: Some(FetchBody::Bytes(vec![0, 1, 2])),
+                credential_id: Some(1),
+                ..Default::default()
+            };
+            let response = Response {
+                done_date: OffsetDateTime::now_utc(),
+                status: 201,
+                headers: Vec::new(),
+                body: Vec::new(),
+                elapsed_time: 12,
+            };
+            save_request_log(
+                &pool,
+                "https://example.com/a",
+                Some(&options),
+                &Ok::<_, FetchError>(response),
+            )
+            .await
+            .unwrap();
+            save_request_log(
+                &pool,
+                "https://example.com/b",
+                None,
+                &Err(FetchError::TooLarge),
+            )
+            .await
+            .unwrap();
+
+            let response = query_request_log(
+                &pool,
+                "post",
+                PageToken {
+                    limit: 10,
+                    offset: 0,
+                },
+            )
+            .await
+            .unwrap();
+            assert_eq!(response.logs.len(), 1);
+            let log = &response.logs[0];
+            assert_eq!(log.status, Some(201));
+            assert_eq!(log.to_options().method.as_deref(), Some("POST"));
+            assert_eq!(log.to_options().body, Some(FetchBody::Bytes(vec![0, 1, 2])));
+            assert_eq!(log.to_options().credential_id, Some(1));
+
+            let response = query_request_log(
+                &pool,
+                "",
+                PageToken {
+                    limit: 1,
+                    offset: 0,
+                },
+            )
+            .await
+            .unwrap();
+            assert!(response.next_page_token.is_some());
+            assert!(response.logs[0].error.is_some());
+        });
+    }
+}
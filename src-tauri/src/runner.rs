@@ -0,0 +1,242 @@
+use std::{collections::HashMap, fmt::Display, time::Duration};
+
+use serde::Serialize;
+
+use crate::{
+    assertion::{AssertionResult, check_all},
+    collection::SavedRequest,
+    environment::capture,
+    request::Response,
+};
+
+/// 运行集合中单个请求的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestResult {
+    pub request_id: Option<i64>,
+    pub name: String,
+    pub method: String,
+    /// 保存的原始 URL，不填入变量，避免密钥出现在界面和报告中
+    pub url: String,
+    pub status: Option<u16>,
+    pub elapsed_time: Option<i32>,
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+    pub passed: bool,
+}
+
+impl RequestResult {
+    /// 校验断言，并把提取的变量写入 `variables` 供后续请求使用
+    pub fn new(
+        request: &SavedRequest,
+        result: &Result<Response, impl Display>,
+        variables: &mut HashMap<String, String>,
+    ) -> Self {
+        let (status, elapsed_time, errors, assertions) = match result {
+            Ok(response) => {
+                let errors = request
+                    .captures
+                    .iter()
+                    .filter_map(|item| match capture(&response.body, &item.path) {
+                        Ok(value) => {
+                            variables.insert(item.key.clone(), value);
+                            None
+                        }
+                        Err(e) => Some(format!("提取变量 {} 失败：{e}", item.key)),
+                    })
+                    .collect();
+                (
+                    Some(response.status),
+                    Some(response.elapsed_time),
+                    errors,
+                    check_all(&request.assertions, response),
+                )
+            }
+            Err(e) => (None, None, vec![e.to_string()], Vec::new()),
+        };
+        let error = (!errors.is_empty()).then(|| errors.join("\n"));
+
+        Self {
+            request_id: request.id,
+            name: request.name.clone(),
+            method: request.method.clone(),
+            url: request.url.clone(),
+            status,
+            elapsed_time,
+            passed: error.is_none() && assertions.iter().all(|result| result.passed),
+            error,
+            assertions,
+        }
+    }
+}
+
+/// 集合运行报告，`junit` 为可直接保存的 JUnit XML
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub name: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub elapsed_time: i32,
+    pub results: Vec<RequestResult>,
+    pub junit: String,
+}
+
+impl RunReport {
+    pub fn new(name: String, results: Vec<RequestResult>, elapsed: Duration) -> Self {
+        let passed = results.iter().filter(|result| result.passed).count();
+        let mut report = Self {
+            name,
+            passed,
+            failed: results.len() - passed,
+            elapsed_time: elapsed.as_millis() as i32,
+            results,
+            junit: String::new(),
+        };
+        report.junit = report.to_junit();
+        report
+    }
+
+    /// 每个请求一个 testcase，请求失败为 error，断言失败为 failure
+    fn to_junit(&self) -> String {
+        let errors = self
+            .results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .count();
+        let failures = self.failed - errors;
+        let name = escape_xml(&self.name);
+        let time = seconds(self.elapsed_time);
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"{name}\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\">\n",
+            self.results.len()
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{name}\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\">\n",
+            self.results.len()
+        ));
+        for result in &self.results {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+                escape_xml(&format!("{} {}", result.method, result.name)),
+                name,
+                seconds(result.elapsed_time.unwrap_or_default())
+            ));
+            if result.passed {
+                xml.push_str("/>\n");
+                continue;
+            }
+
+            xml.push_str(">\n");
+            if let Some(error) = &result.error {
+                xml.push_str(&format!(
+                    "      <error message=\"{}\"/>\n",
+                    escape_xml(error)
+                ));
+            }
+            for assertion in result.assertions.iter().filter(|result| !result.passed) {
+                xml.push_str(&format!(
+                    "      <failure message=\"{}\"/>\n",
+                    escape_xml(&assertion.message)
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn seconds(millis: i32) -> String {
+    format!("{:.3}", millis as f64 / 1000.0)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::Json;
+    use time::OffsetDateTime;
+
+    use super::*;
+    use crate::{assertion::Assertion, environment::Capture};
+
+    #[test]
+    fn test_run_report() {
+        let login = SavedRequest {
+            id: Some(1),
+            collection_id: 1,
+            name: "login".to_string(),
+            method: "POST".to_string(),
+            url: "https://example.com/login".to_string(),
+            headers: Json(Vec::new()),
+            body: None,
+            credential_id: None,
+            assertions: Json(vec![Assertion::Status { equals: 200 }]),
+            captures: Json(vec![Capture {
+                key: "token".to_string(),
+                path: "$.token".to_string(),
+            }]),
+            update_time: None,
+        };
+        let profile = SavedRequest {
+            id: Some(2),
+            name: "<profile>".to_string(),
+            method: "GET".to_string(),
+            url: "https://example.com/me?token={{token}}".to_string(),
+            assertions: Json(vec![Assertion::Status { equals: 200 }]),
+            captures: Json(Vec::new()),
+            ..login.clone()
+        };
+        let response = |status: u16| Response {
+            done_date: OffsetDateTime::UNIX_EPOCH,
+            status,
+            headers: Vec::new(),
+            body: br#"{"token":"secret"}"#.to_vec(),
+            elapsed_time: 1500,
+        };
+
+        let mut variables = HashMap::new();
+        let results = vec![
+            RequestResult::new(&login, &Ok::<_, String>(response(200)), &mut variables),
+            RequestResult::new(&profile, &Ok::<_, String>(response(401)), &mut variables),
+            RequestResult::new(&login, &Err::<Response, _>("连接超时"), &mut variables),
+        ];
+        assert_eq!(variables["token"], "secret");
+        assert_eq!(results[1].url, "https://example.com/me?token={{token}}");
+
+        let report = RunReport::new("api".to_string(), results, Duration::from_millis(3000));
+        assert_eq!((report.passed, report.failed), (1, 2));
+        assert!(
+            report.junit.contains(
+                r#"<testsuite name="api" tests="3" failures="1" errors="1" time="3.000">"#
+            )
+        );
+        assert!(
+            report
+                .junit
+                .contains(r#"<testcase name="POST login" classname="api" time="1.500"/>"#)
+        );
+        assert!(report.junit.contains(r#"name="GET &lt;profile&gt;""#));
+        assert!(
+            report
+                .junit
+                .contains(r#"<failure message="状态码为 401，期望 200"/>"#)
+        );
+        assert!(report.junit.contains(r#"<error message="连接超时"/>"#));
+    }
+}
@@ -0,0 +1,97 @@
+use std::net::IpAddr;
+
+use publicsuffix::{List, Psl as _};
+use sqlx::SqlitePool;
+use url::Url;
+
+use crate::public_suffix::get_public_suffix_cached;
+
+/// 计算 host 所属站点，即可注册域名（eTLD+1），如 `gist.github.com` 属于 `github.com`
+///
+/// IP、localhost 等无可注册域名的 host 自成一个站点
+pub fn site(list: &List, host: &str) -> String {
+    // IP 没有域名层级，按公共后缀的默认规则会被截成最后两段
+    let ip = host.trim_start_matches('[').trim_end_matches(']');
+    if ip.parse::<IpAddr>().is_ok() {
+        return host.to_string();
+    }
+
+    list.domain(host.as_bytes())
+        .and_then(|domain| std::str::from_utf8(domain.as_bytes()).ok())
+        .unwrap_or(host)
+        .to_string()
+}
+
+/// 计算 URL 所属站点，无 host 的 URL（如 `file:`、`about:`）返回 `None`
+pub fn site_of_url(list: &List, url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    Some(site(list, url.host_str()?))
+}
+
+pub async fn get_site(pool: &SqlitePool, host: &str) -> String {
+    match get_public_suffix_cached(pool).await {
+        Ok(list) => site(&list, host),
+        Err(_) => host.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tauri::async_runtime;
+
+    use super::*;
+    use crate::public_suffix::CACHE_TEST_LOCK;
+
+    const LIST: &str = "// ===BEGIN ICANN DOMAINS===\ncom\nuk\nco.uk\nio\n// ===END ICANN DOMAINS===\n// ===BEGIN PRIVATE DOMAINS===\ngithub.io\n// ===END PRIVATE DOMAINS===\n";
+
+    #[test]
+    fn test_site() {
+        let list: List = LIST.parse().unwrap();
+        assert_eq!(site(&list, "gist.github.com"), "github.com");
+        assert_eq!(site(&list, "github.com"), "github.com");
+        // 多级公共后缀
+        assert_eq!(site(&list, "www.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(site(&list, "a.b.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(site(&list, "user.github.io"), "user.github.io");
+        // 没有可注册域名的 host 自成一个站点
+        assert_eq!(site(&list, "co.uk"), "co.uk");
+        assert_eq!(site(&list, "localhost"), "localhost");
+        assert_eq!(site(&list, "127.0.0.1"), "127.0.0.1");
+        assert_eq!(site(&list, "[::1]"), "[::1]");
+    }
+
+    #[test]
+    fn test_site_of_url() {
+        let list: List = LIST.parse().unwrap();
+        assert_eq!(
+            site_of_url(&list, "https://news.bbc.co.uk/a?b=c").as_deref(),
+            Some("bbc.co.uk")
+        );
+        assert_eq!(
+            site_of_url(&list, "http://192.168.1.1:8080/").as_deref(),
+            Some("192.168.1.1")
+        );
+        assert_eq!(
+            site_of_url(&list, "http://localhost:3000/").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(site_of_url(&list, "file:///etc/hosts"), None);
+        assert_eq!(site_of_url(&list, "about:blank"), None);
+        assert_eq!(site_of_url(&list, "not a url"), None);
+    }
+
+    #[test]
+    fn test_get_site() {
+        let _lock = CACHE_TEST_LOCK.lock().unwrap();
+        async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            // 数据库中没有列表时使用内置列表
+            assert_eq!(get_site(&pool, "gist.github.com").await, "github.com");
+            assert_eq!(get_site(&pool, "www.bbc.co.uk").await, "bbc.co.uk");
+            assert_eq!(get_site(&pool, "10.0.0.1").await, "10.0.0.1");
+            assert_eq!(get_site(&pool, "localhost").await, "localhost");
+        });
+    }
+}
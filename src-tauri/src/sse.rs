@@ -0,0 +1,214 @@
+use reqwest::Client;
+use serde::Serialize;
+
+use crate::{
+    error::FetchError,
+    request::{self, FetchOptions, HttpHeader},
+};
+
+const DEFAULT_EVENT_TYPE: &str = "message";
+
+/// Server-Sent Events 订阅事件，先 open，之后每个完整事件一条 message
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum SseEvent {
+    Open {
+        status: u16,
+        headers: Vec<HttpHeader>,
+    },
+    Message {
+        id: Option<String>,
+        r#type: String,
+        data: String,
+    },
+}
+
+/// 按行解析 text/event-stream，响应块可能在任意字节处截断
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    last_id: Option<String>,
+    r#type: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if let Some(event) = self.parse_line(line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn parse_line(&mut self, line: &str) -> Option<SseEvent> {
+        // 空行分发事件，没有 data 的事件直接丢弃
+        if line.is_empty() {
+            let r#type = self.r#type.take();
+            if self.data.is_empty() {
+                return None;
+            }
+            return Some(SseEvent::Message {
+                id: self.last_id.clone(),
+                r#type: r#type.unwrap_or_else(|| DEFAULT_EVENT_TYPE.to_string()),
+                data: std::mem::take(&mut self.data).join("\n"),
+            });
+        }
+        // 冒号开头为注释，常用作心跳
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.r#type = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// 订阅 SSE，直到服务端关闭连接或任务被取消
+pub async fn subscribe(
+    client: &Client,
+    url: &str,
+    options: Option<FetchOptions>,
+    on_event: impl Fn(SseEvent) -> Result<(), FetchError>,
+) -> Result<(), FetchError> {
+    let mut options = options.unwrap_or_default();
+    let headers = options.headers.get_or_insert_with(Vec::new);
+    if !headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case("accept"))
+    {
+        headers.push(HttpHeader {
+            key: "Accept".to_string(),
+            value: "text/event-stream".to_string(),
+        });
+    }
+
+    let request_builder = request::build_request(client, url, Some(options), false).await?;
+    let mut raw_response = request_builder.send().await?;
+    on_event(SseEvent::Open {
+        status: raw_response.status().as_u16(),
+        headers: request::to_headers(&raw_response),
+    })?;
+    // 失败状态或非事件流只报告 open，不把响应体当作事件解析
+    if !raw_response.status().is_success() || !is_event_stream(&raw_response) {
+        return Ok(());
+    }
+
+    let mut parser = SseParser::default();
+    while let Some(chunk) = raw_response.chunk().await? {
+        for event in parser.feed(&chunk) {
+            on_event(event)?;
+        }
+    }
+    Ok(())
+}
+
+fn is_event_stream(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/event-stream"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tauri::async_runtime;
+
+    use super::*;
+    use crate::request::tests::serve;
+
+    fn message(id: Option<&str>, r#type: &str, data: &str) -> SseEvent {
+        SseEvent::Message {
+            id: id.map(str::to_string),
+            r#type: r#type.to_string(),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let mut parser = SseParser::default();
+        let stream = ": ping\n\ndata: first\r\ndata:second\n\n\
+            id: 7\nevent: update\ndata: {\"a\":1}\n\nevent: empty\n\n";
+        // 逐字节输入，模拟任意位置截断的响应块
+        let events: Vec<SseEvent> = stream
+            .as_bytes()
+            .iter()
+            .flat_map(|byte| parser.feed(&[*byte]))
+            .collect();
+
+        assert_eq!(
+            events,
+            [
+                message(None, "message", "first\nsecond"),
+                message(Some("7"), "update", "{\"a\":1}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subscribe() {
+        async_runtime::block_on(async {
+            let (url, server) = serve(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+                 event: greeting\ndata: 白洞\n\n"
+                    .to_string(),
+            );
+            let events = Mutex::new(Vec::new());
+            subscribe(&Client::new(), &url, None, |event| {
+                events.lock().unwrap().push(event);
+                Ok(())
+            })
+            .await
+            .unwrap();
+            let (headers, _) = server.join().unwrap();
+
+            assert!(headers.contains(&"accept: text/event-stream".to_string()));
+            let events = events.into_inner().unwrap();
+            assert!(matches!(events[0], SseEvent::Open { status: 200, .. }));
+            assert_eq!(events[1..], [message(None, "greeting", "白洞")]);
+        });
+    }
+    #[test]
+    fn test_subscribe_not_event_stream() {
+        async_runtime::block_on(async {
+            for response in [
+                "HTTP/1.1 404 Not Found\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+                 data: missing\n\n",
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n\
+                 data: html\n\n",
+            ] {
+                let (url, server) = serve(response.to_string());
+                let events = Mutex::new(Vec::new());
+                subscribe(&Client::new(), &url, None, |event| {
+                    events.lock().unwrap().push(event);
+                    Ok(())
+                })
+                .await
+                .unwrap();
+                server.join().unwrap();
+
+                let events = events.into_inner().unwrap();
+                assert_eq!(events.len(), 1);
+                assert!(matches!(events[0], SseEvent::Open { .. }));
+            }
+        });
+    }
+}
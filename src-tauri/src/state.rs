@@ -1,6 +1,8 @@
 use serde::Serialize;
 use tauri::async_runtime::RwLock;
 
+use crate::url::DisplayUrl;
+
 const CHINESE_NAME: &str = "白洞";
 const ENGLISH_NAME: &str = "White Hole";
 
@@ -9,6 +11,7 @@ pub struct BrowserState {
     pub icon_url: String,
     pub title: String,
     pub url: String,
+    pub display_url: Option<DisplayUrl>,
     pub maximized: bool,
     pub loading: bool,
     pub can_back: bool,
@@ -16,6 +19,8 @@ pub struct BrowserState {
     pub focus: bool,
     pub incognito: bool,
     pub darkreader: bool,
+    pub capture: bool,
+    pub vim: bool,
 }
 
 impl Default for BrowserState {
@@ -23,6 +28,7 @@ impl Default for BrowserState {
         Self {
             title: CHINESE_NAME.to_string(),
             url: ENGLISH_NAME.to_string(),
+            display_url: None,
             icon_url: String::new(),
             maximized: false,
             loading: false,
@@ -31,6 +37,8 @@ impl Default for BrowserState {
             focus: false,
             incognito: false,
             darkreader: true,
+            capture: false,
+            vim: false,
         }
     }
 }
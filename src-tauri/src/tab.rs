@@ -14,10 +14,12 @@ use uuid::Uuid;
 use crate::{
     IsMainView as _,
     browser::BrowserExt,
+    capture::{CAPTURE_DISABLE_SCRIPT, CAPTURE_ENABLE_SCRIPT},
     darkreader::{DARKREADER_DISABLE_SCRIPT, DARKREADER_ENABLE_SCRIPT},
     error::FrameworkError,
     state::BrowserState,
     user_agent::get_user_agent,
+    vim::{VIM_DISABLE_SCRIPT, VIM_ENABLE_SCRIPT, VimCommand, VimMode},
 };
 
 const BLANK_URL: &str = "about:blank";
@@ -29,6 +31,11 @@ pub struct Tab {
     loading: bool,
     incognito: bool,
     darkreader: bool,
+    capture: bool,
+    vim: bool,
+    vim_mode: VimMode,
+    /// 后端下发过链接提示命令，页面才能进入提示模式
+    vim_hint_pending: bool,
     index: isize,
     history: Vec<i64>,
 }
@@ -55,6 +62,7 @@ impl Tab {
             tauri::webview::WebviewBuilder::new(&label, WebviewUrl::External(url.clone()))
                 .initialization_script(include_str!("../js/darkreader.js"))
                 .initialization_script(include_str!("../js/webview_init.js"))
+                .initialization_script(include_str!("../js/vim_init.js"))
                 .initialization_script_for_all_frames(include_str!("../js/all_frames_init.js"))
                 .user_agent(&get_user_agent())
                 .incognito(incognito)
@@ -75,6 +83,10 @@ impl Tab {
             loading: true,
             incognito,
             darkreader: true,
+            capture: false,
+            vim: false,
+            vim_mode: VimMode::default(),
+            vim_hint_pending: false,
             history: Vec::new(),
             index: -1,
         })
@@ -220,6 +232,38 @@ impl Tab {
 
         result
     }
+
+    /// 开启后页面的 fetch、XHR 请求会上报到抓取缓冲区
+    pub fn set_capture(&mut self, enable: bool) -> Result<(), tauri::Error> {
+        let result = if enable {
+            self.eval(CAPTURE_ENABLE_SCRIPT)
+        } else {
+            self.eval(CAPTURE_DISABLE_SCRIPT)
+        };
+
+        if result.is_ok() {
+            self.capture = enable;
+        }
+
+        result
+    }
+
+    /// 开启后页面脚本会上报当前模式，模式从普通模式重新开始
+    pub fn set_vim(&mut self, enable: bool) -> Result<(), tauri::Error> {
+        let result = if enable {
+            self.eval(VIM_ENABLE_SCRIPT)
+        } else {
+            self.eval(VIM_DISABLE_SCRIPT)
+        };
+
+        if result.is_ok() {
+            self.vim = enable;
+            self.vim_mode = VimMode::default();
+            self.vim_hint_pending = false;
+        }
+
+        result
+    }
 }
 
 pub struct TabIndex(RwLock<String>);
@@ -419,6 +463,108 @@ impl TabMap {
             .unwrap_or(Ok(true))
     }
 
+    pub async fn capture(&self, label: &str) -> Result<bool, tauri::Error> {
+        self.0
+            .update_async(label, |_, tab| {
+                tab.set_capture(!tab.capture).map(|_| tab.capture)
+            })
+            .await
+            .unwrap_or(Ok(false))
+    }
+
+    pub async fn is_capturing(&self, label: &str) -> bool {
+        self.0
+            .read_async(label, |_, tab| tab.capture)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// 跨域跳转后 sessionStorage 失效，页面加载完成时重新开启
+    pub async fn restore_capture(&self, label: &str) -> Result<(), tauri::Error> {
+        self.0
+            .update_async(label, |_, tab| {
+                if tab.capture {
+                    tab.set_capture(true)
+                } else {
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap_or(Ok(()))
+    }
+
+    pub async fn set_vim(&self, label: &str, enable: bool) -> Result<(), tauri::Error> {
+        self.0
+            .update_async(label, |_, tab| tab.set_vim(enable))
+            .await
+            .unwrap_or(Ok(()))
+    }
+
+    pub async fn vim(&self, label: &str) -> Result<bool, tauri::Error> {
+        self.0
+            .update_async(label, |_, tab| tab.set_vim(!tab.vim).map(|_| tab.vim))
+            .await
+            .unwrap_or(Ok(false))
+    }
+
+    /// 页面不能自行上报提示模式，只有后端下发过链接提示命令时才接受
+    pub async fn set_vim_mode(&self, label: &str, mode: VimMode) {
+        self.0
+            .update_async(label, |_, tab| {
+                if mode == VimMode::Hint {
+                    if !tab.vim_hint_pending {
+                        return;
+                    }
+                } else {
+                    tab.vim_hint_pending = false;
+                }
+                tab.vim_mode = mode;
+            })
+            .await;
+    }
+
+    /// 标签页正在显示链接提示时回到普通模式并返回真，否则不变
+    pub async fn take_vim_hint(&self, label: &str) -> bool {
+        self.0
+            .update_async(label, |_, tab| {
+                let hint = tab.vim && tab.vim_hint_pending && tab.vim_mode == VimMode::Hint;
+                if hint {
+                    tab.vim_mode = VimMode::Normal;
+                    tab.vim_hint_pending = false;
+                }
+                hint
+            })
+            .await
+            .unwrap_or(false)
+    }
+
+    /// 开启了 Vim 模式且处于普通模式时，Vim 快捷键生效
+    pub async fn is_vim_normal(&self, label: &str) -> bool {
+        self.0
+            .read_async(label, |_, tab| tab.vim && tab.vim_mode == VimMode::Normal)
+            .await
+            .unwrap_or(false)
+    }
+
+    pub async fn vim_command(
+        &self,
+        label: &str,
+        command: VimCommand,
+    ) -> Result<(), FrameworkError> {
+        self.0
+            .update_async(label, |_, tab| {
+                let result = tab.eval(command.script());
+                if result.is_ok() && matches!(command, VimCommand::Hints | VimCommand::HintsNewTab)
+                {
+                    tab.vim_hint_pending = true;
+                }
+                result
+            })
+            .await
+            .unwrap_or(Err(tauri::Error::WebviewNotFound))?;
+        Ok(())
+    }
+
     pub async fn devtools(&self, label: &str) {
         self.0
             .read_async(label, |_, tab| {
@@ -456,6 +602,8 @@ impl TabMap {
                     can_back: tab.can_back(),
                     can_forward: tab.can_forward(),
                     darkreader: tab.darkreader,
+                    capture: tab.capture,
+                    vim: tab.vim,
                     ..Default::default()
                 })
             })
@@ -1,32 +1,44 @@
 use delay_timer::prelude::*;
 use log::error;
 use sqlx::SqlitePool;
+use tauri::AppHandle;
 
-use crate::{database::DB_PATH, public_suffix::sync_public_suffix};
+use crate::{
+    database::DB_PATH, http_client::HttpClientExt as _, public_suffix::sync_public_suffix,
+};
 
-pub fn setup() -> Result<(), TaskError> {
+pub fn setup(app: AppHandle) -> Result<(), TaskError> {
     let delay_timer = DelayTimerBuilder::default()
         .tokio_runtime_by_default()
         .build();
 
-    delay_timer.add_task(startup_task()?)?;
-    delay_timer.add_task(everyday_task()?)?;
+    delay_timer.add_task(startup_task(app.clone())?)?;
+    delay_timer.add_task(everyday_task(app)?)?;
 
     Ok(())
 }
 
-fn startup_task() -> Result<Task, TaskError> {
+fn startup_task(app: AppHandle) -> Result<Task, TaskError> {
     let mut task_builder = TaskBuilder::default();
-    let body = || async {
-        let Some(db_path) = DB_PATH.get() else {
-            return;
-        };
-        let Ok(pool) = SqlitePool::connect(&format!("sqlite:{db_path}")).await else {
-            return;
-        };
+    let body = move || {
+        let app = app.clone();
+        async move {
+            let Some(db_path) = DB_PATH.get() else {
+                return;
+            };
+            let Ok(pool) = SqlitePool::connect(&format!("sqlite:{db_path}")).await else {
+                return;
+            };
 
-        if let Err(e) = sync_public_suffix(&pool).await {
-            error!("同步 public suffix 失败：{e}");
+            let client = app.http_client().get().await;
+            if let Err(e) = sync_public_suffix(&pool, &client).await {
+                error!("同步 public suffix 失败：{e}");
+            }
+
+            // 升级后尽快补全历史记录的站点，否则按站点分组会退回按 URL 分组
+            if let Err(e) = crate::log::fill_site(&pool).await {
+                error!("补全浏览记录站点失败：{e}");
+            }
         }
     };
 
@@ -37,26 +49,38 @@ fn startup_task() -> Result<Task, TaskError> {
         .spawn_async_routine(body)
 }
 
-fn everyday_task() -> Result<Task, TaskError> {
+fn everyday_task(app: AppHandle) -> Result<Task, TaskError> {
     let mut task_builder = TaskBuilder::default();
-    let body = || async move {
-        let Some(db_path) = DB_PATH.get() else {
-            return;
-        };
-        let Ok(pool) = SqlitePool::connect(&format!("sqlite:{db_path}")).await else {
-            return;
-        };
+    let body = move || {
+        let app = app.clone();
+        async move {
+            let Some(db_path) = DB_PATH.get() else {
+                return;
+            };
+            let Ok(pool) = SqlitePool::connect(&format!("sqlite:{db_path}")).await else {
+                return;
+            };
 
-        if let Err(e) = sync_public_suffix(&pool).await {
-            error!("同步 public suffix 失败：{e}");
-        }
+            let client = app.http_client().get().await;
+            if let Err(e) = sync_public_suffix(&pool, &client).await {
+                error!("同步 public suffix 失败：{e}");
+            }
 
-        if let Err(e) = crate::log::clear_log(&pool).await {
-            error!("清理浏览记录失败：{e}");
-        }
+            if let Err(e) = crate::log::clear_log(&pool).await {
+                error!("清理浏览记录失败：{e}");
+            }
+
+            if let Err(e) = crate::icon::clear_icon(&pool).await {
+                error!("清理图标缓存失败：{e}");
+            }
+
+            if let Err(e) = crate::log::fill_site(&pool).await {
+                error!("补全浏览记录站点失败：{e}");
+            }
 
-        if let Err(e) = crate::icon::clear_icon(&pool).await {
-            error!("清理图标缓存失败：{e}");
+            if let Err(e) = crate::request_log::clear_request_log(&pool).await {
+                error!("清理请求记录失败：{e}");
+            }
         }
     };
 
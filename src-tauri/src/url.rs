@@ -1,12 +1,33 @@
 use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 use publicsuffix::{List, Psl as _};
-use url::{Host, Url};
+use serde::Serialize;
+use unicode_security::{
+    GeneralSecurityProfile as _, RestrictionLevel, RestrictionLevelDetection as _, skeleton,
+};
+use url::{Host, Position, Url};
+
+use crate::site::site;
 
 const ALLOWED_SCHEMES: [&str; 5] = ["http", "https", "file", "data", "ftp"];
 
 /// 参考：https://url.spec.whatwg.org/#fragment-percent-encode-set
 const FRAGMENT: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'<').add(b'>').add(b'`');
 
+/// 标题栏展示用的结构化 URL
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DisplayUrl {
+    /// 协议及认证信息，如 `https://`
+    pub prefix: String,
+    /// 可注册域名之前的子域名，如 `www.`
+    pub subdomain: String,
+    /// 可注册域名（eTLD+1），需要突出显示
+    pub domain: String,
+    /// 端口、路径、查询及片段
+    pub suffix: String,
+    /// 域名含混合脚本或易混淆字符，保留 punycode 展示
+    pub suspicious: bool,
+}
+
 pub fn encode(keyword: &str) -> String {
     utf8_percent_encode(keyword, FRAGMENT).to_string()
 }
@@ -69,3 +90,85 @@ pub async fn parse_keyword(public_suffix: Option<List>, keyword: &str) -> Option
 fn complete_search_url(input: &str) -> Option<Url> {
     Url::parse_with_params("https://cn.bing.com/search", &[("q", input)]).ok()
 }
+
+/// 将 URL 转换为展示结构：IDNA 域名在通过混合脚本及易混淆检查后才转为 Unicode
+pub fn display_url(public_suffix: Option<&List>, url: &str) -> Option<DisplayUrl> {
+    let url = Url::parse(url).ok()?;
+    let Some(Host::Domain(host)) = url.host() else {
+        return None;
+    };
+
+    let (unicode_host, result) = idna::domain_to_unicode(host);
+    let suspicious = result.is_err() || unicode_host.split('.').any(|label| !is_safe_label(label));
+    let display_host = if suspicious { host } else { &unicode_host };
+
+    // 站点按 ASCII 形式计算，再按标签数映射回展示形式
+    let domain_labels = public_suffix
+        .map(|list| site(list, host).split('.').count())
+        .unwrap_or(0);
+    let labels: Vec<&str> = display_host.split('.').collect();
+    let split = labels.len().saturating_sub(domain_labels);
+    let (subdomain, domain) = if domain_labels == 0 || split == 0 {
+        (String::new(), labels.join("."))
+    } else {
+        (
+            format!("{}.", labels[..split].join(".")),
+            labels[split..].join("."),
+        )
+    };
+
+    Some(DisplayUrl {
+        prefix: url[..Position::BeforeHost].to_string(),
+        subdomain,
+        domain,
+        suffix: url[Position::AfterHost..].to_string(),
+        suspicious,
+    })
+}
+
+/// 参考：https://www.unicode.org/reports/tr39/#Restriction_Level_Detection
+fn is_safe_label(label: &str) -> bool {
+    if label.is_ascii() {
+        return true;
+    }
+
+    let allowed = label.chars().all(|c| c == '-' || c.identifier_allowed());
+    // 整个标签都能被 ASCII 字符冒充，如西里尔字母拼写的 аррӏе
+    let whole_script_confusable = skeleton(label).all(|c| c.is_ascii());
+
+    allowed
+        && label.check_restriction_level(RestrictionLevel::HighlyRestrictive)
+        && !whole_script_confusable
+}
+
+#[test]
+fn test_display_url() {
+    let url = display_url(None, "https://www.xn--fiq228c.com/%E4%B8%AD").unwrap();
+    assert!(!url.suspicious);
+    assert_eq!(url.domain, "www.中文.com");
+    assert_eq!(url.suffix, "/%E4%B8%AD");
+
+    // 西里尔字母拼写的 apple.com
+    let url = display_url(None, "https://xn--80ak6aa92e.com/").unwrap();
+    assert!(url.suspicious);
+    assert_eq!(url.domain, "xn--80ak6aa92e.com");
+
+    // 按公共后缀拆出可注册域名
+    let list: List = "// ===BEGIN ICANN DOMAINS===\ncom\nuk\nco.uk\n// ===END ICANN DOMAINS===\n"
+        .parse()
+        .unwrap();
+    let url = display_url(Some(&list), "https://www.example.co.uk/a?b").unwrap();
+    assert_eq!(url.prefix, "https://");
+    assert_eq!(url.subdomain, "www.");
+    assert_eq!(url.domain, "example.co.uk");
+    assert_eq!(url.suffix, "/a?b");
+
+    let url = display_url(Some(&list), "https://a.xn--fiq228c.example.com/").unwrap();
+    assert!(!url.suspicious);
+    assert_eq!(url.subdomain, "a.中文.");
+    assert_eq!(url.domain, "example.com");
+
+    let url = display_url(Some(&list), "https://example.com/").unwrap();
+    assert_eq!(url.subdomain, "");
+    assert_eq!(url.domain, "example.com");
+}
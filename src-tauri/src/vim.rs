@@ -0,0 +1,122 @@
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+/// 页面脚本据此在普通模式下拦截单键、在可编辑元素获得焦点时进入插入模式，见 `vim_init.js`
+pub const VIM_ENABLE_SCRIPT: &str =
+    "window.__WHITE_HOLE_VIM__ && window.__WHITE_HOLE_VIM__.enable()";
+pub const VIM_DISABLE_SCRIPT: &str =
+    "window.__WHITE_HOLE_VIM__ && window.__WHITE_HOLE_VIM__.disable()";
+
+/// 页面脚本上报的当前模式，只有普通模式下 Vim 快捷键生效
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VimMode {
+    #[default]
+    Normal,
+    /// 可编辑元素获得焦点或按 `i` 进入，按键交给页面，Esc 退出
+    Insert,
+    /// 正在显示链接提示，按键用于选择提示
+    Hint,
+}
+
+/// 普通模式下由快捷键触发、在页面中执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimCommand {
+    ScrollDown,
+    ScrollUp,
+    ScrollTop,
+    ScrollBottom,
+    /// 标注可点击元素，选中后点击
+    Hints,
+    /// 标注链接，选中后在新标签页打开
+    HintsNewTab,
+    Find,
+    FindNext,
+    FindPrevious,
+    Insert,
+}
+
+impl VimCommand {
+    pub fn script(self) -> &'static str {
+        match self {
+            Self::ScrollDown => "window.__WHITE_HOLE_VIM__.scroll(1)",
+            Self::ScrollUp => "window.__WHITE_HOLE_VIM__.scroll(-1)",
+            Self::ScrollTop => "window.__WHITE_HOLE_VIM__.scrollToEdge(false)",
+            Self::ScrollBottom => "window.__WHITE_HOLE_VIM__.scrollToEdge(true)",
+            Self::Hints => "window.__WHITE_HOLE_VIM__.hints(false)",
+            Self::HintsNewTab => "window.__WHITE_HOLE_VIM__.hints(true)",
+            Self::Find => "window.__WHITE_HOLE_VIM__.find()",
+            Self::FindNext => "window.__WHITE_HOLE_VIM__.findNext(false)",
+            Self::FindPrevious => "window.__WHITE_HOLE_VIM__.findNext(true)",
+            Self::Insert => "window.__WHITE_HOLE_VIM__.insert()",
+        }
+    }
+}
+
+/// host 本身或其所属站点在列表中时开启 Vim 模式，默认关闭
+pub async fn switch(pool: &SqlitePool, host: &str, site: &str) -> bool {
+    sqlx::query!(
+        "select id from vim_site where (by_site = 0 and host = ?) or (by_site = 1 and host = ?)",
+        host,
+        site
+    )
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .is_some()
+}
+
+pub async fn save_site(pool: &SqlitePool, host: &str, by_site: bool) -> Result<i64, sqlx::Error> {
+    sqlx::query!(
+        "insert or replace into vim_site (host, by_site) values (?, ?)",
+        host,
+        by_site
+    )
+    .execute(pool)
+    .await
+    .map(|result| result.last_insert_rowid())
+}
+
+/// 同时删除 host 及其所属站点的记录
+pub async fn delete_site(pool: &SqlitePool, host: &str, site: &str) -> Result<u64, sqlx::Error> {
+    sqlx::query!(
+        "delete from vim_site where (by_site = 0 and host = ?) or (by_site = 1 and host = ?)",
+        host,
+        site
+    )
+    .execute(pool)
+    .await
+    .map(|result| result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch() {
+        tauri::async_runtime::block_on(async {
+            let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+            sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+            assert!(!switch(&pool, "github.com", "github.com").await);
+
+            // 在可注册域名本身开启时，整个站点都开启
+            save_site(&pool, "github.com", true).await.unwrap();
+            assert!(switch(&pool, "gist.github.com", "github.com").await);
+
+            save_site(&pool, "docs.rs", false).await.unwrap();
+            assert!(switch(&pool, "docs.rs", "docs.rs").await);
+            assert!(!switch(&pool, "www.docs.rs", "docs.rs").await);
+
+            assert_eq!(
+                delete_site(&pool, "gist.github.com", "github.com")
+                    .await
+                    .unwrap(),
+                1
+            );
+            assert!(!switch(&pool, "github.com", "github.com").await);
+        });
+    }
+}
@@ -0,0 +1,338 @@
+use futures_util::{SinkExt as _, StreamExt as _};
+use scc::{HashMap, hash_map::Entry};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        Message,
+        client::IntoClientRequest as _,
+        handshake::client::Request,
+        http::{self, HeaderName, HeaderValue},
+        protocol::{CloseFrame, frame::coding::CloseCode},
+    },
+};
+
+use crate::{
+    error::StreamError,
+    request::{Authorization, FetchOptions, HttpHeader, with_query},
+};
+
+/// 未收到关闭帧时的关闭码
+const ABNORMAL_CLOSURE: u16 = 1006;
+
+/// WebSocket 连接事件，依次为 open、若干 message、close
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum WebSocketEvent {
+    Open {
+        status: u16,
+        headers: Vec<HttpHeader>,
+    },
+    Message(WebSocketMessage),
+    Close {
+        code: u16,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum WebSocketMessage {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+enum SocketCommand {
+    Send(WebSocketMessage),
+    Close { code: u16, reason: String },
+}
+
+/// 已建立的 WebSocket 连接，按 connection id 发送消息或关闭
+#[derive(Default)]
+pub struct WebSocketRegistry(HashMap<String, mpsc::UnboundedSender<SocketCommand>>);
+
+impl WebSocketRegistry {
+    /// 建立连接并转发收到的消息，直到连接关闭。
+    ///
+    /// 握手由 tungstenite 直接发起，不经过共享 HTTP 客户端，不使用其中配置的代理和额外信任的证书
+    pub async fn connect(
+        &self,
+        connection_id: String,
+        url: &str,
+        options: Option<FetchOptions>,
+        on_event: impl Fn(WebSocketEvent) -> Result<(), StreamError>,
+    ) -> Result<(), StreamError> {
+        let request = build_request(url, options)?;
+        // 连接前先占用 id，同一 id 同时只能有一个连接
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        match self.0.entry_async(connection_id.clone()).await {
+            Entry::Occupied(_) => return Err(StreamError::DuplicateConnection { connection_id }),
+            Entry::Vacant(entry) => {
+                entry.insert_entry(tx.clone());
+            }
+        }
+
+        let result = async {
+            let (stream, response) = connect_async(request).await?;
+            let (mut sink, mut stream) = stream.split();
+            on_event(WebSocketEvent::Open {
+                status: response.status().as_u16(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .map(|(key, value)| HttpHeader {
+                        key: key.as_str().to_string(),
+                        value: value.to_str().unwrap_or("...").to_string(),
+                    })
+                    .collect(),
+            })?;
+
+            let mut closed = false;
+            loop {
+                tokio::select! {
+                    Some(command) = rx.recv() => match command {
+                        SocketCommand::Send(message) => sink.send(message.into()).await?,
+                        // 发送关闭帧后继续读取，等待服务端回应
+                        SocketCommand::Close { code, reason } => {
+                            sink.send(Message::Close(Some(CloseFrame {
+                                code: CloseCode::from(code),
+                                reason: reason.into(),
+                            })))
+                            .await?
+                        }
+                    },
+                    message = stream.next() => match message {
+                        Some(Ok(Message::Text(text))) => on_event(WebSocketEvent::Message(
+                            WebSocketMessage::Text(text.to_string()),
+                        ))?,
+                        Some(Ok(Message::Binary(data))) => on_event(WebSocketEvent::Message(
+                            WebSocketMessage::Binary(data.to_vec()),
+                        ))?,
+                        Some(Ok(Message::Close(frame))) => {
+                            closed = true;
+                            let (code, reason) = frame
+                                .map(|frame| (frame.code.into(), frame.reason.to_string()))
+                                .unwrap_or((ABNORMAL_CLOSURE, String::new()));
+                            on_event(WebSocketEvent::Close { code, reason })?
+                        }
+                        // ping、pong 由 tungstenite 自动处理
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) if closed => {
+                            log::debug!("WebSocket 关闭后读取失败：{e}");
+                            break;
+                        }
+                        Some(Err(e)) => return Err(e.into()),
+                        None => break,
+                    },
+                }
+            }
+
+            if !closed {
+                on_event(WebSocketEvent::Close {
+                    code: ABNORMAL_CLOSURE,
+                    reason: String::new(),
+                })?;
+            }
+            Ok(())
+        }
+        .await;
+
+        // 只移除自己的记录
+        self.0
+            .remove_if_async(&connection_id, |sender| sender.same_channel(&tx))
+            .await;
+        result
+    }
+
+    pub async fn send(
+        &self,
+        connection_id: &str,
+        message: WebSocketMessage,
+    ) -> Result<(), StreamError> {
+        self.command(connection_id, SocketCommand::Send(message))
+            .await
+    }
+
+    pub async fn close(
+        &self,
+        connection_id: &str,
+        code: Option<u16>,
+        reason: Option<String>,
+    ) -> Result<(), StreamError> {
+        let code = code.unwrap_or(CloseCode::Normal.into());
+        // 1005/1006/1015 只用于本地状态，不能出现在关闭帧里
+        if !(1000..5000).contains(&code) || matches!(code, 1005 | 1006 | 1015) {
+            return Err(StreamError::InvalidCloseCode { code });
+        }
+        let reason = reason.unwrap_or_default();
+        self.command(connection_id, SocketCommand::Close { code, reason })
+            .await
+    }
+
+    async fn command(
+        &self,
+        connection_id: &str,
+        command: SocketCommand,
+    ) -> Result<(), StreamError> {
+        self.0
+            .read_async(connection_id, |_, tx| tx.send(command).is_ok())
+            .await
+            .filter(|sent| *sent)
+            .map(|_| ())
+            .ok_or(StreamError::NotConnected)
+    }
+}
+
+impl From<WebSocketMessage> for Message {
+    fn from(message: WebSocketMessage) -> Self {
+        match message {
+            WebSocketMessage::Text(text) => Message::text(text),
+            WebSocketMessage::Binary(data) => Message::binary(data),
+        }
+    }
+}
+
+/// 握手请求带上自定义请求头与认证信息，请求方法和请求体不适用
+fn build_request(url: &str, options: Option<FetchOptions>) -> Result<Request, StreamError> {
+    let options = options.unwrap_or_default();
+    let url = match &options.authorization {
+        Some(Authorization::Query { key, value }) => with_query(url, key, value),
+        _ => url.to_string(),
+    };
+
+    let mut request = url.into_client_request()?;
+    let headers = request.headers_mut();
+    for header in options.headers.unwrap_or_default() {
+        headers.append(
+            HeaderName::from_bytes(header.key.as_bytes()).map_err(http::Error::from)?,
+            HeaderValue::from_str(&header.value).map_err(http::Error::from)?,
+        );
+    }
+    if let Some(Authorization::Header { key, value }) = options.authorization {
+        headers.insert(
+            HeaderName::from_bytes(key.as_bytes()).map_err(http::Error::from)?,
+            HeaderValue::from_str(&value).map_err(http::Error::from)?,
+        );
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use tauri::async_runtime;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// 本地回显服务，原样返回文本与二进制消息
+    async fn echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        async_runtime::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if (message.is_text() || message.is_binary()) && ws.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn test_echo() {
+        async_runtime::block_on(async {
+            let url = echo_server().await;
+            let registry = std::sync::Arc::new(WebSocketRegistry::default());
+            async_runtime::spawn({
+                let registry = registry.clone();
+                let url = url.clone();
+                async move {
+                    // 等待连接登记后发送
+                    while registry
+                        .send("echo", WebSocketMessage::Text("白洞".to_string()))
+                        .await
+                        .is_err()
+                    {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                    // 同一 id 已有连接时拒绝，不影响已有连接
+                    assert!(matches!(
+                        registry
+                            .connect("echo".to_string(), &url, None, |_| Ok(()))
+                            .await,
+                        Err(StreamError::DuplicateConnection { .. })
+                    ));
+                    registry
+                        .send("echo", WebSocketMessage::Binary(vec![0, 255]))
+                        .await
+                        .unwrap();
+                    for code in [999, 1005, 1006, 1015, 5000] {
+                        assert!(matches!(
+                            registry.close("echo", Some(code), None).await,
+                            Err(StreamError::InvalidCloseCode { .. })
+                        ));
+                    }
+                    registry
+                        .close("echo", Some(4000), Some("bye".to_string()))
+                        .await
+                        .unwrap();
+                }
+            });
+
+            let events = Mutex::new(Vec::new());
+            registry
+                .connect("echo".to_string(), &url, None, |event| {
+                    events.lock().unwrap().push(event);
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            let events = events.into_inner().unwrap();
+            assert!(matches!(
+                events[0],
+                WebSocketEvent::Open { status: 101, .. }
+            ));
+            assert_eq!(
+                events[1..],
+                [
+                    WebSocketEvent::Message(WebSocketMessage::Text("白洞".to_string())),
+                    WebSocketEvent::Message(WebSocketMessage::Binary(vec![0, 255])),
+                    WebSocketEvent::Close {
+                        code: 4000,
+                        reason: "bye".to_string(),
+                    },
+                ]
+            );
+            assert!(
+                registry
+                    .send("echo", WebSocketMessage::Text(String::new()))
+                    .await
+                    .is_err()
+            );
+        });
+    }
+
+    #[test]
+    fn test_build_request() {
+        let options = FetchOptions {
+            headers: Some(vec![HttpHeader {
+                key: "Sec-WebSocket-Protocol".to_string(),
+                value: "chat".to_string(),
+            }]),
+            authorization: Some(Authorization::Query {
+                key: "token".to_string(),
+                value: "secret".to_string(),
+            }),
+            ..Default::default()
+        };
+        let request = build_request("wss://example.com/socket", Some(options)).unwrap();
+        assert_eq!(request.uri(), "wss://example.com/socket?token=secret");
+        assert_eq!(request.headers()["sec-websocket-protocol"], "chat");
+    }
+}
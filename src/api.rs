@@ -1,7 +1,9 @@
+use std::collections::HashMap;
+
 use dioxus::prelude::dioxus_stores;
 use dioxus::stores::Store;
 use serde::{Deserialize, Serialize};
-use tauri_sys::core::{invoke, invoke_result};
+use tauri_sys::core::{Channel, invoke, invoke_result};
 use time::OffsetDateTime;
 
 pub async fn get_state() -> Result<BrowserState, Error> {
@@ -22,12 +24,14 @@ pub async fn update_star(id: i64) -> Result<(), Error> {
 
 pub async fn query_navigation_log(
     keyword: String,
+    group_by_site: bool,
     page_token: PageToken,
 ) -> Result<QueryLogResponse, Error> {
     invoke_result(
         "query_navigation_log",
         &QueryLogRequest {
             keyword,
+            group_by_site,
             page_token,
         },
     )
@@ -83,13 +87,272 @@ pub async fn darkreader() {
     invoke::<()>("darkreader", &()).await;
 }
 
+/// 开关当前标签页的请求抓取
+pub async fn capture() {
+    invoke::<()>("capture", &()).await;
+}
+
+/// 开关当前站点的 Vim 模式
+pub async fn vim() {
+    invoke::<()>("vim", &()).await;
+}
+
+pub async fn query_captured_requests() -> Result<Vec<CapturedRequest>, Error> {
+    invoke_result("query_captured_requests", &()).await
+}
+
+pub async fn clear_captured_requests() -> Result<(), Error> {
+    invoke_result("clear_captured_requests", &()).await
+}
+
 pub async fn fetch(req: HttpRequest) -> Result<HttpResponse, Error> {
     invoke_result("fetch", &req).await
 }
 
+pub async fn cancel_fetch(request_id: String) -> Result<bool, Error> {
+    invoke_result("cancel_fetch", &CancelFetchRequest { request_id }).await
+}
+
+pub async fn query_request_log(
+    keyword: String,
+    page_token: PageToken,
+) -> Result<QueryRequestLogResponse, Error> {
+    invoke_result(
+        "query_request_log",
+        &QueryRequestLogRequest {
+            keyword,
+            page_token,
+        },
+    )
+    .await
+}
+
+pub async fn delete_request_log(id: i64) -> Result<(), Error> {
+    invoke_result("delete_request_log", &IdRequest { id }).await
+}
+
+pub async fn replay_request_log(id: i64, request_id: String) -> Result<HttpResponse, Error> {
+    invoke_result("replay_request_log", &ReplayRequest { id, request_id }).await
+}
+
+pub async fn query_collections() -> Result<Vec<Collection>, Error> {
+    invoke_result("query_collections", &()).await
+}
+
+pub async fn save_collection(parent_id: Option<i64>, name: String) -> Result<i64, Error> {
+    invoke_result(
+        "save_collection",
+        &SaveCollectionRequest {
+            id: None,
+            parent_id,
+            name,
+        },
+    )
+    .await
+}
+
+pub async fn delete_collection(id: i64) -> Result<(), Error> {
+    invoke_result("delete_collection", &IdRequest { id }).await
+}
+
+pub async fn save_request(request: SavedRequest) -> Result<i64, Error> {
+    invoke_result("save_request", &SaveRequestRequest { request }).await
+}
+
+pub async fn delete_saved_request(id: i64) -> Result<(), Error> {
+    invoke_result("delete_saved_request", &IdRequest { id }).await
+}
+
+pub async fn replay_saved_request(id: i64, request_id: String) -> Result<HttpResponse, Error> {
+    invoke_result("replay_saved_request", &ReplayRequest { id, request_id }).await
+}
+
+pub async fn query_environments() -> Result<Vec<Environment>, Error> {
+    invoke_result("query_environments", &()).await
+}
+
+pub async fn save_environment(id: Option<i64>, name: String) -> Result<i64, Error> {
+    invoke_result("save_environment", &SaveEnvironmentRequest { id, name }).await
+}
+
+pub async fn delete_environment(id: i64) -> Result<(), Error> {
+    invoke_result("delete_environment", &IdRequest { id }).await
+}
+
+pub async fn set_active_environment(id: Option<i64>) -> Result<(), Error> {
+    invoke_result("set_active_environment", &ActiveEnvironmentRequest { id }).await
+}
+
+pub async fn save_variable(variable: Variable) -> Result<i64, Error> {
+    invoke_result("save_variable", &SaveVariableRequest { variable }).await
+}
+
+pub async fn delete_variable(id: i64) -> Result<(), Error> {
+    invoke_result("delete_variable", &IdRequest { id }).await
+}
+
+pub async fn capture_variable(key: String, path: String, body: Vec<u8>) -> Result<String, Error> {
+    invoke_result(
+        "capture_variable",
+        &CaptureVariableRequest { key, path, body },
+    )
+    .await
+}
+
+pub async fn query_credentials() -> Result<Vec<Credential>, Error> {
+    invoke_result("query_credentials", &()).await
+}
+
+pub async fn save_credential(credential: Credential) -> Result<i64, Error> {
+    invoke_result("save_credential", &SaveCredentialRequest { credential }).await
+}
+
+pub async fn delete_credential(id: i64) -> Result<(), Error> {
+    invoke_result("delete_credential", &IdRequest { id }).await
+}
+
+/// 保存到下载目录，返回实际保存的路径
+pub async fn save_response_body(file_name: String, body: Vec<u8>) -> Result<String, Error> {
+    invoke_result(
+        "save_response_body",
+        &SaveResponseBodyRequest { file_name, body },
+    )
+    .await
+}
+
+/// 导出为 HAR 文件并保存到下载目录，返回实际保存的路径
+pub async fn export_har(source: HarSource) -> Result<String, Error> {
+    invoke_result("export_har", &ExportHarRequest { source }).await
+}
+
+/// 从本地 HAR 文件导入请求到集合，返回导入数量
+pub async fn import_har(collection_id: i64, path: String) -> Result<usize, Error> {
+    invoke_result(
+        "import_har",
+        &ImportHarRequest {
+            collection_id,
+            path,
+        },
+    )
+    .await
+}
+
+/// 依次发送集合及其子文件夹中的请求并校验断言
+pub async fn run_collection(id: i64) -> Result<RunReport, Error> {
+    invoke_result("run_collection", &IdRequest { id }).await
+}
+
+pub async fn check_assertions(
+    assertions: Vec<Assertion>,
+    response: HttpResponse,
+) -> Result<Vec<AssertionResult>, Error> {
+    invoke_result(
+        "check_assertions",
+        &CheckAssertionsRequest {
+            assertions,
+            response,
+        },
+    )
+    .await
+}
+
+/// 订阅 SSE，事件推送到 `on_event`，服务端关闭连接或 `cancel_fetch` 后返回
+pub fn subscribe_sse(
+    url: String,
+    options: Option<FetchOptions>,
+    request_id: String,
+    on_event: &Channel<SseEvent>,
+) -> impl Future<Output = Result<(), Error>> + use<> {
+    // 参数先行序列化，调用期间通道留给调用方读取
+    let args = serde_json::to_value(SubscribeSseRequest {
+        url,
+        options,
+        request_id,
+        on_event,
+    });
+    async move { invoke_result("subscribe_sse", &args.map_err(Error::from)?).await }
+}
+
+/// 建立 WebSocket 连接，事件推送到 `on_event`，连接关闭后返回
+pub fn connect_websocket(
+    url: String,
+    options: Option<FetchOptions>,
+    connection_id: String,
+    on_event: &Channel<WebSocketEvent>,
+) -> impl Future<Output = Result<(), Error>> + use<> {
+    let args = serde_json::to_value(ConnectWebSocketRequest {
+        url,
+        options,
+        connection_id,
+        on_event,
+    });
+    async move { invoke_result("connect_websocket", &args.map_err(Error::from)?).await }
+}
+
+pub async fn send_websocket(connection_id: String, message: WebSocketMessage) -> Result<(), Error> {
+    invoke_result(
+        "send_websocket",
+        &SendWebSocketRequest {
+            connection_id,
+            message,
+        },
+    )
+    .await
+}
+
+pub async fn close_websocket(
+    connection_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    invoke_result(
+        "close_websocket",
+        &CloseWebSocketRequest {
+            connection_id,
+            code,
+            reason,
+        },
+    )
+    .await
+}
+
+/// 所有快捷键及其描述、分类，按分类排序
+pub async fn list_hotkeys() -> Result<Vec<HotkeyBinding>, Error> {
+    invoke_result("list_hotkeys", &()).await
+}
+
+/// 替换动作的全部快捷键，空列表表示解绑
+pub async fn set_hotkey_binding(action: String, hotkeys: Vec<String>) -> Result<(), Error> {
+    invoke_result(
+        "set_hotkey_binding",
+        &SetHotkeyBindingRequest { action, hotkeys },
+    )
+    .await
+}
+
+pub async fn reset_hotkey_binding(action: String) -> Result<(), Error> {
+    invoke_result("reset_hotkey_binding", &ActionRequest { action }).await
+}
+
+/// 录制快捷键期间暂停触发
+pub async fn set_hotkey_enabled(enabled: bool) -> Result<(), Error> {
+    invoke_result("set_hotkey_enabled", &HotkeyEnabledRequest { enabled }).await
+}
+
+/// 按键位置（如 `KeyW`）到当前键盘布局下该位置输入的字符
+pub async fn set_keyboard_layout(layout: HashMap<String, String>) -> Result<(), Error> {
+    invoke_result("set_keyboard_layout", &KeyboardLayoutRequest { layout }).await
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Error(String);
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self(e.to_string())
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -103,6 +366,7 @@ pub struct BrowserState {
     pub icon_url: String,
     pub title: String,
     pub url: String,
+    pub display_url: Option<DisplayUrl>,
     pub maximized: bool,
     pub loading: bool,
     pub can_back: bool,
@@ -110,6 +374,18 @@ pub struct BrowserState {
     pub focus: bool,
     pub incognito: bool,
     pub darkreader: bool,
+    pub capture: bool,
+    pub vim: bool,
+}
+
+/// 后端拆分好的展示用 URL，`domain` 为可注册域名
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct DisplayUrl {
+    pub prefix: String,
+    pub subdomain: String,
+    pub domain: String,
+    pub suffix: String,
+    pub suspicious: bool,
 }
 
 #[derive(Debug, Clone, Serialize, PartialEq, Deserialize)]
@@ -150,9 +426,262 @@ struct TouchLogRequest {
 #[derive(Serialize)]
 struct QueryLogRequest {
     pub keyword: String,
+    pub group_by_site: bool,
     pub page_token: PageToken,
 }
 
+#[derive(Serialize)]
+struct CancelFetchRequest {
+    request_id: String,
+}
+
+#[derive(Serialize)]
+struct IdRequest {
+    id: i64,
+}
+
+#[derive(Serialize)]
+struct ReplayRequest {
+    id: i64,
+    request_id: String,
+}
+
+#[derive(Serialize)]
+struct QueryRequestLogRequest {
+    keyword: String,
+    page_token: PageToken,
+}
+
+#[derive(Serialize)]
+struct SaveCollectionRequest {
+    id: Option<i64>,
+    parent_id: Option<i64>,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct SaveRequestRequest {
+    request: SavedRequest,
+}
+
+#[derive(Serialize)]
+struct SaveEnvironmentRequest {
+    id: Option<i64>,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct ActiveEnvironmentRequest {
+    id: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct SaveVariableRequest {
+    variable: Variable,
+}
+
+#[derive(Serialize)]
+struct CaptureVariableRequest {
+    key: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct SaveCredentialRequest {
+    credential: Credential,
+}
+
+#[derive(Serialize)]
+struct SaveResponseBodyRequest {
+    file_name: String,
+    body: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct ExportHarRequest {
+    source: HarSource,
+}
+
+#[derive(Serialize)]
+struct ImportHarRequest {
+    collection_id: i64,
+    path: String,
+}
+
+#[derive(Serialize)]
+struct CheckAssertionsRequest {
+    assertions: Vec<Assertion>,
+    response: HttpResponse,
+}
+
+#[derive(Serialize)]
+struct SubscribeSseRequest<'a> {
+    url: String,
+    options: Option<FetchOptions>,
+    request_id: String,
+    on_event: &'a Channel<SseEvent>,
+}
+
+#[derive(Serialize)]
+struct ConnectWebSocketRequest<'a> {
+    url: String,
+    options: Option<FetchOptions>,
+    connection_id: String,
+    on_event: &'a Channel<WebSocketEvent>,
+}
+
+#[derive(Serialize)]
+struct SendWebSocketRequest {
+    connection_id: String,
+    message: WebSocketMessage,
+}
+
+#[derive(Serialize)]
+struct CloseWebSocketRequest {
+    connection_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SetHotkeyBindingRequest {
+    action: String,
+    hotkeys: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ActionRequest {
+    action: String,
+}
+
+#[derive(Serialize)]
+struct HotkeyEnabledRequest {
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+struct KeyboardLayoutRequest {
+    layout: HashMap<String, String>,
+}
+
+#[derive(Clone, Default, Deserialize)]
+pub struct QueryRequestLogResponse {
+    pub next_page_token: Option<PageToken>,
+    pub logs: Vec<RequestLog>,
+}
+
+/// HTTP 客户端执行过的请求
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RequestLog {
+    pub id: i64,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    pub status: Option<u16>,
+    pub elapsed_time: Option<i32>,
+    pub error: Option<String>,
+    pub credential_id: Option<i64>,
+    pub create_time: OffsetDateTime,
+}
+
+/// 请求集合，`parent_id` 不为空时为子文件夹
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Collection {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub requests: Vec<SavedRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedRequest {
+    pub id: Option<i64>,
+    pub collection_id: i64,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    pub credential_id: Option<i64>,
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+    #[serde(default)]
+    pub captures: Vec<Capture>,
+    pub update_time: Option<OffsetDateTime>,
+}
+
+/// 请求环境，同一时间最多一个处于激活状态
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Environment {
+    pub id: i64,
+    pub name: String,
+    pub active: bool,
+    pub variables: Vec<Variable>,
+}
+
+/// 私密变量的 `value` 不返回；保存时为空表示保持原值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variable {
+    pub id: Option<i64>,
+    pub environment_id: i64,
+    pub key: String,
+    pub value: Option<String>,
+    pub secret: bool,
+}
+
+/// 认证凭据，私密字段不返回；保存时为空表示保持原值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    pub id: Option<i64>,
+    pub name: String,
+    pub auth: Auth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Auth {
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    Bearer {
+        token: Option<String>,
+    },
+    ApiKey {
+        key: String,
+        value: Option<String>,
+        location: ApiKeyLocation,
+    },
+    #[serde(rename = "oauth2")]
+    OAuth2 {
+        grant: OAuth2Grant,
+        token_url: String,
+        client_id: String,
+        client_secret: Option<String>,
+        #[serde(default)]
+        username: String,
+        password: Option<String>,
+        #[serde(default)]
+        scope: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuth2Grant {
+    ClientCredentials,
+    Password,
+}
+
 #[derive(Clone, Default, Deserialize)]
 pub struct QueryLogResponse {
     pub next_page_token: Option<PageToken>,
@@ -165,18 +694,206 @@ pub struct HttpHeader {
     pub value: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HttpRequest {
     pub url: String,
     pub options: Option<FetchOptions>,
+    pub request_id: Option<String>,
 }
 
 /// fetch 函数的可选配置项，支持 JSON 序列化
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct FetchOptions {
     pub method: Option<String>,
     pub headers: Option<Vec<HttpHeader>>,
-    pub body: Option<String>,
+    pub body: Option<FetchBody>,
+    pub max_size: Option<u64>,
+    /// 认证凭据 id，由后端注入认证信息
+    #[serde(default)]
+    pub credential_id: Option<i64>,
+}
+
+/// 请求体：文本、字节数组、base64，或由后端编码的 JSON、表单
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FetchBody {
+    Text(String),
+    Bytes(Vec<u8>),
+    Base64 { base64: String },
+    Json { json: String },
+    Form { form: Vec<FormField> },
+    Multipart { multipart: Vec<FormField> },
+}
+
+/// 表单字段，multipart 中 `file` 为真时 `value` 是本地文件路径
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub file: bool,
+}
+
+impl FetchBody {
+    /// 编辑器中显示的文本，二进制内容按 UTF-8 有损解码
+    pub fn to_text(&self) -> String {
+        match self {
+            FetchBody::Text(text) => text.clone(),
+            FetchBody::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+            FetchBody::Base64 { base64 } => base64.clone(),
+            FetchBody::Json { json } => json.clone(),
+            FetchBody::Form { form: fields } | FetchBody::Multipart { multipart: fields } => fields
+                .iter()
+                .map(|field| format!("{}={}", field.key, field.value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// 无法按文本无损编辑的请求体，编辑器中只读显示，原样发送
+    pub fn is_binary(&self) -> bool {
+        match self {
+            FetchBody::Bytes(bytes) => std::str::from_utf8(bytes).is_err(),
+            FetchBody::Base64 { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// 表单字段，非表单请求体返回空
+    pub fn fields(&self) -> Vec<FormField> {
+        match self {
+            FetchBody::Form { form: fields } | FetchBody::Multipart { multipart: fields } => {
+                fields.clone()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 导出 HAR 的来源
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HarSource {
+    Request {
+        url: String,
+        options: Option<FetchOptions>,
+        response: Option<HttpResponse>,
+    },
+    Collection {
+        id: i64,
+    },
+    History,
+}
+
+/// 标签页通过 fetch 或 XHR 发出的请求，`initiator` 为 `fetch` 或 `xhr`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CapturedRequest {
+    pub id: u64,
+    pub page_url: String,
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    pub initiator: String,
+    /// 含 `{{`，重新发送时会填入环境变量
+    pub templated: bool,
+    #[serde(with = "time::serde::iso8601")]
+    pub capture_time: OffsetDateTime,
+}
+
+/// 响应断言，`JsonPath` 的 `equals` 为空时只要求有匹配值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Assertion {
+    Status {
+        equals: u16,
+    },
+    Header {
+        name: String,
+        contains: String,
+    },
+    JsonPath {
+        path: String,
+        equals: Option<String>,
+    },
+    ResponseTime {
+        under: i32,
+    },
+}
+
+/// 运行集合时从响应中提取变量
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    pub key: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AssertionResult {
+    pub assertion: Assertion,
+    pub passed: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RequestResult {
+    pub request_id: Option<i64>,
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub elapsed_time: Option<i32>,
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+    pub passed: bool,
+}
+
+/// 集合运行报告，`junit` 为 JUnit XML
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RunReport {
+    pub name: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub elapsed_time: i32,
+    pub results: Vec<RequestResult>,
+    pub junit: String,
+}
+
+/// Server-Sent Events 订阅事件
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum SseEvent {
+    Open {
+        status: u16,
+        headers: Vec<HttpHeader>,
+    },
+    Message {
+        id: Option<String>,
+        r#type: String,
+        data: String,
+    },
+}
+
+/// WebSocket 连接事件，依次为 open、若干 message、close
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum WebSocketEvent {
+    Open {
+        status: u16,
+        headers: Vec<HttpHeader>,
+    },
+    Message(WebSocketMessage),
+    Close {
+        code: u16,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum WebSocketMessage {
+    Text(String),
+    Binary(Vec<u8>),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -189,9 +906,36 @@ pub struct HttpResponse {
     pub elapsed_time: i32,
 }
 
+/// 快捷键为 `KeySequence` 的字符串形式，如 `shift+control+Tab`、`control+k control+w`；
+/// `labels`、`default_labels` 为当前键盘布局下的显示名称，如 `Ctrl+K Ctrl+W`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HotkeyBinding {
+    pub action: String,
+    pub scope: String,
+    pub description: String,
+    pub category: String,
+    pub hotkeys: Vec<String>,
+    pub defaults: Vec<String>,
+    pub labels: Vec<String>,
+    pub default_labels: Vec<String>,
+}
+
 impl HttpRequest {
-    pub fn new(url: String, method: String, header: Vec<HttpHeader>, body: String) -> Self {
-        let options = if method.is_empty() && header.is_empty() && body.is_empty() {
+    pub fn new(
+        url: String,
+        method: String,
+        header: Vec<HttpHeader>,
+        body: Option<FetchBody>,
+        max_size: Option<u64>,
+        credential_id: Option<i64>,
+        request_id: Option<String>,
+    ) -> Self {
+        let options = if method.is_empty()
+            && header.is_empty()
+            && body.is_none()
+            && max_size.is_none()
+            && credential_id.is_none()
+        {
             None
         } else {
             Some(FetchOptions {
@@ -205,10 +949,16 @@ impl HttpRequest {
                 } else {
                     Some(header)
                 },
-                body: if body.is_empty() { None } else { Some(body) },
+                body,
+                max_size,
+                credential_id,
             })
         };
 
-        Self { url, options }
+        Self {
+            url,
+            options,
+            request_id,
+        }
     }
 }
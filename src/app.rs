@@ -4,7 +4,8 @@ use futures_util::StreamExt as _;
 use tauri_sys::event::listen;
 
 use crate::{
-    api::{BrowserState, get_state},
+    api::{BrowserState, DisplayUrl, get_state},
+    hotkey::{CheatSheet, use_keyboard_layout},
     http_client::{HttpClient, HttpClientGate},
     search_page::SearchPage,
     title_bar::TitleBar,
@@ -17,6 +18,7 @@ pub struct Browser {
     pub icon_url: Memo<String>,
     pub title: Memo<String>,
     pub url: Memo<String>,
+    pub display_url: Memo<Option<DisplayUrl>>,
     pub maximized: Memo<bool>,
     pub loading: Memo<bool>,
     pub can_back: Memo<bool>,
@@ -24,6 +26,8 @@ pub struct Browser {
     pub focus: Memo<bool>,
     pub incognito: Memo<bool>,
     pub darkreader: Memo<bool>,
+    pub capture: Memo<bool>,
+    pub vim: Memo<bool>,
     pub is_client: Memo<bool>,
 }
 
@@ -41,6 +45,7 @@ pub fn App() -> Element {
     let icon_url = use_memo(move || browser_state.read().icon_url.clone());
     let title = use_memo(move || browser_state.read().title.clone());
     let url = use_memo(move || browser_state.read().url.clone());
+    let display_url = use_memo(move || browser_state.read().display_url.clone());
     let maximized = use_memo(move || browser_state.read().maximized);
     let loading = use_memo(move || browser_state.read().loading);
     let can_back = use_memo(move || browser_state.read().can_back);
@@ -48,11 +53,14 @@ pub fn App() -> Element {
     let focus = use_memo(move || browser_state.read().focus);
     let incognito = use_memo(move || browser_state.read().incognito);
     let darkreader = use_memo(move || browser_state.read().darkreader);
+    let capture = use_memo(move || browser_state.read().capture);
+    let vim = use_memo(move || browser_state.read().vim);
     let is_client = use_memo(|| false);
     use_context_provider(|| Browser {
         icon_url,
         title,
         url,
+        display_url,
         maximized,
         loading,
         focus,
@@ -60,6 +68,8 @@ pub fn App() -> Element {
         can_forward,
         incognito,
         darkreader,
+        capture,
+        vim,
         is_client,
     });
 
@@ -88,11 +98,13 @@ pub fn App() -> Element {
 fn InnerApp() -> Element {
     let focus = use_browser().focus;
     let is_client = use_browser().is_client;
+    use_keyboard_layout();
 
     rsx! {
         document::Stylesheet { href: CSS }
 
         HttpClientGate {}
+        CheatSheet {}
 
         if focus() {
             if is_client() {
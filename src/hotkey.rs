@@ -0,0 +1,464 @@
+use std::{collections::HashMap, rc::Rc};
+
+use dioxus::{html::input_data::MouseButton, prelude::*};
+use futures_util::StreamExt as _;
+use tauri_sys::event::listen;
+
+use crate::{
+    api::{
+        HotkeyBinding, list_hotkeys, reset_hotkey_binding, set_hotkey_binding, set_hotkey_enabled,
+        set_keyboard_layout,
+    },
+    app::use_browser,
+};
+
+/// 处理函数的文档注释，没有时退回动作 id
+fn description(binding: &HotkeyBinding) -> &str {
+    if binding.description.is_empty() {
+        &binding.action
+    } else {
+        &binding.description
+    }
+}
+
+/// 作用域由后端按焦点与全屏状态切换，同一快捷键在内层作用域优先
+fn scope_label(scope: &str) -> &str {
+    match scope {
+        "global" => "全局",
+        "mainview" => "搜索视图",
+        "tab" => "标签页",
+        "fullscreen" => "全屏",
+        _ => scope,
+    }
+}
+
+/// 与后端 `Hotkey` 的字符串形式一致，修饰键顺序为 shift、control、alt、super；序列以空格分隔。
+/// 不按 Shift、Alt 时记录布局下输入的字符，快捷键随键盘布局落到对应按键上；否则字符会变，记录按键位置
+fn hotkey_string(e: &KeyboardEvent) -> Option<String> {
+    let code = e.code();
+    if matches!(
+        code,
+        Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::AltLeft
+            | Code::AltRight
+            | Code::MetaLeft
+            | Code::MetaRight
+    ) {
+        // 等待主键
+        return None;
+    }
+
+    let modifiers = e.modifiers();
+    let mut hotkey = modifier_prefix(modifiers);
+    match e.key() {
+        Key::Character(c)
+            if !modifiers.intersects(Modifiers::SHIFT | Modifiers::ALT)
+                && c.chars().count() == 1
+                && c != "+"
+                && !c.trim().is_empty() =>
+        {
+            hotkey.push_str(&c.to_lowercase());
+        }
+        _ => hotkey.push_str(&code.to_string()),
+    }
+    Some(hotkey)
+}
+
+/// 鼠标侧键，与后端 `MouseButton` 的字符串形式一致
+fn mouse_hotkey_string(e: &MouseEvent) -> Option<String> {
+    let button = match e.trigger_button()? {
+        MouseButton::Fourth => "MouseBack",
+        MouseButton::Fifth => "MouseForward",
+        _ => return None,
+    };
+    Some(modifier_prefix(e.modifiers()) + button)
+}
+
+/// 鼠标手势的笔画，与后端 `Direction` 的 `Gesture…` 字符串形式一致
+const GESTURE_STROKES: [(&str, &str); 4] = [
+    ("GestureUp", "↑"),
+    ("GestureDown", "↓"),
+    ("GestureLeft", "←"),
+    ("GestureRight", "→"),
+];
+
+fn is_gesture(hotkey: &str) -> bool {
+    hotkey.starts_with("Gesture")
+}
+
+fn modifier_prefix(modifiers: Modifiers) -> String {
+    let mut prefix = String::new();
+    for (modifier, name) in [
+        (Modifiers::SHIFT, "shift+"),
+        (Modifiers::CONTROL, "control+"),
+        (Modifiers::ALT, "alt+"),
+        (Modifiers::META, "super+"),
+    ] {
+        if modifiers.contains(modifier) {
+            prefix.push_str(name);
+        }
+    }
+    prefix
+}
+
+/// 读取当前键盘布局交给后端，按字符绑定的快捷键据此找到按键位置；主视图焦点变化时刷新，以便切换布局后生效
+pub fn use_keyboard_layout() {
+    let focus = use_browser().focus;
+    use_effect(move || {
+        focus();
+        spawn(async move {
+            let mut eval = document::eval(
+                r#"
+                const layout = navigator.keyboard ? await navigator.keyboard.getLayoutMap() : new Map();
+                dioxus.send(Object.fromEntries(layout));
+                "#,
+            );
+            if let Ok(layout) = eval.recv::<HashMap<String, String>>().await {
+                let _ = set_keyboard_layout(layout).await;
+            }
+        });
+    });
+}
+
+#[component]
+pub fn HotkeyMenu(open: Signal<bool>) -> Element {
+    rsx! {
+        li {
+            button {
+                class: "btn btn-ghost btn-block",
+                onclick: move |_| open.set(true),
+                "⌨️ 快捷键"
+            }
+        }
+    }
+}
+
+/// 列出所有动作及其快捷键，可录制新快捷键、解绑或恢复默认
+#[component]
+pub fn HotkeySettings(open: Signal<bool>) -> Element {
+    let mut bindings = use_resource(list_hotkeys);
+    let mut recording = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    let update = move |action: String, hotkeys: Vec<String>| async move {
+        match set_hotkey_binding(action, hotkeys).await {
+            Ok(()) => error.set(None),
+            Err(e) => error.set(Some(e.to_string())),
+        }
+        bindings.restart();
+    };
+
+    if !open() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "modal modal-open",
+            div { class: "modal-box max-w-2xl",
+                h3 { class: "font-bold text-lg", "快捷键" }
+                if let Some(e) = error() {
+                    div { class: "text-sm text-error", "{e}" }
+                }
+                match &*bindings.read() {
+                    Some(Ok(list)) => rsx! {
+                        table { class: "table table-sm",
+                            tbody {
+                                for binding in list.iter().cloned() {
+                                    BindingRow {
+                                        key: "{binding.action}",
+                                        binding,
+                                        recording,
+                                        onchange: move |(action, hotkeys)| update(action, hotkeys),
+                                        onreset: move |action| async move {
+                                            match reset_hotkey_binding(action).await {
+                                                Ok(()) => error.set(None),
+                                                Err(e) => error.set(Some(e.to_string())),
+                                            }
+                                            bindings.restart();
+                                        },
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-sm text-error", "{e}" }
+                    },
+                    None => rsx! {
+                        span { class: "loading loading-spinner" }
+                    },
+                }
+                div { class: "modal-action",
+                    button {
+                        class: "btn btn-sm",
+                        onclick: move |_| {
+                            recording.set(None);
+                            open.set(false);
+                        },
+                        "关闭"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BindingRow(
+    binding: HotkeyBinding,
+    recording: Signal<Option<String>>,
+    onchange: EventHandler<(String, Vec<String>)>,
+    onreset: EventHandler<String>,
+) -> Element {
+    let action = binding.action.clone();
+    let is_recording = recording.read().as_deref() == Some(action.as_str());
+    let defaults = binding.default_labels.join(", ");
+    let is_default = binding.hotkeys.len() == binding.defaults.len()
+        && binding
+            .hotkeys
+            .iter()
+            .all(|hotkey| binding.defaults.contains(hotkey));
+
+    rsx! {
+        tr {
+            td { class: "whitespace-nowrap", title: "{action}", {description(&binding)} }
+            td {
+                span { class: "badge badge-ghost badge-sm", {scope_label(&binding.scope)} }
+            }
+            td {
+                div { class: "flex flex-wrap gap-1 items-center",
+                    for (hotkey, label) in binding.hotkeys.iter().cloned().zip(binding.labels.iter().cloned()) {
+                        span {
+                            key: "{hotkey}",
+                            class: "badge badge-neutral gap-1 font-mono",
+                            title: "{hotkey}",
+                            "{label}"
+                            button {
+                                class: "cursor-pointer",
+                                title: "解绑",
+                                onclick: {
+                                    let action = action.clone();
+                                    let hotkeys: Vec<String> = binding
+                                        .hotkeys
+                                        .iter()
+                                        .filter(|h| **h != hotkey)
+                                        .cloned()
+                                        .collect();
+                                    move |_| onchange.call((action.clone(), hotkeys.clone()))
+                                },
+                                "✕"
+                            }
+                        }
+                    }
+                    if is_recording {
+                        HotkeyRecorder {
+                            onrecord: {
+                                let action = action.clone();
+                                let hotkeys = binding.hotkeys.clone();
+                                move |hotkey: String| {
+                                    recording.set(None);
+                                    if !hotkeys.contains(&hotkey) {
+                                        let mut hotkeys = hotkeys.clone();
+                                        hotkeys.push(hotkey);
+                                        onchange.call((action.clone(), hotkeys));
+                                    }
+                                }
+                            },
+                            oncancel: move |_| recording.set(None),
+                        }
+                    } else {
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            title: "添加快捷键",
+                            onclick: {
+                                let action = action.clone();
+                                move |_| recording.set(Some(action.clone()))
+                            },
+                            "+"
+                        }
+                    }
+                }
+            }
+            td { class: "text-right",
+                if !is_default {
+                    button {
+                        class: "btn btn-ghost btn-xs",
+                        title: "恢复默认：{defaults}",
+                        onclick: move |_| onreset.call(action.clone()),
+                        "恢复默认"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 聚焦期间暂停全局快捷键，可依次按下多个组合键或鼠标侧键作为序列，回车确认；
+/// 手势无法在输入框里画出，点击方向按钮逐笔录入，手势不与按键混在一个序列里
+#[component]
+fn HotkeyRecorder(onrecord: EventHandler<String>, oncancel: EventHandler<()>) -> Element {
+    let mut sequence = use_signal(Vec::<String>::new);
+    use_drop(|| {
+        spawn_forever(async {
+            let _ = set_hotkey_enabled(true).await;
+        });
+    });
+
+    let onmounted = move |e: MountedEvent| async move {
+        let element: Rc<MountedData> = e.data();
+        let _ = element.set_focus(true).await;
+    };
+
+    let mut push = move |hotkey: String| {
+        let mut sequence = sequence.write();
+        if sequence
+            .last()
+            .is_some_and(|last| is_gesture(last) != is_gesture(&hotkey))
+        {
+            sequence.clear();
+        }
+        // 页面脚本会合并同方向的连续移动，重复笔画永远匹配不到
+        if !(is_gesture(&hotkey) && sequence.last() == Some(&hotkey)) {
+            sequence.push(hotkey);
+        }
+    };
+
+    rsx! {
+        input {
+            r#type: "text",
+            readonly: true,
+            value: sequence.read().join(" "),
+            class: "input input-xs w-48 font-mono",
+            placeholder: "按下快捷键或点击手势方向，回车确认",
+            onmounted,
+            onfocus: |_| async {
+                let _ = set_hotkey_enabled(false).await;
+            },
+            onblur: move |_| oncancel.call(()),
+            onkeydown: move |e: KeyboardEvent| {
+                e.prevent_default();
+                e.stop_propagation();
+                let confirm = e.key() == Key::Enter && e.modifiers().is_empty();
+                if confirm && !sequence.read().is_empty() {
+                    onrecord.call(sequence.read().join(" "));
+                } else if let Some(hotkey) = hotkey_string(&e) {
+                    push(hotkey);
+                }
+            },
+            onmouseup: move |e: MouseEvent| {
+                if let Some(hotkey) = mouse_hotkey_string(&e) {
+                    e.prevent_default();
+                    push(hotkey);
+                }
+            },
+        }
+        for (stroke, arrow) in GESTURE_STROKES {
+            button {
+                key: "{stroke}",
+                class: "btn btn-ghost btn-xs",
+                title: "手势 {arrow}",
+                // 保持输入框焦点，否则失焦即取消录制
+                onmousedown: |e: MouseEvent| e.prevent_default(),
+                onclick: move |_| push(stroke.to_string()),
+                "{arrow}"
+            }
+        }
+    }
+}
+
+/// 按分类列出所有快捷键，由 Ctrl+/ 唤出，离开主视图时关闭
+#[component]
+pub fn CheatSheet() -> Element {
+    let focus = use_browser().focus;
+    let mut open = use_signal(|| false);
+    let mut hotkeys = use_resource(list_hotkeys);
+
+    use_hook(|| {
+        spawn(async move {
+            let Ok(mut events) = listen::<()>("cheat-sheet").await else {
+                return;
+            };
+            while events.next().await.is_some() {
+                hotkeys.restart();
+                let opened = open();
+                open.set(!opened);
+            }
+        })
+    });
+
+    use_effect(move || {
+        if !focus() {
+            open.set(false);
+        }
+    });
+
+    if !open() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "modal modal-open",
+            div { class: "modal-box max-w-2xl",
+                h3 { class: "font-bold text-lg", "快捷键速查" }
+                match &*hotkeys.read() {
+                    Some(Ok(list)) => rsx! {
+                        for (category, bindings) in group_by_category(list) {
+                            div { key: "{category}",
+                                h4 { class: "font-semibold mt-3 mb-1",
+                                    if category.is_empty() {
+                                        "其他"
+                                    } else {
+                                        "{category}"
+                                    }
+                                }
+                                table { class: "table table-xs",
+                                    tbody {
+                                        for binding in bindings {
+                                            tr { key: "{binding.action}",
+                                                td { {description(&binding)} }
+                                                td { class: "text-right",
+                                                    for label in binding.labels.iter() {
+                                                        kbd { key: "{label}", class: "kbd kbd-sm ml-1",
+                                                            "{label}"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-sm text-error", "{e}" }
+                    },
+                    None => rsx! {
+                        span { class: "loading loading-spinner" }
+                    },
+                }
+                div { class: "modal-action",
+                    button { class: "btn btn-sm", onclick: move |_| open.set(false), "关闭" }
+                }
+            }
+            div { class: "modal-backdrop", onclick: move |_| open.set(false) }
+        }
+    }
+}
+
+/// 后端已按分类排序，相邻的同类动作归为一组
+fn group_by_category(list: &[HotkeyBinding]) -> Vec<(String, Vec<HotkeyBinding>)> {
+    let mut groups: Vec<(String, Vec<HotkeyBinding>)> = Vec::new();
+    for binding in list {
+        match groups.last_mut() {
+            Some((category, bindings)) if *category == binding.category => {
+                bindings.push(binding.clone());
+            }
+            _ => groups.push((binding.category.clone(), vec![binding.clone()])),
+        }
+    }
+    groups
+}
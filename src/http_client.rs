@@ -1,23 +1,47 @@
+mod assertion;
+mod auth;
 mod body;
+mod capture;
+mod curl;
+mod environment;
+mod har;
 mod header;
-// mod log;
+mod json_tree;
+mod log;
 mod method;
+mod preview;
 mod response;
 mod send;
+mod stream;
 mod uri;
 
 use crate::{
-    api::{HttpRequest, fetch},
+    api::{HarSource, HttpRequest, cancel_fetch, fetch, replay_request_log, replay_saved_request},
     app::use_browser,
 };
-use body::BodyArea;
+use assertion::{AssertionEditor, AssertionResults};
+use auth::AuthSelect;
+use body::BodyEditor;
+use curl::CurlMenu;
 use dioxus::prelude::*;
+use environment::{CaptureForm, EnvironmentSelect};
+use har::HarMenu;
 use header::HeaderTable;
+use log::{Replay, RequestDraft, RequestSidebar};
 use method::MethodSelect;
 use response::ResponseView;
 use send::SendButton;
+use stream::{ClientMode, SsePanel, WebSocketPanel};
 use uri::UriInput;
 
+/// 响应体大小上限，避免大文件撑爆界面
+const MAX_BODY_SIZE: u64 = 64 * 1024 * 1024;
+
+enum ClientAction {
+    Fetch(HttpRequest),
+    Replay(Replay, String),
+}
+
 #[component]
 pub fn HttpClientGate() -> Element {
     let mut is_client = use_browser().is_client;
@@ -42,76 +66,198 @@ pub fn HttpClientGate() -> Element {
 
 #[component]
 pub fn HttpClient() -> Element {
-    let method_value = use_signal(String::new);
-    let uri_value = use_signal(String::new);
-    let body_value = use_signal(String::new);
-    let header_value = use_store(Vec::new);
+    let mut method_value = use_signal(String::new);
+    let mut uri_value = use_signal(String::new);
+    let mut body_value = use_signal(|| None);
+    let mut credential_value = use_signal(|| None);
+    let mut header_value = use_store(Vec::new);
+    let mut assertion_value = use_signal(Vec::new);
+    let mut capture_value = use_signal(Vec::new);
     let body_editable = use_memo(move || {
         let method = method_value();
         "PATCH" == method || "POST" == method || "PUT" == method
     });
 
-    let mut resp = use_action(move |req| async move { fetch(req).await });
-    let pending = use_signal(|| false);
+    let draft = use_memo(move || RequestDraft {
+        method: method_value(),
+        url: uri_value(),
+        headers: header_value(),
+        body: body_value(),
+        credential_id: credential_value(),
+        assertions: assertion_value(),
+        captures: capture_value(),
+    });
+
+    let mut mode = use_signal(|| ClientMode::Http);
+    let mut pending = use_signal(|| false);
+    let mut history_version = use_signal(|| 0u32);
+    let mut environment_version = use_signal(|| 0u32);
+    let mut resp = use_action(move |action| async move {
+        let result = match action {
+            ClientAction::Fetch(req) => fetch(req).await,
+            ClientAction::Replay(Replay::Log(id), request_id) => {
+                replay_request_log(id, request_id).await
+            }
+            ClientAction::Replay(Replay::Saved(id), request_id) => {
+                replay_saved_request(id, request_id).await
+            }
+        };
+        pending.set(false);
+        history_version += 1;
+        result
+    });
+    let mut request_seq = use_signal(|| 0u32);
+    let request_id = use_memo(move || format!("http-client-{}", request_seq()));
 
     let on_submit = move |_| {
-        resp.call(HttpRequest::new(
+        request_seq += 1;
+        pending.set(true);
+        resp.call(ClientAction::Fetch(HttpRequest::new(
             uri_value(),
             method_value(),
             header_value(),
             body_value(),
-        ))
+            Some(MAX_BODY_SIZE),
+            credential_value(),
+            Some(request_id()),
+        )))
+    };
+
+    let on_load = move |draft: RequestDraft| {
+        method_value.set(draft.method);
+        uri_value.set(draft.url);
+        *header_value.write() = draft.headers;
+        body_value.set(draft.body);
+        credential_value.set(draft.credential_id);
+        assertion_value.set(draft.assertions);
+        capture_value.set(draft.captures);
+    };
+
+    let on_replay = move |replay: Replay| {
+        request_seq += 1;
+        pending.set(true);
+        resp.call(ClientAction::Replay(replay, request_id()));
+    };
+
+    let on_cancel = move |_| {
+        spawn(async move {
+            let _ = cancel_fetch(request_id()).await;
+        });
     };
 
     rsx! {
-        div { class: "grid grid-cols-2 gap-4",
+        div { class: "grid grid-cols-[16rem_1fr_1fr] gap-4",
+            RequestSidebar {
+                class: "p-4 max-h-screen",
+                refresh: history_version,
+                draft,
+                onload: on_load,
+                onreplay: on_replay,
+            }
             div { class: "p-4 min-h-screen",
                 div { class: "join join-vertical h-full w-full",
+                    div { class: "flex gap-2 w-full join-item",
+                        EnvironmentSelect { refresh: environment_version, class: "w-1/2" }
+                        AuthSelect { value: credential_value, class: "w-1/2" }
+                    }
+                    div { class: "my-1" }
+                    div { role: "tablist", class: "tabs tabs-box tabs-sm join-item",
+                        for (value , label) in ClientMode::ALL {
+                            a {
+                                role: "tab",
+                                class: if mode() == value { "tab tab-active" } else { "tab" },
+                                onclick: move |_| mode.set(value),
+                                "{label}"
+                            }
+                        }
+                    }
+                    div { class: "my-1" }
                     div { class: "join w-full join-item",
-                        MethodSelect { value: method_value, class: "join-item" }
+                        if mode() == ClientMode::Http {
+                            MethodSelect { value: method_value, class: "join-item" }
+                        }
                         UriInput { value: uri_value, class: "w-full join-item" }
-                        SendButton { onclick: on_submit, class: "join-item" }
+                        if mode() == ClientMode::Http {
+                            SendButton { onclick: on_submit, class: "join-item" }
+                        }
+                        CurlMenu { draft, onimport: on_load, class: "join-item" }
                     }
                     div { class: "my-1" }
                     HeaderTable { rows: header_value, class: "w-full join-item" }
                     div { class: "my-1" }
-                    BodyArea {
+                    BodyEditor {
                         value: body_value,
-                        contenteditable: body_editable(),
+                        disabled: !body_editable(),
                         class: "h-full w-full join-item",
                     }
+                    div { class: "my-1" }
+                    AssertionEditor {
+                        assertions: assertion_value,
+                        captures: capture_value,
+                        class: "w-full join-item",
+                    }
                 }
             }
-            if pending() {
-                div { class: "flex min-h-screen",
-                    div { class: "m-auto",
-                        span { class: "loading loading-infinity loading-lg" }
+            // 切换模式时保留连接和消息记录
+            div { class: if mode() == ClientMode::WebSocket { "p-4 max-h-screen" } else { "hidden" },
+                WebSocketPanel { draft }
+            }
+            div { class: if mode() == ClientMode::Sse { "p-4 max-h-screen" } else { "hidden" },
+                SsePanel { draft }
+            }
+            if mode() == ClientMode::Http {
+                if pending() {
+                    div { class: "flex min-h-screen",
+                        div { class: "m-auto flex flex-col items-center gap-4",
+                            span { class: "loading loading-infinity loading-lg" }
+                            button { class: "btn btn-ghost btn-sm", onclick: on_cancel, "取消请求" }
+                        }
                     }
-                }
-            } else {
-                div { class: "p-4",
-                    match resp.value() {
-                        Some(Ok(rr)) => rsx! {
-                            ResponseView { resp: rr }
-                        },
-                        Some(Err(e)) => rsx! {
-                            div { role: "alert", class: "alert alert-error",
-                                svg {
-                                    xmlns: "http://www.w3.org/2000/svg",
-                                    class: "h-6 w-6 shrink-0 stroke-current",
-                                    fill: "none",
-                                    view_box: "0 0 24 24",
-                                    path {
-                                        stroke_linecap: "round",
-                                        stroke_linejoin: "round",
-                                        stroke_width: "2",
-                                        d: "M10 14l2-2m0 0l2-2m-2 2l-2-2m2 2l2 2m7-2a9 9 0 11-18 0 9 9 0 0118 0z",
+                } else {
+                    div { class: "p-4",
+                        match resp.value() {
+                            Some(Ok(rr)) => {
+                                let request = HttpRequest::from(&draft());
+                                let source = HarSource::Request {
+                                    url: request.url,
+                                    options: request.options,
+                                    response: Some(rr.read().clone()),
+                                };
+                                rsx! {
+                                    div { class: "flex gap-2 items-start mb-2",
+                                        CaptureForm {
+                                            body: rr.read().body.clone(),
+                                            oncapture: move |_| environment_version += 1,
+                                            class: "grow",
+                                        }
+                                        HarMenu { source }
                                     }
+                                    AssertionResults {
+                                        assertions: assertion_value,
+                                        resp: rr.read().clone(),
+                                    }
+                                    ResponseView { resp: rr }
                                 }
-                                span { "{e}" }
                             }
-                        },
-                        None => rsx! {},
+                            Some(Err(e)) => rsx! {
+                                div { role: "alert", class: "alert alert-error",
+                                    svg {
+                                        xmlns: "http://www.w3.org/2000/svg",
+                                        class: "h-6 w-6 shrink-0 stroke-current",
+                                        fill: "none",
+                                        view_box: "0 0 24 24",
+                                        path {
+                                            stroke_linecap: "round",
+                                            stroke_linejoin: "round",
+                                            stroke_width: "2",
+                                            d: "M10 14l2-2m0 0l2-2m-2 2l-2-2m2 2l2 2m7-2a9 9 0 11-18 0 9 9 0 0118 0z",
+                                        }
+                                    }
+                                    span { "{e}" }
+                                }
+                            },
+                            None => rsx! {},
+                        }
                     }
                 }
             }
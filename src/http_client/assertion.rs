@@ -0,0 +1,344 @@
+use dioxus::prelude::*;
+
+use crate::api::{
+    Assertion, AssertionResult, Capture, HttpResponse, RunReport, check_assertions, run_collection,
+    save_response_body,
+};
+
+const ASSERTION_KINDS: [&str; 4] = ["状态码", "响应头", "JSONPath", "响应时间"];
+
+fn new_assertion(kind: &str) -> Assertion {
+    match kind {
+        "响应头" => Assertion::Header {
+            name: String::new(),
+            contains: String::new(),
+        },
+        "JSONPath" => Assertion::JsonPath {
+            path: "$.".to_string(),
+            equals: None,
+        },
+        "响应时间" => Assertion::ResponseTime { under: 1000 },
+        _ => Assertion::Status { equals: 200 },
+    }
+}
+
+/// 编辑保存到集合的断言和变量提取，发送后立即校验断言
+#[component]
+pub fn AssertionEditor(
+    #[props(default)] class: String,
+    assertions: Signal<Vec<Assertion>>,
+    captures: Signal<Vec<Capture>>,
+) -> Element {
+    let count = assertions.read().len() + captures.read().len();
+
+    rsx! {
+        details { class: "collapse collapse-arrow bg-base-200 {class}",
+            summary { class: "collapse-title text-sm min-h-0 py-2", "断言与变量提取（{count}）" }
+            div { class: "collapse-content flex flex-col gap-1",
+                for (i , assertion) in assertions().into_iter().enumerate() {
+                    div { key: "assertion-{i}", class: "join w-full",
+                        AssertionInput {
+                            assertion,
+                            onchange: move |assertion| assertions.write()[i] = assertion,
+                        }
+                        button {
+                            class: "btn btn-sm btn-ghost join-item",
+                            onclick: move |_| {
+                                assertions.write().remove(i);
+                            },
+                            "✕"
+                        }
+                    }
+                }
+                for (i , capture) in captures().into_iter().enumerate() {
+                    div { key: "capture-{i}", class: "join w-full",
+                        span { class: "btn btn-sm join-item no-animation", "提取" }
+                        input {
+                            value: "{capture.path}",
+                            r#type: "text",
+                            class: "input input-sm join-item w-full font-mono",
+                            placeholder: "$.data.token",
+                            oninput: move |e| captures.write()[i].path = e.value(),
+                        }
+                        input {
+                            value: "{capture.key}",
+                            r#type: "text",
+                            class: "input input-sm join-item w-1/3",
+                            placeholder: "变量名",
+                            oninput: move |e| captures.write()[i].key = e.value(),
+                        }
+                        button {
+                            class: "btn btn-sm btn-ghost join-item",
+                            onclick: move |_| {
+                                captures.write().remove(i);
+                            },
+                            "✕"
+                        }
+                    }
+                }
+                div { class: "flex gap-1",
+                    for kind in ASSERTION_KINDS {
+                        button {
+                            class: "btn btn-xs",
+                            onclick: move |_| assertions.write().push(new_assertion(kind)),
+                            "+ {kind}"
+                        }
+                    }
+                    button {
+                        class: "btn btn-xs",
+                        onclick: move |_| captures.write().push(Capture::default()),
+                        "+ 提取变量"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AssertionInput(assertion: Assertion, onchange: EventHandler<Assertion>) -> Element {
+    let input_class = "input input-sm join-item w-full";
+    match assertion {
+        Assertion::Status { equals } => rsx! {
+            span { class: "btn btn-sm join-item no-animation", "状态码 =" }
+            input {
+                value: "{equals}",
+                r#type: "number",
+                class: input_class,
+                oninput: move |e| {
+                    if let Ok(equals) = e.value().parse() {
+                        onchange.call(Assertion::Status { equals });
+                    }
+                },
+            }
+        },
+        Assertion::Header { name, contains } => rsx! {
+            span { class: "btn btn-sm join-item no-animation", "响应头" }
+            input {
+                value: "{name}",
+                r#type: "text",
+                class: input_class,
+                placeholder: "Content-Type",
+                oninput: {
+                    let contains = contains.clone();
+                    move |e: FormEvent| {
+                        onchange.call(Assertion::Header {
+                            name: e.value(),
+                            contains: contains.clone(),
+                        })
+                    }
+                },
+            }
+            input {
+                value: "{contains}",
+                r#type: "text",
+                class: input_class,
+                placeholder: "包含",
+                oninput: move |e| {
+                    onchange.call(Assertion::Header {
+                        name: name.clone(),
+                        contains: e.value(),
+                    })
+                },
+            }
+        },
+        Assertion::JsonPath { path, equals } => rsx! {
+            span { class: "btn btn-sm join-item no-animation", "JSONPath" }
+            input {
+                value: "{path}",
+                r#type: "text",
+                class: "{input_class} font-mono",
+                placeholder: "$.data.id",
+                oninput: {
+                    let equals = equals.clone();
+                    move |e: FormEvent| {
+                        onchange.call(Assertion::JsonPath {
+                            path: e.value(),
+                            equals: equals.clone(),
+                        })
+                    }
+                },
+            }
+            input {
+                value: equals.clone().unwrap_or_default(),
+                r#type: "text",
+                class: input_class,
+                placeholder: "等于（留空表示存在）",
+                oninput: move |e| {
+                    onchange.call(Assertion::JsonPath {
+                        path: path.clone(),
+                        equals: Some(e.value()).filter(|value| !value.is_empty()),
+                    })
+                },
+            }
+        },
+        Assertion::ResponseTime { under } => rsx! {
+            span { class: "btn btn-sm join-item no-animation", "响应时间 <" }
+            input {
+                value: "{under}",
+                r#type: "number",
+                class: input_class,
+                oninput: move |e| {
+                    if let Ok(under) = e.value().parse() {
+                        onchange.call(Assertion::ResponseTime { under });
+                    }
+                },
+            }
+            span { class: "btn btn-sm join-item no-animation", "ms" }
+        },
+    }
+}
+
+/// 发送后校验编辑器中的断言
+#[component]
+pub fn AssertionResults(assertions: ReadSignal<Vec<Assertion>>, resp: HttpResponse) -> Element {
+    let results = use_resource(use_reactive!(|resp| async move {
+        let assertions = assertions();
+        if assertions.is_empty() {
+            return Ok(Vec::new());
+        }
+        check_assertions(assertions, resp).await
+    }));
+
+    match &*results.read() {
+        Some(Ok(results)) if !results.is_empty() => rsx! {
+            ResultList { results: results.clone() }
+        },
+        Some(Err(e)) => rsx! {
+            div { class: "text-xs text-error", "{e}" }
+        },
+        _ => rsx! {},
+    }
+}
+
+#[component]
+fn ResultList(results: Vec<AssertionResult>) -> Element {
+    rsx! {
+        ul { class: "text-xs",
+            for (i , result) in results.into_iter().enumerate() {
+                li {
+                    key: "{i}",
+                    class: if result.passed { "text-success" } else { "text-error" },
+                    if result.passed {
+                        "✓ "
+                    } else {
+                        "✗ "
+                    }
+                    "{result.message}"
+                }
+            }
+        }
+    }
+}
+
+/// 运行集合并展示报告，可导出 JUnit XML 到下载目录
+#[component]
+pub fn RunCollectionMenu(id: i64) -> Element {
+    let mut running = use_signal(|| false);
+    let mut report = use_signal(|| None::<Result<RunReport, String>>);
+    let mut saved = use_signal(|| None::<Result<String, String>>);
+
+    let run = move |_| async move {
+        running.set(true);
+        saved.set(None);
+        let result = run_collection(id).await.map_err(|e| e.to_string());
+        report.set(Some(result));
+        running.set(false);
+    };
+
+    let export = move |_| async move {
+        let Some(Ok(report)) = report() else {
+            return;
+        };
+        let file_name = format!("{}.xml", report.name);
+        let result = save_response_body(file_name, report.junit.into_bytes())
+            .await
+            .map(|path| format!("已导出：{path}"));
+        saved.set(Some(result.map_err(|e| e.to_string())));
+    };
+
+    rsx! {
+        div { class: "dropdown dropdown-end",
+            div {
+                tabindex: "0",
+                role: "button",
+                class: "btn btn-ghost btn-xs",
+                title: "运行集合",
+                onclick: move |e| e.prevent_default(),
+                "▶"
+            }
+            div {
+                tabindex: "0",
+                class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-96 p-2 flex flex-col gap-1",
+                button {
+                    class: "btn btn-sm btn-primary w-full",
+                    disabled: running(),
+                    onclick: run,
+                    if running() {
+                        span { class: "loading loading-spinner loading-xs" }
+                    }
+                    "依次运行全部请求"
+                }
+                match report() {
+                    Some(Ok(report)) => rsx! {
+                        div { class: "flex items-center gap-2 text-sm",
+                            span { class: "text-success", "通过 {report.passed}" }
+                            span { class: "text-error", "失败 {report.failed}" }
+                            span { class: "opacity-60", "{report.elapsed_time} ms" }
+                            button { class: "btn btn-xs ml-auto", onclick: export, "导出 JUnit" }
+                        }
+                        ul { class: "list max-h-96 overflow-y-auto",
+                            for (i , result) in report.results.into_iter().enumerate() {
+                                li { key: "{i}", class: "list-row p-1 block",
+                                    div { class: "flex gap-2 items-center text-sm",
+                                        span { class: if result.passed { "text-success" } else { "text-error" },
+                                            if result.passed {
+                                                "✓"
+                                            } else {
+                                                "✗"
+                                            }
+                                        }
+                                        span { class: "truncate", title: "{result.url}",
+                                            "{result.method} {result.name}"
+                                        }
+                                        span { class: "ml-auto opacity-60 whitespace-nowrap",
+                                            if let Some(status) = result.status {
+                                                "{status} "
+                                            }
+                                            if let Some(elapsed_time) = result.elapsed_time {
+                                                "{elapsed_time} ms"
+                                            }
+                                        }
+                                    }
+                                    if let Some(error) = result.error {
+                                        div { class: "text-xs text-error whitespace-pre-wrap", "{error}" }
+                                    }
+                                    ResultList {
+                                        results: result
+                                            .assertions
+                                            .into_iter()
+                                            .filter(|result| !result.passed)
+                                            .collect::<Vec<_>>(),
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-xs text-error", "{e}" }
+                    },
+                    None => rsx! {},
+                }
+                match saved() {
+                    Some(Ok(message)) => rsx! {
+                        div { class: "text-xs text-success break-all", "{message}" }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-xs text-error", "{e}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
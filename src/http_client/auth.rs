@@ -0,0 +1,362 @@
+use dioxus::prelude::*;
+
+use crate::api::{
+    ApiKeyLocation, Auth, Credential, OAuth2Grant, delete_credential, query_credentials,
+    save_credential,
+};
+
+const AUTH_TYPES: [(&str, &str); 4] = [
+    ("basic", "Basic"),
+    ("bearer", "Bearer"),
+    ("api_key", "API Key"),
+    ("oauth2", "OAuth2"),
+];
+
+impl Auth {
+    fn kind(&self) -> &'static str {
+        match self {
+            Auth::Basic { .. } => "basic",
+            Auth::Bearer { .. } => "bearer",
+            Auth::ApiKey { .. } => "api_key",
+            Auth::OAuth2 { .. } => "oauth2",
+        }
+    }
+
+    fn of_kind(kind: &str) -> Self {
+        match kind {
+            "bearer" => Auth::Bearer { token: None },
+            "api_key" => Auth::ApiKey {
+                key: "X-API-Key".to_string(),
+                value: None,
+                location: ApiKeyLocation::Header,
+            },
+            "oauth2" => Auth::OAuth2 {
+                grant: OAuth2Grant::ClientCredentials,
+                token_url: String::new(),
+                client_id: String::new(),
+                client_secret: None,
+                username: String::new(),
+                password: None,
+                scope: String::new(),
+            },
+            _ => Auth::Basic {
+                username: String::new(),
+                password: None,
+            },
+        }
+    }
+}
+
+/// 请求引用的认证凭据，密钥保存在后端，由后端在发送时注入
+#[component]
+pub fn AuthSelect(#[props(default)] class: String, value: Signal<Option<i64>>) -> Element {
+    let mut credentials =
+        use_resource(|| async { query_credentials().await.unwrap_or_default() });
+    let all = credentials().unwrap_or_default();
+    let selected = all
+        .iter()
+        .find(|credential| credential.id == value())
+        .cloned();
+
+    rsx! {
+        div { class: "join {class}",
+            select {
+                class: "select select-sm join-item w-full",
+                onchange: move |e| value.set(e.value().parse().ok()),
+                option { value: "", selected: value().is_none(), "无认证" }
+                for credential in all.iter() {
+                    option {
+                        key: "{credential.id.unwrap_or_default()}",
+                        value: "{credential.id.unwrap_or_default()}",
+                        selected: credential.id == value(),
+                        "{credential.name}"
+                    }
+                }
+            }
+            div { class: "dropdown dropdown-end join-item",
+                div { tabindex: "0", role: "button", class: "btn btn-sm", title: "编辑认证", "🔑" }
+                div {
+                    tabindex: "0",
+                    class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-[28rem] p-4 flex flex-col gap-2",
+                    CredentialEditor {
+                        key: "{value():?}-{selected.is_some()}",
+                        credential: selected,
+                        onsave: move |id| {
+                            value.set(Some(id));
+                            credentials.restart();
+                        },
+                        ondelete: move |_| {
+                            value.set(None);
+                            credentials.restart();
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 未选择凭据时新建，私密字段留空保存时保持原值
+#[component]
+fn CredentialEditor(
+    credential: Option<Credential>,
+    onsave: EventHandler<i64>,
+    ondelete: EventHandler<()>,
+) -> Element {
+    let id = credential.as_ref().and_then(|credential| credential.id);
+    let mut name = use_signal(|| {
+        credential
+            .as_ref()
+            .map(|credential| credential.name.clone())
+            .unwrap_or_default()
+    });
+    let mut auth = use_signal(|| {
+        credential
+            .as_ref()
+            .map(|credential| credential.auth.clone())
+            .unwrap_or_else(|| Auth::of_kind("basic"))
+    });
+    let kind = auth.read().kind();
+
+    let save = move |_| async move {
+        let credential = Credential {
+            id,
+            name: name(),
+            auth: auth(),
+        };
+        if let Ok(id) = save_credential(credential).await {
+            onsave.call(id);
+        }
+    };
+
+    rsx! {
+        input {
+            value: name,
+            r#type: "text",
+            class: "input input-sm w-full",
+            placeholder: "凭据名称",
+            oninput: move |e| name.set(e.value()),
+        }
+        select {
+            class: "select select-sm w-full",
+            onchange: move |e| auth.set(Auth::of_kind(&e.value())),
+            for (value , label) in AUTH_TYPES {
+                option { value, selected: value == kind, "{label}" }
+            }
+        }
+        match auth() {
+            Auth::Basic { username, password } => rsx! {
+                TextField {
+                    label: "用户名",
+                    value: username,
+                    oninput: move |value| {
+                        if let Auth::Basic { username, .. } = &mut *auth.write() {
+                            *username = value;
+                        }
+                    },
+                }
+                SecretField {
+                    label: "密码",
+                    value: password,
+                    saved: id.is_some(),
+                    oninput: move |value| {
+                        if let Auth::Basic { password, .. } = &mut *auth.write() {
+                            *password = value;
+                        }
+                    },
+                }
+            },
+            Auth::Bearer { token } => rsx! {
+                SecretField {
+                    label: "Token",
+                    value: token,
+                    saved: id.is_some(),
+                    oninput: move |value| {
+                        if let Auth::Bearer { token } = &mut *auth.write() {
+                            *token = value;
+                        }
+                    },
+                }
+            },
+            Auth::ApiKey { key, value, location } => rsx! {
+                TextField {
+                    label: "名称",
+                    value: key,
+                    oninput: move |value| {
+                        if let Auth::ApiKey { key, .. } = &mut *auth.write() {
+                            *key = value;
+                        }
+                    },
+                }
+                SecretField {
+                    label: "值",
+                    value,
+                    saved: id.is_some(),
+                    oninput: move |new_value| {
+                        if let Auth::ApiKey { value, .. } = &mut *auth.write() {
+                            *value = new_value;
+                        }
+                    },
+                }
+                select {
+                    class: "select select-sm w-full",
+                    onchange: move |e| {
+                        if let Auth::ApiKey { location, .. } = &mut *auth.write() {
+                            *location = if e.value() == "query" {
+                                ApiKeyLocation::Query
+                            } else {
+                                ApiKeyLocation::Header
+                            };
+                        }
+                    },
+                    option {
+                        value: "header",
+                        selected: location == ApiKeyLocation::Header,
+                        "放在请求头"
+                    }
+                    option {
+                        value: "query",
+                        selected: location == ApiKeyLocation::Query,
+                        "放在查询参数"
+                    }
+                }
+            },
+            Auth::OAuth2 { grant, token_url, client_id, client_secret, username, password, scope } => rsx! {
+                select {
+                    class: "select select-sm w-full",
+                    onchange: move |e| {
+                        if let Auth::OAuth2 { grant, .. } = &mut *auth.write() {
+                            *grant = if e.value() == "password" {
+                                OAuth2Grant::Password
+                            } else {
+                                OAuth2Grant::ClientCredentials
+                            };
+                        }
+                    },
+                    option {
+                        value: "client_credentials",
+                        selected: grant == OAuth2Grant::ClientCredentials,
+                        "Client Credentials"
+                    }
+                    option {
+                        value: "password",
+                        selected: grant == OAuth2Grant::Password,
+                        "Password"
+                    }
+                }
+                TextField {
+                    label: "Token URL",
+                    value: token_url,
+                    oninput: move |value| {
+                        if let Auth::OAuth2 { token_url, .. } = &mut *auth.write() {
+                            *token_url = value;
+                        }
+                    },
+                }
+                TextField {
+                    label: "Client ID",
+                    value: client_id,
+                    oninput: move |value| {
+                        if let Auth::OAuth2 { client_id, .. } = &mut *auth.write() {
+                            *client_id = value;
+                        }
+                    },
+                }
+                SecretField {
+                    label: "Client Secret",
+                    value: client_secret,
+                    saved: id.is_some(),
+                    oninput: move |value| {
+                        if let Auth::OAuth2 { client_secret, .. } = &mut *auth.write() {
+                            *client_secret = value;
+                        }
+                    },
+                }
+                if grant == OAuth2Grant::Password {
+                    TextField {
+                        label: "用户名",
+                        value: username,
+                        oninput: move |value| {
+                            if let Auth::OAuth2 { username, .. } = &mut *auth.write() {
+                                *username = value;
+                            }
+                        },
+                    }
+                    SecretField {
+                        label: "密码",
+                        value: password,
+                        saved: id.is_some(),
+                        oninput: move |value| {
+                            if let Auth::OAuth2 { password, .. } = &mut *auth.write() {
+                                *password = value;
+                            }
+                        },
+                    }
+                }
+                TextField {
+                    label: "Scope",
+                    value: scope,
+                    oninput: move |value| {
+                        if let Auth::OAuth2 { scope, .. } = &mut *auth.write() {
+                            *scope = value;
+                        }
+                    },
+                }
+            },
+        }
+        div { class: "flex gap-2 justify-end",
+            if let Some(id) = id {
+                button {
+                    class: "btn btn-error btn-outline btn-xs",
+                    onclick: move |_| async move {
+                        if delete_credential(id).await.is_ok() {
+                            ondelete.call(());
+                        }
+                    },
+                    "删除"
+                }
+            }
+            button {
+                class: "btn btn-primary btn-xs",
+                disabled: name().is_empty(),
+                onclick: save,
+                if id.is_some() { "保存" } else { "新建凭据" }
+            }
+        }
+    }
+}
+
+#[component]
+fn TextField(label: String, value: String, oninput: EventHandler<String>) -> Element {
+    rsx! {
+        label { class: "input input-sm w-full",
+            span { class: "label", "{label}" }
+            input {
+                value: "{value}",
+                r#type: "text",
+                oninput: move |e| oninput.call(e.value()),
+            }
+        }
+    }
+}
+
+/// 已保存的私密字段不回显，留空表示保持原值
+#[component]
+fn SecretField(
+    label: String,
+    value: Option<String>,
+    saved: bool,
+    oninput: EventHandler<Option<String>>,
+) -> Element {
+    rsx! {
+        label { class: "input input-sm w-full",
+            span { class: "label", "{label}" }
+            input {
+                value: value.unwrap_or_default(),
+                r#type: "password",
+                placeholder: if saved { "••••••" } else { "" },
+                oninput: move |e| oninput.call(Some(e.value()).filter(|value| !value.is_empty())),
+            }
+        }
+    }
+}
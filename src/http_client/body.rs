@@ -1,20 +1,225 @@
 use dioxus::prelude::*;
+use serde_json::Value;
+
+use crate::api::{FetchBody, FormField};
+
+#[derive(Clone, Copy, PartialEq)]
+enum BodyMode {
+    None,
+    Raw,
+    Json,
+    Form,
+    Multipart,
+}
+
+impl BodyMode {
+    const ALL: [(BodyMode, &str); 5] = [
+        (BodyMode::None, "无"),
+        (BodyMode::Raw, "文本"),
+        (BodyMode::Json, "JSON"),
+        (BodyMode::Form, "表单"),
+        (BodyMode::Multipart, "Multipart"),
+    ];
+
+    fn of(body: &Option<FetchBody>) -> Self {
+        match body {
+            None => BodyMode::None,
+            Some(FetchBody::Json { .. }) => BodyMode::Json,
+            Some(FetchBody::Form { .. }) => BodyMode::Form,
+            Some(FetchBody::Multipart { .. }) => BodyMode::Multipart,
+            Some(_) => BodyMode::Raw,
+        }
+    }
+
+    /// 切换模式时保留已填写的文本或表单字段
+    fn convert(self, body: &Option<FetchBody>) -> Option<FetchBody> {
+        if self == BodyMode::of(body) {
+            return body.clone();
+        }
+
+        let text = body
+            .as_ref()
+            .filter(|_| matches!(BodyMode::of(body), BodyMode::Raw | BodyMode::Json))
+            .map(FetchBody::to_text)
+            .unwrap_or_default();
+        let fields = body.as_ref().map(FetchBody::fields).unwrap_or_default();
+        match self {
+            BodyMode::None => None,
+            BodyMode::Raw => Some(FetchBody::Text(text)),
+            BodyMode::Json => Some(FetchBody::Json { json: text }),
+            BodyMode::Form => Some(FetchBody::Form { form: fields }),
+            BodyMode::Multipart => Some(FetchBody::Multipart { multipart: fields }),
+        }
+    }
+}
 
 #[component]
-pub fn BodyArea(
+pub fn BodyEditor(
     #[props(default)] class: String,
-    value: Signal<String>,
-    contenteditable: bool,
+    value: Signal<Option<FetchBody>>,
+    disabled: bool,
 ) -> Element {
+    let mode = BodyMode::of(&value());
+
+    rsx! {
+        div { class: "flex flex-col gap-2 {class}",
+            div { role: "tablist", class: "tabs tabs-border tabs-sm",
+                for (tab , label) in BodyMode::ALL {
+                    button {
+                        role: "tab",
+                        class: if tab == mode { "tab tab-active" } else { "tab" },
+                        disabled,
+                        onclick: move |_| {
+                            let body = tab.convert(&value.peek());
+                            value.set(body);
+                        },
+                        "{label}"
+                    }
+                }
+            }
+            match mode {
+                BodyMode::None => rsx! {},
+                BodyMode::Raw | BodyMode::Json => rsx! {
+                    TextBody { value, disabled, json: mode == BodyMode::Json }
+                },
+                BodyMode::Form | BodyMode::Multipart => rsx! {
+                    FieldTable { value, disabled, multipart: mode == BodyMode::Multipart }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn TextBody(value: Signal<Option<FetchBody>>, disabled: bool, json: bool) -> Element {
+    let body = value();
+    let binary = body.as_ref().is_some_and(FetchBody::is_binary);
+    let text = body.map(|body| body.to_text()).unwrap_or_default();
+    let error = if json && !text.trim().is_empty() {
+        serde_json::from_str::<Value>(&text)
+            .err()
+            .map(|e| e.to_string())
+    } else {
+        None
+    };
+    let invalid = error.is_some();
+    let wrap = move |text: String| {
+        if json {
+            FetchBody::Json { json: text }
+        } else {
+            FetchBody::Text(text)
+        }
+    };
+
+    rsx! {
+        textarea {
+            value: "{text}",
+            class: "body-area textarea textarea-ghost textarea-neutral h-full w-full font-mono",
+            disabled,
+            readonly: binary,
+            oninput: move |e| value.set(Some(wrap(e.value()))),
+        }
+        if binary {
+            span { class: "text-xs opacity-60",
+                "二进制请求体，按原始字节发送，不可编辑"
+            }
+        }
+        if json {
+            div { class: "flex items-center gap-2",
+                if let Some(e) = error {
+                    span { class: "text-xs text-error", "JSON 无效：{e}" }
+                }
+                button {
+                    class: "btn btn-ghost btn-xs ml-auto",
+                    disabled: disabled || invalid,
+                    onclick: move |_| {
+                        let pretty = serde_json::from_str::<Value>(&text)
+                            .and_then(|json| serde_json::to_string_pretty(&json));
+                        if let Ok(pretty) = pretty {
+                            value.set(Some(wrap(pretty)));
+                        }
+                    },
+                    "格式化"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FieldTable(value: Signal<Option<FetchBody>>, disabled: bool, multipart: bool) -> Element {
+    let fields = value().map(|body| body.fields()).unwrap_or_default();
+    let mut update = move |f: &dyn Fn(&mut Vec<FormField>)| {
+        let mut fields = value
+            .peek()
+            .as_ref()
+            .map(FetchBody::fields)
+            .unwrap_or_default();
+        f(&mut fields);
+        value.set(Some(if multipart {
+            FetchBody::Multipart { multipart: fields }
+        } else {
+            FetchBody::Form { form: fields }
+        }));
+    };
+
     rsx! {
-        div {
-            class: "body-area textarea textarea-ghost textarea-neutral {class}",
-            contenteditable: contenteditable.to_string(),
-            oninput: move |e| {
-                value.set(e.value());
-            },
-
-            {value}
+        table { class: "table table-sm",
+            tbody {
+                for (i , field) in fields.into_iter().enumerate() {
+                    tr { key: "{i}",
+                        td { class: "w-1/3",
+                            input {
+                                value: "{field.key}",
+                                class: "input input-ghost input-sm w-full",
+                                placeholder: "name",
+                                disabled,
+                                oninput: move |e| update(&|fields| fields[i].key = e.value()),
+                            }
+                        }
+                        td {
+                            input {
+                                value: "{field.value}",
+                                class: "input input-ghost input-sm w-full",
+                                placeholder: if field.file { "文件路径" } else { "value" },
+                                disabled,
+                                oninput: move |e| update(&|fields| fields[i].value = e.value()),
+                            }
+                        }
+                        if multipart {
+                            td { class: "w-16",
+                                label { class: "label text-xs",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "checkbox checkbox-xs",
+                                        checked: field.file,
+                                        disabled,
+                                        onchange: move |e| update(&|fields| fields[i].file = e.checked()),
+                                    }
+                                    "文件"
+                                }
+                            }
+                        }
+                        td { class: "w-8",
+                            button {
+                                class: "btn btn-ghost btn-xs",
+                                title: "删除",
+                                disabled,
+                                onclick: move |_| update(&|fields| {
+                                    fields.remove(i);
+                                }),
+                                "✕"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        button {
+            class: "btn btn-ghost btn-xs self-start",
+            disabled,
+            onclick: move |_| update(&|fields| fields.push(FormField::default())),
+            "添加字段"
         }
     }
 }
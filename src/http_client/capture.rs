@@ -0,0 +1,109 @@
+use dioxus::prelude::*;
+use futures_util::StreamExt as _;
+use tauri_sys::event::listen;
+use time::macros::format_description;
+
+use super::log::{MethodBadge, RequestDraft};
+use crate::{
+    api::{CapturedRequest, capture, clear_captured_requests, query_captured_requests},
+    app::use_browser,
+};
+
+/// 与后端缓冲区一致，超出时丢弃最早的请求
+const MAX_CAPTURE_COUNT: usize = 500;
+
+/// 当前标签页抓取到的 fetch、XHR 请求，点击载入编辑器
+#[component]
+pub fn CaptureList(onload: EventHandler<RequestDraft>) -> Element {
+    let capturing = use_browser().capture;
+    let mut keyword = use_signal(String::new);
+    let mut requests = use_signal(Vec::<CapturedRequest>::new);
+
+    use_future(move || async move {
+        if let Ok(captured) = query_captured_requests().await {
+            requests.set(captured);
+        }
+        let Ok(mut events) = listen::<CapturedRequest>("request-captured").await else {
+            return;
+        };
+        while let Some(event) = events.next().await {
+            let mut requests = requests.write();
+            requests.truncate(MAX_CAPTURE_COUNT - 1);
+            requests.insert(0, event.payload);
+        }
+    });
+
+    let filtered: Vec<CapturedRequest> = requests
+        .read()
+        .iter()
+        .filter(|request| request.url.contains(keyword.read().as_str()))
+        .cloned()
+        .collect();
+
+    rsx! {
+        div { class: "flex gap-1 items-center",
+            label { class: "label text-sm whitespace-nowrap",
+                input {
+                    r#type: "checkbox",
+                    class: "toggle toggle-sm",
+                    checked: capturing(),
+                    onclick: |_| async { capture().await },
+                }
+                "抓取当前标签页"
+            }
+            button {
+                class: "btn btn-ghost btn-xs ml-auto",
+                onclick: move |_| async move {
+                    if clear_captured_requests().await.is_ok() {
+                        requests.write().clear();
+                    }
+                },
+                "清空"
+            }
+        }
+        input {
+            value: keyword,
+            r#type: "text",
+            class: "input input-sm w-full",
+            placeholder: "过滤 URL",
+            oninput: move |e| keyword.set(e.value()),
+        }
+        ul { class: "list overflow-y-auto",
+            for request in filtered {
+                CaptureItem { key: "{request.id}", request, onload }
+            }
+        }
+    }
+}
+
+#[component]
+fn CaptureItem(request: CapturedRequest, onload: EventHandler<RequestDraft>) -> Element {
+    let draft = RequestDraft::from(&request);
+    let time = request
+        .capture_time
+        .format(format_description!("[hour]:[minute]:[second]"))
+        .unwrap_or_default();
+
+    rsx! {
+        li {
+            class: "list-row items-center p-2 cursor-pointer",
+            onclick: move |_| onload.call(draft.clone()),
+            MethodBadge { method: request.method.clone() }
+            div { class: "list-col-grow min-w-0",
+                div { class: "truncate text-sm", title: "{request.url}", "{request.url}" }
+                div {
+                    class: "truncate text-xs opacity-60",
+                    title: "{request.page_url}",
+                    "{time} · {request.initiator}"
+                }
+            }
+            if request.templated {
+                span {
+                    class: "badge badge-warning badge-xs",
+                    title: "请求含 {{{{ }}}}，发送时会填入环境变量的值，请确认后再发送",
+                    "模板"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,959 @@
+use std::fmt::{self, Display};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use dioxus::prelude::*;
+
+use super::log::RequestDraft;
+use crate::api::{FetchBody, FetchOptions, FormField, HttpHeader, HttpRequest};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurlError {
+    NotCurl,
+    UnclosedQuote,
+    MissingValue(String),
+    MissingUrl,
+    MultipleUrls,
+    FileNotSupported(String),
+}
+
+impl Display for CurlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurlError::NotCurl => write!(f, "不是 curl 命令"),
+            CurlError::UnclosedQuote => write!(f, "引号未闭合"),
+            CurlError::MissingValue(option) => write!(f, "参数 {option} 缺少值"),
+            CurlError::MissingUrl => write!(f, "缺少 URL"),
+            CurlError::MultipleUrls => write!(f, "只支持一个 URL"),
+            CurlError::FileNotSupported(option) => write!(f, "参数 {option} 不支持读取文件"),
+        }
+    }
+}
+
+/// 带值的短参数
+const SHORT_VALUE_OPTIONS: &str = "AbcCdDeEFHKmoPQrtTuUwxXyYz";
+
+/// 带值的长参数，未处理的也要跳过其值，否则值会被当作 URL
+const LONG_VALUE_OPTIONS: &[&str] = &[
+    "abstract-unix-socket",
+    "alt-svc",
+    "aws-sigv4",
+    "cacert",
+    "capath",
+    "cert",
+    "cert-type",
+    "ciphers",
+    "config",
+    "connect-timeout",
+    "connect-to",
+    "continue-at",
+    "cookie",
+    "cookie-jar",
+    "create-file-mode",
+    "crlfile",
+    "curves",
+    "data",
+    "data-ascii",
+    "data-binary",
+    "data-raw",
+    "data-urlencode",
+    "delegation",
+    "dns-interface",
+    "dns-ipv4-addr",
+    "dns-ipv6-addr",
+    "dns-servers",
+    "doh-url",
+    "dump-header",
+    "ech",
+    "egd-file",
+    "engine",
+    "etag-compare",
+    "etag-save",
+    "expect100-timeout",
+    "form",
+    "form-string",
+    "ftp-account",
+    "ftp-alternative-to-user",
+    "ftp-method",
+    "ftp-port",
+    "ftp-ssl-ccc-mode",
+    "happy-eyeballs-timeout-ms",
+    "haproxy-clientip",
+    "header",
+    "hostpubmd5",
+    "hostpubsha256",
+    "hsts",
+    "interface",
+    "ip-tos",
+    "ipfs-gateway",
+    "json",
+    "keepalive-cnt",
+    "keepalive-time",
+    "key",
+    "key-type",
+    "krb",
+    "libcurl",
+    "limit-rate",
+    "local-port",
+    "login-options",
+    "mail-auth",
+    "mail-from",
+    "mail-rcpt",
+    "max-filesize",
+    "max-redirs",
+    "max-time",
+    "netrc-file",
+    "noproxy",
+    "oauth2-bearer",
+    "output",
+    "output-dir",
+    "parallel-max",
+    "pass",
+    "pinnedpubkey",
+    "preproxy",
+    "proto",
+    "proto-default",
+    "proto-redir",
+    "proxy",
+    "proxy-cacert",
+    "proxy-capath",
+    "proxy-cert",
+    "proxy-cert-type",
+    "proxy-ciphers",
+    "proxy-crlfile",
+    "proxy-header",
+    "proxy-key",
+    "proxy-key-type",
+    "proxy-pass",
+    "proxy-pinnedpubkey",
+    "proxy-service-name",
+    "proxy-tls13-ciphers",
+    "proxy-tlsauthtype",
+    "proxy-tlspassword",
+    "proxy-tlsuser",
+    "proxy-user",
+    "proxy1.0",
+    "pubkey",
+    "quote",
+    "random-file",
+    "range",
+    "rate",
+    "referer",
+    "request",
+    "request-target",
+    "resolve",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "sasl-authzid",
+    "service-name",
+    "socks4",
+    "socks4a",
+    "socks5",
+    "socks5-gssapi-service",
+    "socks5-hostname",
+    "speed-limit",
+    "speed-time",
+    "stderr",
+    "telnet-option",
+    "tftp-blksize",
+    "time-cond",
+    "tls-max",
+    "tls13-ciphers",
+    "tlsauthtype",
+    "tlspassword",
+    "tlsuser",
+    "trace",
+    "trace-ascii",
+    "trace-config",
+    "unix-socket",
+    "upload-file",
+    "url",
+    "url-query",
+    "user",
+    "user-agent",
+    "variable",
+    "write-out",
+];
+
+/// 解析 curl 命令行
+pub fn parse_curl(command: &str) -> Result<HttpRequest, CurlError> {
+    let mut args = tokenize(command)?.into_iter();
+    if args.next().as_deref() != Some(b"curl".as_slice()) {
+        return Err(CurlError::NotCurl);
+    }
+
+    let mut url = None;
+    let mut method = None;
+    let mut headers = Vec::new();
+    let mut data: Vec<Vec<u8>> = Vec::new();
+    // --data-urlencode 的 name=content 字段，数据全部由字段组成时作为表单请求体
+    let mut form = Vec::new();
+    let mut raw_data = false;
+    let mut multipart = Vec::new();
+    let mut user = None;
+    let mut get = false;
+    let mut json = false;
+
+    while let Some(arg) = args.next() {
+        let arg = String::from_utf8_lossy(&arg).into_owned();
+        let options = split_option(&arg);
+        if options.is_empty() {
+            if url.replace(arg).is_some() {
+                return Err(CurlError::MultipleUrls);
+            }
+            continue;
+        }
+
+        for (option, inline) in options {
+            // 请求体保留原始字节，其余参数按文本处理
+            let mut raw_value = || -> Result<Vec<u8>, CurlError> {
+                match inline.clone() {
+                    Some(value) => Ok(value.into_bytes()),
+                    None => args
+                        .next()
+                        .ok_or_else(|| CurlError::MissingValue(option.to_string())),
+                }
+            };
+            let mut value =
+                || raw_value().map(|value| String::from_utf8_lossy(&value).into_owned());
+
+            match option.as_str() {
+                "-X" | "--request" => method = Some(value()?.to_uppercase()),
+                "-H" | "--header" => {
+                    let header = value()?;
+                    if let Some((key, value)) = header.split_once(':') {
+                        headers.push(HttpHeader {
+                            key: key.trim().to_lowercase(),
+                            value: value.trim().to_string(),
+                        });
+                    }
+                }
+                "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                    let value = raw_value()?;
+                    if option != "--data-raw" && value.starts_with(b"@") {
+                        return Err(CurlError::FileNotSupported(option.to_string()));
+                    }
+                    data.push(value);
+                    raw_data = true;
+                }
+                // 与 -d 相同，另外默认带上 JSON 的 Content-Type 与 Accept
+                "--json" => {
+                    let value = raw_value()?;
+                    if value.starts_with(b"@") {
+                        return Err(CurlError::FileNotSupported(option.to_string()));
+                    }
+                    data.push(value);
+                    raw_data = true;
+                    json = true;
+                }
+                "--data-urlencode" => {
+                    let value = value()?;
+                    match value.split_once('=') {
+                        Some(("", content)) => {
+                            data.push(encode_component(content).into_bytes());
+                            raw_data = true;
+                        }
+                        Some((name, content)) => {
+                            data.push(format!("{name}={}", encode_component(content)).into_bytes());
+                            form.push(FormField {
+                                key: name.to_string(),
+                                value: content.to_string(),
+                                file: false,
+                            });
+                        }
+                        None if value.contains('@') => {
+                            return Err(CurlError::FileNotSupported(option.to_string()));
+                        }
+                        None => {
+                            data.push(encode_component(&value).into_bytes());
+                            raw_data = true;
+                        }
+                    }
+                }
+                "-F" | "--form" | "--form-string" => {
+                    let value = value()?;
+                    let (name, value) = value.split_once('=').unwrap_or((&value, ""));
+                    let field = match value.strip_prefix('@') {
+                        // 去掉 `;type=`、`;filename=` 等附加属性
+                        Some(path) if option != "--form-string" => FormField {
+                            key: name.to_string(),
+                            value: path.split(';').next().unwrap_or_default().to_string(),
+                            file: true,
+                        },
+                        _ if option != "--form-string" && value.starts_with('<') => {
+                            return Err(CurlError::FileNotSupported(option.to_string()));
+                        }
+                        _ => FormField {
+                            key: name.to_string(),
+                            value: value.to_string(),
+                            file: false,
+                        },
+                    };
+                    multipart.push(field);
+                }
+                "-u" | "--user" => user = Some(value()?),
+                "-A" | "--user-agent" => headers.push(HttpHeader {
+                    key: "user-agent".to_string(),
+                    value: value()?,
+                }),
+                "-e" | "--referer" => headers.push(HttpHeader {
+                    key: "referer".to_string(),
+                    value: value()?,
+                }),
+                "-b" | "--cookie" => headers.push(HttpHeader {
+                    key: "cookie".to_string(),
+                    value: value()?,
+                }),
+                "-I" | "--head" => method = Some("HEAD".to_string()),
+                "-G" | "--get" => get = true,
+                "--url" => {
+                    let value = value()?;
+                    if url.replace(value).is_some() {
+                        return Err(CurlError::MultipleUrls);
+                    }
+                }
+                // 其余带值参数与请求内容无关，跳过其值
+                option if takes_value(option) => {
+                    value()?;
+                }
+                // 客户端不解压响应、证书校验由客户端配置决定，忽略 --compressed、-k 等开关
+                _ => {}
+            }
+        }
+    }
+
+    let url = url.ok_or(CurlError::MissingUrl)?;
+
+    if json {
+        for key in ["content-type", "accept"] {
+            if !has_header(&headers, key) {
+                headers.push(HttpHeader {
+                    key: key.to_string(),
+                    value: "application/json".to_string(),
+                });
+            }
+        }
+    }
+
+    if let Some(user) = user
+        && !has_header(&headers, "authorization")
+    {
+        headers.push(HttpHeader {
+            key: "authorization".to_string(),
+            value: format!("Basic {}", STANDARD.encode(user)),
+        });
+    }
+
+    let data = (!data.is_empty()).then(|| data.join(b"&".as_slice()));
+
+    // -G 将数据拼接到查询参数
+    let (url, data) = match data {
+        Some(data) if get => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            (
+                format!("{url}{separator}{}", String::from_utf8_lossy(&data)),
+                None,
+            )
+        }
+        data => (url, data),
+    };
+
+    // 表单、JSON 和 multipart 的 Content-Type 由后端生成
+    let content_type = headers
+        .iter()
+        .find(|header| header.key.eq_ignore_ascii_case("content-type"))
+        .map(|header| header.value.clone());
+    let body = if !multipart.is_empty() {
+        Some(FetchBody::Multipart { multipart })
+    } else {
+        data.map(|data| match content_type {
+            None if !raw_data => FetchBody::Form { form },
+            Some(content_type) if content_type.starts_with("application/json") => {
+                match String::from_utf8(data) {
+                    Ok(json) => FetchBody::Json { json },
+                    Err(e) => FetchBody::Bytes(e.into_bytes()),
+                }
+            }
+            content_type => {
+                if content_type.is_none() {
+                    headers.push(HttpHeader {
+                        key: "content-type".to_string(),
+                        value: "application/x-www-form-urlencoded".to_string(),
+                    });
+                }
+                match String::from_utf8(data) {
+                    Ok(text) => FetchBody::Text(text),
+                    Err(e) => FetchBody::Bytes(e.into_bytes()),
+                }
+            }
+        })
+    };
+
+    let method = match method {
+        Some(method) => method,
+        None if body.is_some() => "POST".to_string(),
+        None => "GET".to_string(),
+    };
+
+    Ok(HttpRequest {
+        url,
+        options: Some(FetchOptions {
+            method: Some(method),
+            headers: Some(headers).filter(|headers| !headers.is_empty()),
+            body,
+            ..Default::default()
+        }),
+        request_id: None,
+    })
+}
+
+/// 生成可直接粘贴到终端的 curl 命令
+pub fn to_curl(request: &HttpRequest) -> String {
+    let mut command = format!("curl {}", quote(request.url.as_bytes()));
+    let Some(options) = &request.options else {
+        return command;
+    };
+
+    let method = options.method.as_deref().unwrap_or("GET").to_uppercase();
+    let default_method = if options.body.is_some() {
+        "POST"
+    } else {
+        "GET"
+    };
+    if method != default_method {
+        command.push_str(&format!(" \\\n  -X {method}"));
+    }
+
+    for HttpHeader { key, value } in options.headers.iter().flatten() {
+        command.push_str(&format!(
+            " \\\n  -H {}",
+            quote(format!("{key}: {value}").as_bytes())
+        ));
+    }
+
+    let headers = options.headers.as_deref().unwrap_or_default();
+    match &options.body {
+        Some(FetchBody::Json { json }) => {
+            if !has_header(headers, "content-type") {
+                command.push_str(" \\\n  -H 'content-type: application/json'");
+            }
+            command.push_str(&format!(" \\\n  --data-raw {}", quote(json.as_bytes())));
+        }
+        Some(FetchBody::Form { form }) => {
+            for FormField { key, value, .. } in form {
+                command.push_str(&format!(
+                    " \\\n  --data-urlencode {}",
+                    quote(format!("{key}={value}").as_bytes())
+                ));
+            }
+        }
+        Some(FetchBody::Multipart { multipart }) => {
+            for FormField { key, value, file } in multipart {
+                let (option, value) = if *file {
+                    ("-F", format!("{key}=@{value}"))
+                } else if value.starts_with(['@', '<']) || value.contains(';') {
+                    // 避免被 curl 当作文件或附加属性
+                    ("--form-string", format!("{key}={value}"))
+                } else {
+                    ("-F", format!("{key}={value}"))
+                };
+                command.push_str(&format!(" \\\n  {option} {}", quote(value.as_bytes())));
+            }
+        }
+        Some(FetchBody::Text(text)) => {
+            command.push_str(&format!(" \\\n  --data-raw {}", quote(text.as_bytes())));
+        }
+        Some(FetchBody::Bytes(bytes)) => {
+            command.push_str(&format!(" \\\n  --data-binary {}", quote(bytes)));
+        }
+        Some(FetchBody::Base64 { base64 }) => {
+            let bytes = STANDARD.decode(base64).unwrap_or_default();
+            command.push_str(&format!(" \\\n  --data-binary {}", quote(&bytes)));
+        }
+        None => {}
+    }
+
+    command
+}
+
+/// 按 shell 规则拆分参数，支持单双引号、`$'...'` 和行尾续行
+fn tokenize(command: &str) -> Result<Vec<Vec<u8>>, CurlError> {
+    let mut args = Vec::new();
+    let mut arg: Option<Vec<u8>> = None;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(arg) = arg.take() {
+                    args.push(arg);
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => push_char(arg.get_or_insert_default(), c),
+                None => {}
+            },
+            '\'' => {
+                let arg = arg.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => push_char(arg, c),
+                        None => return Err(CurlError::UnclosedQuote),
+                    }
+                }
+            }
+            '"' => {
+                let arg = arg.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => push_char(arg, c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                arg.push(b'\\');
+                                push_char(arg, c);
+                            }
+                            None => return Err(CurlError::UnclosedQuote),
+                        },
+                        Some(c) => push_char(arg, c),
+                        None => return Err(CurlError::UnclosedQuote),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let arg = arg.get_or_insert_default();
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => arg.push(b'\n'),
+                            Some('r') => arg.push(b'\r'),
+                            Some('t') => arg.push(b'\t'),
+                            Some('0') => arg.push(0),
+                            Some('x') => {
+                                let hex: String = (0..2).filter_map(|_| chars.next()).collect();
+                                let byte = u8::from_str_radix(&hex, 16)
+                                    .map_err(|_| CurlError::UnclosedQuote)?;
+                                arg.push(byte);
+                            }
+                            Some(c) => push_char(arg, c),
+                            None => return Err(CurlError::UnclosedQuote),
+                        },
+                        Some(c) => push_char(arg, c),
+                        None => return Err(CurlError::UnclosedQuote),
+                    }
+                }
+            }
+            c => push_char(arg.get_or_insert_default(), c),
+        }
+    }
+
+    if let Some(arg) = arg {
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+fn push_char(arg: &mut Vec<u8>, c: char) {
+    let mut buf = [0; 4];
+    arg.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+}
+
+/// 拆分参数名与紧跟的值，非参数返回空。短参数可以组合，如 `-sSL`、`-sXPOST`，
+/// 逐个字符解析到第一个带值的参数为止，其后的字符都是它的值。与 curl 一致，长参数不支持 `--request=POST`
+fn split_option(arg: &str) -> Vec<(String, Option<String>)> {
+    if let Some(long) = arg.strip_prefix("--") {
+        if long.is_empty() {
+            return Vec::new();
+        }
+        return vec![(arg.to_string(), None)];
+    }
+
+    let Some(cluster) = arg.strip_prefix('-').filter(|cluster| !cluster.is_empty()) else {
+        return Vec::new();
+    };
+
+    let mut options = Vec::new();
+    for (i, c) in cluster.char_indices() {
+        let option = format!("-{c}");
+        if SHORT_VALUE_OPTIONS.contains(c) {
+            let value = &cluster[i + c.len_utf8()..];
+            options.push((
+                option,
+                Some(value.to_string()).filter(|value| !value.is_empty()),
+            ));
+            break;
+        }
+        options.push((option, None));
+    }
+    options
+}
+
+fn takes_value(option: &str) -> bool {
+    match option.strip_prefix("--") {
+        Some(long) => LONG_VALUE_OPTIONS.contains(&long),
+        None => option
+            .strip_prefix('-')
+            .is_some_and(|short| short.len() == 1 && SHORT_VALUE_OPTIONS.contains(short)),
+    }
+}
+
+fn has_header(headers: &[HttpHeader], key: &str) -> bool {
+    headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case(key))
+}
+
+/// 与 curl 一致，只保留非保留字符
+fn encode_component(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// 可打印文本用单引号，否则用 `$'...'` 转义
+fn quote(value: &[u8]) -> String {
+    match std::str::from_utf8(value) {
+        Ok(text) if !text.chars().any(|c| c.is_control() && c != '\n') => {
+            format!("'{}'", text.replace('\'', r"'\''"))
+        }
+        _ => {
+            let mut quoted = String::from("$'");
+            for chunk in value.utf8_chunks() {
+                for c in chunk.valid().chars() {
+                    match c {
+                        '\'' => quoted.push_str(r"\'"),
+                        '\\' => quoted.push_str(r"\\"),
+                        '\n' => quoted.push_str(r"\n"),
+                        '\r' => quoted.push_str(r"\r"),
+                        '\t' => quoted.push_str(r"\t"),
+                        c if c.is_control() => quoted.push_str(&format!(r"\x{:02x}", c as u32)),
+                        c => quoted.push(c),
+                    }
+                }
+                for byte in chunk.invalid() {
+                    quoted.push_str(&format!(r"\x{byte:02x}"));
+                }
+            }
+            quoted.push('\'');
+            quoted
+        }
+    }
+}
+
+#[component]
+pub fn CurlMenu(
+    #[props(default)] class: String,
+    draft: ReadSignal<RequestDraft>,
+    onimport: EventHandler<RequestDraft>,
+) -> Element {
+    let mut command = use_signal(String::new);
+    let mut error = use_signal(|| None::<CurlError>);
+
+    let on_import = move |_| match parse_curl(&command()) {
+        Ok(request) => {
+            onimport.call(RequestDraft::from(&request));
+            command.set(String::new());
+            error.set(None);
+        }
+        Err(e) => error.set(Some(e)),
+    };
+
+    let on_export = move |_| {
+        let curl = to_curl(&HttpRequest::from(&draft()));
+        command.set(curl.clone());
+        error.set(None);
+        let eval = document::eval(
+            "let text = await dioxus.recv(); await navigator.clipboard.writeText(text);",
+        );
+        let _ = eval.send(curl);
+    };
+
+    rsx! {
+        div { class: "dropdown dropdown-end {class}",
+            div { tabindex: "0", role: "button", class: "btn btn-ghost", title: "cURL", "cURL" }
+            div {
+                tabindex: "0",
+                class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-[32rem] p-4 flex flex-col gap-2",
+                textarea {
+                    value: command,
+                    class: "textarea textarea-sm w-full h-40 font-mono",
+                    placeholder: "curl https://example.com -H 'accept: application/json'",
+                    oninput: move |e| command.set(e.value()),
+                }
+                if let Some(e) = error() {
+                    div { class: "text-xs text-error", "{e}" }
+                }
+                div { class: "flex justify-end gap-2",
+                    button { class: "btn btn-sm", onclick: on_export, "复制为 cURL" }
+                    button {
+                        class: "btn btn-sm btn-primary",
+                        disabled: command().is_empty(),
+                        onclick: on_import,
+                        "导入"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, headers: &[(&str, &str)], body: Option<FetchBody>) -> HttpRequest {
+        HttpRequest {
+            url: "https://example.com/api?q=1".to_string(),
+            options: Some(FetchOptions {
+                method: Some(method.to_string()),
+                headers: Some(
+                    headers
+                        .iter()
+                        .map(|(key, value)| HttpHeader {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                        })
+                        .collect(),
+                )
+                .filter(|headers: &Vec<HttpHeader>| !headers.is_empty()),
+                body,
+                ..Default::default()
+            }),
+            request_id: None,
+        }
+    }
+
+    fn field(key: &str, value: &str, file: bool) -> FormField {
+        FormField {
+            key: key.to_string(),
+            value: value.to_string(),
+            file,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = parse_curl(
+            "curl 'https://example.com/api?q=1' \\\n  -H 'Accept: application/json' \\\n  -H \"x-token: a\\\"b\" \\\n  --data-raw '{\"name\":\"it'\\''s\"}' \\\n  --compressed -k",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            request(
+                "POST",
+                &[
+                    ("accept", "application/json"),
+                    ("x-token", "a\"b"),
+                    ("content-type", "application/x-www-form-urlencoded"),
+                ],
+                Some(FetchBody::Text(r#"{"name":"it's"}"#.to_string())),
+            )
+        );
+
+        let parsed =
+            parse_curl("curl -XPUT -u user:pass -sSL https://example.com/api?q=1").unwrap();
+        assert_eq!(
+            parsed,
+            request("PUT", &[("authorization", "Basic dXNlcjpwYXNz")], None)
+        );
+
+        let parsed = parse_curl("curl -G -d a=1 -d b=2 https://example.com/api?q=1").unwrap();
+        assert_eq!(parsed.url, "https://example.com/api?q=1&a=1&b=2");
+        assert_eq!(parsed.options.unwrap().method.as_deref(), Some("GET"));
+
+        let parsed = parse_curl(
+            "curl https://example.com/api?q=1 -F name=white -F 'upload=@/tmp/a.txt;type=text/plain'",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            request(
+                "POST",
+                &[],
+                Some(FetchBody::Multipart {
+                    multipart: vec![
+                        field("name", "white", false),
+                        field("upload", "/tmp/a.txt", true),
+                    ],
+                }),
+            )
+        );
+
+        let parsed = parse_curl(
+            "curl https://example.com/api?q=1 --data-urlencode 'q=a b' --data-urlencode lang=zh",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            request(
+                "POST",
+                &[],
+                Some(FetchBody::Form {
+                    form: vec![field("q", "a b", false), field("lang", "zh", false)],
+                }),
+            )
+        );
+
+        let parsed = parse_curl("curl -G --data-urlencode 'q=a b' https://example.com/").unwrap();
+        assert_eq!(parsed.url, "https://example.com/?q=a%20b");
+
+        let parsed = parse_curl(
+            "curl https://example.com/api?q=1 -H 'Content-Type: application/json' -d '{\"a\":1}'",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.options.unwrap().body,
+            Some(FetchBody::Json {
+                json: r#"{"a":1}"#.to_string()
+            })
+        );
+
+        assert_eq!(
+            parse_curl("wget https://example.com"),
+            Err(CurlError::NotCurl)
+        );
+        assert_eq!(
+            parse_curl("curl -H"),
+            Err(CurlError::MissingValue("-H".to_string()))
+        );
+        assert_eq!(
+            parse_curl("curl 'https://example.com"),
+            Err(CurlError::UnclosedQuote)
+        );
+        assert_eq!(
+            parse_curl("curl https://example.com -d @body.json"),
+            Err(CurlError::FileNotSupported("-d".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_option_cluster() {
+        // 组合参数与逐个书写的参数等价
+        for (cluster, separate) in [
+            (
+                "curl -sXPOST https://example.com/api?q=1",
+                "curl -s -X POST https://example.com/api?q=1",
+            ),
+            (
+                "curl -sd body https://example.com/api?q=1",
+                "curl -s -d body https://example.com/api?q=1",
+            ),
+            (
+                "curl -sSLd a=1 -GH 'x-token: a' https://example.com/api?q=1",
+                "curl -s -S -L -d a=1 -G -H 'x-token: a' https://example.com/api?q=1",
+            ),
+        ] {
+            assert_eq!(parse_curl(cluster), parse_curl(separate), "{cluster}");
+        }
+
+        let parsed = parse_curl("curl -sXPOST https://example.com/api?q=1").unwrap();
+        assert_eq!(parsed, request("POST", &[], None));
+        assert_eq!(
+            parse_curl("curl https://example.com/api?q=1 -sd"),
+            Err(CurlError::MissingValue("-d".to_string()))
+        );
+
+        // curl 不支持 `--option=value`，按未知参数忽略
+        let parsed = parse_curl("curl --request=PUT https://example.com/api?q=1").unwrap();
+        assert_eq!(parsed, request("GET", &[], None));
+    }
+
+    #[test]
+    fn test_value_options() {
+        // 未处理的带值参数也跳过其值，不会覆盖 URL
+        for command in [
+            "curl https://example.com/api?q=1 --max-redirs 5",
+            "curl -c jar.txt https://example.com/api?q=1 -D h.txt",
+            "curl -sD- --retry-delay 1 --limit-rate 1k https://example.com/api?q=1",
+            "curl -E cert.pem -U u:p -r 0-99 --url https://example.com/api?q=1",
+        ] {
+            assert_eq!(
+                parse_curl(command),
+                Ok(request("GET", &[], None)),
+                "{command}"
+            );
+        }
+
+        assert_eq!(
+            parse_curl("curl https://example.com/a https://example.com/b"),
+            Err(CurlError::MultipleUrls)
+        );
+        assert_eq!(
+            parse_curl("curl --json '{\"a\":1}' https://example.com/api?q=1"),
+            Ok(request(
+                "POST",
+                &[
+                    ("content-type", "application/json"),
+                    ("accept", "application/json"),
+                ],
+                Some(FetchBody::Json {
+                    json: "{\"a\":1}".to_string(),
+                }),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let requests = [
+            request("GET", &[], None),
+            request("DELETE", &[("authorization", "Bearer 'token'")], None),
+            request(
+                "POST",
+                &[("content-type", "application/json")],
+                Some(FetchBody::Json {
+                    json: "{\"a\": \"line\\n\"}\nnext".to_string(),
+                }),
+            ),
+            request(
+                "PUT",
+                &[],
+                Some(FetchBody::Form {
+                    form: vec![field("q", "a&b=c d", false), field("empty", "", false)],
+                }),
+            ),
+            request(
+                "POST",
+                &[("authorization", "Basic dXNlcjpwYXNz")],
+                Some(FetchBody::Multipart {
+                    multipart: vec![
+                        field("name", "white;hole", false),
+                        field("at", "@literal", false),
+                        field("upload", "/tmp/it's.png", true),
+                    ],
+                }),
+            ),
+            request(
+                "PATCH",
+                &[("content-type", "text/plain")],
+                Some(FetchBody::Text("tab\tand 'quote'".to_string())),
+            ),
+            request(
+                "POST",
+                &[("content-type", "application/octet-stream")],
+                Some(FetchBody::Bytes(vec![0, 1, 0xff, b'\'', b'\\'])),
+            ),
+        ];
+
+        for request in requests {
+            let command = to_curl(&request);
+            assert_eq!(parse_curl(&command).unwrap(), request, "{command}");
+        }
+    }
+}
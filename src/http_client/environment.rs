@@ -0,0 +1,311 @@
+use dioxus::prelude::*;
+
+use crate::api::{
+    Environment, Variable, capture_variable, delete_environment, delete_variable,
+    query_environments, save_environment, save_variable, set_active_environment,
+};
+
+/// 环境选择与变量编辑，请求中的 `{{var}}` 由后端按激活环境替换
+#[component]
+pub fn EnvironmentSelect(#[props(default)] class: String, refresh: ReadSignal<u32>) -> Element {
+    let mut name = use_signal(String::new);
+    let mut environments = use_resource(move || async move {
+        let _ = refresh.read();
+        query_environments().await.unwrap_or_default()
+    });
+    let all = environments().unwrap_or_default();
+    let active = all.iter().find(|environment| environment.active).cloned();
+    let active_id = active.as_ref().map(|environment| environment.id);
+
+    rsx! {
+        div { class: "join {class}",
+            select {
+                class: "select select-sm join-item w-full",
+                onchange: move |e| async move {
+                    let id = e.value().parse().ok();
+                    if set_active_environment(id).await.is_ok() {
+                        environments.restart();
+                    }
+                },
+                option { value: "", selected: active_id.is_none(), "无环境" }
+                for environment in all.iter() {
+                    option {
+                        key: "{environment.id}",
+                        value: "{environment.id}",
+                        selected: environment.active,
+                        "{environment.name}"
+                    }
+                }
+            }
+            div { class: "dropdown dropdown-end join-item",
+                div { tabindex: "0", role: "button", class: "btn btn-sm", title: "编辑环境", "⚙" }
+                div {
+                    tabindex: "0",
+                    class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-[32rem] p-4 flex flex-col gap-2",
+                    div { class: "join w-full",
+                        input {
+                            value: name,
+                            r#type: "text",
+                            class: "input input-sm join-item w-full",
+                            placeholder: "环境名称",
+                            oninput: move |e| name.set(e.value()),
+                        }
+                        button {
+                            class: "btn btn-sm join-item",
+                            disabled: name().is_empty(),
+                            onclick: move |_| async move {
+                                if save_environment(None, name()).await.is_ok() {
+                                    name.set(String::new());
+                                    environments.restart();
+                                }
+                            },
+                            "新建环境"
+                        }
+                    }
+                    if let Some(environment) = active {
+                        VariableTable {
+                            key: "{environment.id}",
+                            environment,
+                            onchange: move |_| environments.restart(),
+                        }
+                    } else {
+                        div { class: "text-sm opacity-60", "选择环境后编辑变量" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn VariableTable(environment: Environment, onchange: EventHandler<()>) -> Element {
+    let environment_id = environment.id;
+    let mut key = use_signal(String::new);
+    let mut value = use_signal(String::new);
+    let mut secret = use_signal(|| false);
+
+    rsx! {
+        table { class: "table table-xs",
+            thead {
+                tr {
+                    th { "变量" }
+                    th { "值" }
+                    th { "私密" }
+                    th {}
+                }
+            }
+            tbody {
+                for variable in environment.variables {
+                    VariableRow {
+                        key: "{variable.id.unwrap_or_default()}",
+                        variable,
+                        onchange,
+                    }
+                }
+                tr {
+                    td {
+                        input {
+                            value: key,
+                            class: "input input-xs w-full",
+                            placeholder: "name",
+                            oninput: move |e| key.set(e.value()),
+                        }
+                    }
+                    td {
+                        input {
+                            value,
+                            r#type: if secret() { "password" } else { "text" },
+                            class: "input input-xs w-full",
+                            placeholder: "value",
+                            oninput: move |e| value.set(e.value()),
+                        }
+                    }
+                    td {
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox checkbox-xs",
+                            checked: secret(),
+                            onchange: move |e| secret.set(e.checked()),
+                        }
+                    }
+                    td {
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            title: "添加变量",
+                            disabled: key().is_empty(),
+                            onclick: move |_| async move {
+                                let variable = Variable {
+                                    id: None,
+                                    environment_id,
+                                    key: key(),
+                                    value: Some(value()),
+                                    secret: secret(),
+                                };
+                                if save_variable(variable).await.is_ok() {
+                                    key.set(String::new());
+                                    value.set(String::new());
+                                    secret.set(false);
+                                    onchange.call(());
+                                }
+                            },
+                            "＋"
+                        }
+                    }
+                }
+            }
+        }
+        button {
+            class: "btn btn-error btn-outline btn-xs self-end",
+            onclick: move |_| async move {
+                if delete_environment(environment_id).await.is_ok() {
+                    onchange.call(());
+                }
+            },
+            "删除环境"
+        }
+    }
+}
+
+#[component]
+fn VariableRow(variable: Variable, onchange: EventHandler<()>) -> Element {
+    let id = variable.id;
+    let mut key = use_signal(|| variable.key.clone());
+    // 私密变量的值不回显，留空保存时后端保持原值
+    let mut value = use_signal(|| variable.value.clone().unwrap_or_default());
+    let mut secret = use_signal(|| variable.secret);
+    let mut dirty = use_signal(|| false);
+
+    let save = move |_| {
+        let variable = variable.clone();
+        async move {
+            let value = value();
+            let variable = Variable {
+                key: key(),
+                value: if secret() && value.is_empty() {
+                    None
+                } else {
+                    Some(value)
+                },
+                secret: secret(),
+                ..variable
+            };
+            if save_variable(variable).await.is_ok() {
+                dirty.set(false);
+                onchange.call(());
+            }
+        }
+    };
+
+    rsx! {
+        tr {
+            td {
+                input {
+                    value: key,
+                    class: "input input-xs w-full",
+                    oninput: move |e| {
+                        key.set(e.value());
+                        dirty.set(true);
+                    },
+                }
+            }
+            td {
+                input {
+                    value,
+                    r#type: if secret() { "password" } else { "text" },
+                    class: "input input-xs w-full",
+                    placeholder: if secret() { "••••••" } else { "" },
+                    oninput: move |e| {
+                        value.set(e.value());
+                        dirty.set(true);
+                    },
+                }
+            }
+            td {
+                input {
+                    r#type: "checkbox",
+                    class: "checkbox checkbox-xs",
+                    checked: secret(),
+                    onchange: move |e| {
+                        secret.set(e.checked());
+                        dirty.set(true);
+                    },
+                }
+            }
+            td { class: "flex gap-1",
+                button {
+                    class: "btn btn-ghost btn-xs",
+                    title: "保存",
+                    disabled: !dirty(),
+                    onclick: save,
+                    "💾"
+                }
+                button {
+                    class: "btn btn-ghost btn-xs",
+                    title: "删除",
+                    onclick: move |_| async move {
+                        if let Some(id) = id
+                            && delete_variable(id).await.is_ok()
+                        {
+                            onchange.call(());
+                        }
+                    },
+                    "✕"
+                }
+            }
+        }
+    }
+}
+
+/// 用 JSONPath 从响应体中提取值，保存到激活环境，供后续请求引用
+#[component]
+pub fn CaptureForm(
+    #[props(default)] class: String,
+    body: ReadSignal<Vec<u8>>,
+    oncapture: EventHandler<()>,
+) -> Element {
+    let mut key = use_signal(String::new);
+    let mut path = use_signal(|| "$.".to_string());
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    rsx! {
+        div { class: "flex flex-col gap-1 {class}",
+            div { class: "join w-full",
+                input {
+                    value: path,
+                    r#type: "text",
+                    class: "input input-sm join-item w-full font-mono",
+                    placeholder: "$.data.token",
+                    oninput: move |e| path.set(e.value()),
+                }
+                input {
+                    value: key,
+                    r#type: "text",
+                    class: "input input-sm join-item w-1/3",
+                    placeholder: "变量名",
+                    oninput: move |e| key.set(e.value()),
+                }
+                button {
+                    class: "btn btn-sm join-item",
+                    disabled: key().is_empty() || path().is_empty(),
+                    onclick: move |_| async move {
+                        let captured = capture_variable(key(), path(), body()).await;
+                        if captured.is_ok() {
+                            oncapture.call(());
+                        }
+                        result.set(Some(captured.map_err(|e| e.to_string())));
+                    },
+                    "提取到环境"
+                }
+            }
+            match result() {
+                Some(Ok(value)) => rsx! {
+                    div { class: "text-xs text-success truncate", "{key} = {value}" }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "text-xs text-error", "{e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use dioxus::prelude::*;
+
+use crate::api::{HarSource, export_har, import_har};
+
+/// HAR 导入导出菜单，指定集合时可从本地文件导入到该集合
+#[component]
+pub fn HarMenu(
+    #[props(default)] class: String,
+    source: HarSource,
+    #[props(default)] collection_id: Option<i64>,
+    #[props(default)] onimport: EventHandler<()>,
+) -> Element {
+    let mut path = use_signal(String::new);
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    rsx! {
+        div { class: "dropdown dropdown-end {class}",
+            div {
+                tabindex: "0",
+                role: "button",
+                class: "btn btn-ghost btn-xs",
+                title: "HAR 导入导出",
+                // 避免在集合标题中点击时折叠文件夹
+                onclick: move |e| e.prevent_default(),
+                "HAR"
+            }
+            div {
+                tabindex: "0",
+                class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-80 p-2 flex flex-col gap-1",
+                button {
+                    class: "btn btn-sm w-full",
+                    onclick: move |_| {
+                        let source = source.clone();
+                        async move {
+                            let exported = export_har(source)
+                                .await
+                                .map(|path| format!("已导出：{path}"));
+                            result.set(Some(exported.map_err(|e| e.to_string())));
+                        }
+                    },
+                    "导出 HAR 到下载目录"
+                }
+                if let Some(collection_id) = collection_id {
+                    div { class: "join w-full",
+                        input {
+                            value: path,
+                            r#type: "text",
+                            class: "input input-sm join-item w-full",
+                            placeholder: "HAR 文件路径",
+                            oninput: move |e| path.set(e.value()),
+                        }
+                        button {
+                            class: "btn btn-sm join-item",
+                            disabled: path().is_empty(),
+                            onclick: move |_| async move {
+                                let imported = import_har(collection_id, path())
+                                    .await
+                                    .map(|count| format!("已导入 {count} 个请求"));
+                                if imported.is_ok() {
+                                    path.set(String::new());
+                                    onimport.call(());
+                                }
+                                result.set(Some(imported.map_err(|e| e.to_string())));
+                            },
+                            "导入"
+                        }
+                    }
+                }
+                match result() {
+                    Some(Ok(message)) => rsx! {
+                        div { class: "text-xs text-success break-all", "{message}" }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-xs text-error", "{e}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use dioxus::prelude::*;
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// 每个节点最多展开的子项数量，避免超大数组拖慢界面
+const MAX_CHILDREN: usize = 500;
+/// 默认展开的层级
+const OPEN_DEPTH: usize = 2;
+
+/// 可折叠的 JSON 树，支持 JSONPath 过滤
+#[component]
+pub fn JsonTree(value: ReadSignal<Value>) -> Element {
+    let mut path = use_signal(String::new);
+    let filtered = use_memo(move || {
+        let path = path();
+        if path.trim().is_empty() {
+            return Ok(value());
+        }
+        JsonPath::parse(&path)
+            .map(|path| {
+                let value = value.read();
+                Value::Array(path.query(&value).all().into_iter().cloned().collect())
+            })
+            .map_err(|e| e.to_string())
+    });
+
+    rsx! {
+        input {
+            value: path,
+            r#type: "text",
+            class: "input input-sm w-full font-mono",
+            placeholder: "JSONPath 过滤，如 $.data[*].id",
+            oninput: move |e| path.set(e.value()),
+        }
+        match filtered() {
+            Ok(value) => rsx! {
+                ul { class: "font-mono text-sm py-2",
+                    JsonNode { value, depth: 0 }
+                }
+            },
+            Err(e) => rsx! {
+                div { class: "text-xs text-error", "JSONPath 无效：{e}" }
+            },
+        }
+    }
+}
+
+#[component]
+fn JsonNode(#[props(default)] name: Option<String>, value: Value, depth: usize) -> Element {
+    let label = name.map(|name| format!("{name}: ")).unwrap_or_default();
+    let open = depth < OPEN_DEPTH;
+
+    let (len, children): (usize, Vec<(String, Value)>) = match &value {
+        Value::Object(map) => (
+            map.len(),
+            map.iter()
+                .take(MAX_CHILDREN)
+                .map(|(key, value)| (format!("\"{key}\""), value.clone()))
+                .collect(),
+        ),
+        Value::Array(items) => (
+            items.len(),
+            items
+                .iter()
+                .take(MAX_CHILDREN)
+                .enumerate()
+                .map(|(i, value)| (i.to_string(), value.clone()))
+                .collect(),
+        ),
+        scalar => {
+            let color = match scalar {
+                Value::String(_) => "text-success",
+                Value::Number(_) => "text-info",
+                _ => "text-warning",
+            };
+            return rsx! {
+                li { class: "pl-4 break-all",
+                    span { class: "opacity-70", "{label}" }
+                    span { class: color, "{scalar}" }
+                }
+            };
+        }
+    };
+    let size = if value.is_object() {
+        format!("{{{len}}}")
+    } else {
+        format!("[{len}]")
+    };
+
+    rsx! {
+        li {
+            details { open,
+                summary { class: "cursor-pointer",
+                    span { class: "opacity-70", "{label}" }
+                    span { class: "opacity-50", "{size}" }
+                }
+                ul { class: "pl-4 border-l border-base-300",
+                    for (name , value) in children {
+                        JsonNode {
+                            key: "{name}",
+                            name: Some(name.clone()),
+                            value,
+                            depth: depth + 1,
+                        }
+                    }
+                    if len > MAX_CHILDREN {
+                        li { class: "pl-4 opacity-50", "…还有 {len - MAX_CHILDREN} 项" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,505 @@
+use dioxus::prelude::*;
+use time::macros::format_description;
+
+use super::{assertion::RunCollectionMenu, capture::CaptureList, har::HarMenu};
+use crate::api::{
+    Assertion, Capture, CapturedRequest, Collection, FetchBody, FetchOptions, HarSource, HttpHeader, HttpRequest, PageToken, RequestLog,
+    SavedRequest, delete_collection, delete_request_log, delete_saved_request, query_collections,
+    query_request_log, save_collection, save_request,
+};
+
+/// 载入编辑器的请求内容
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestDraft {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: Option<FetchBody>,
+    pub credential_id: Option<i64>,
+    pub assertions: Vec<Assertion>,
+    pub captures: Vec<Capture>,
+}
+
+impl From<&RequestLog> for RequestDraft {
+    fn from(log: &RequestLog) -> Self {
+        Self {
+            method: log.method.clone(),
+            url: log.url.clone(),
+            headers: log.headers.clone(),
+            body: log.body.clone(),
+            credential_id: log.credential_id,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&SavedRequest> for RequestDraft {
+    fn from(request: &SavedRequest) -> Self {
+        Self {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: request.headers.clone(),
+            body: request.body.clone(),
+            credential_id: request.credential_id,
+            assertions: request.assertions.clone(),
+            captures: request.captures.clone(),
+        }
+    }
+}
+
+impl From<&CapturedRequest> for RequestDraft {
+    fn from(request: &CapturedRequest) -> Self {
+        Self {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: request.headers.clone(),
+            body: request.body.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&HttpRequest> for RequestDraft {
+    fn from(request: &HttpRequest) -> Self {
+        let options = request.options.clone();
+        Self {
+            method: options
+                .as_ref()
+                .and_then(|options| options.method.clone())
+                .unwrap_or_else(|| "GET".to_string()),
+            url: request.url.clone(),
+            headers: options
+                .as_ref()
+                .and_then(|options| options.headers.clone())
+                .unwrap_or_default(),
+            credential_id: options.as_ref().and_then(|options| options.credential_id),
+            body: options.and_then(|options| options.body),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&RequestDraft> for HttpRequest {
+    fn from(draft: &RequestDraft) -> Self {
+        Self {
+            url: draft.url.clone(),
+            options: Some(FetchOptions {
+                method: Some(draft.method.clone()).filter(|method| !method.is_empty()),
+                headers: Some(draft.headers.clone()).filter(|headers| !headers.is_empty()),
+                body: draft.body.clone(),
+                max_size: None,
+                credential_id: draft.credential_id,
+            }),
+            request_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Replay {
+    Log(i64),
+    Saved(i64),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SidebarTab {
+    History,
+    Collections,
+    Captured,
+}
+
+#[component]
+pub fn RequestSidebar(
+    #[props(default)] class: String,
+    refresh: ReadSignal<u32>,
+    draft: ReadSignal<RequestDraft>,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+) -> Element {
+    let mut tab = use_signal(|| SidebarTab::History);
+
+    rsx! {
+        div { class: "flex flex-col gap-2 {class}",
+            div { role: "tablist", class: "tabs tabs-box tabs-sm",
+                a {
+                    role: "tab",
+                    class: if tab() == SidebarTab::History { "tab tab-active" } else { "tab" },
+                    onclick: move |_| tab.set(SidebarTab::History),
+                    "历史"
+                }
+                a {
+                    role: "tab",
+                    class: if tab() == SidebarTab::Collections { "tab tab-active" } else { "tab" },
+                    onclick: move |_| tab.set(SidebarTab::Collections),
+                    "集合"
+                }
+                a {
+                    role: "tab",
+                    class: if tab() == SidebarTab::Captured { "tab tab-active" } else { "tab" },
+                    onclick: move |_| tab.set(SidebarTab::Captured),
+                    "抓取"
+                }
+            }
+            match tab() {
+                SidebarTab::History => rsx! {
+                    HistoryList { refresh, onload, onreplay }
+                },
+                SidebarTab::Collections => rsx! {
+                    CollectionTree { draft, onload, onreplay }
+                },
+                SidebarTab::Captured => rsx! {
+                    CaptureList { onload }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn HistoryList(
+    refresh: ReadSignal<u32>,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+) -> Element {
+    let mut keyword = use_signal(String::new);
+    let mut page_token = use_signal(PageToken::default);
+    let mut next_page_token = use_signal(|| None);
+    let mut logs = use_signal(Vec::<RequestLog>::new);
+
+    use_effect(move || {
+        // 输入关键字或有新请求时，重置页码
+        let _ = (keyword.read(), refresh.read());
+        page_token.set(PageToken::default());
+        next_page_token.set(None);
+    });
+
+    use_resource(move || async move {
+        let Ok(response) = query_request_log(keyword(), page_token()).await else {
+            return;
+        };
+
+        if page_token() == PageToken::default() {
+            logs.write().clear();
+        }
+
+        next_page_token.set(response.next_page_token);
+        logs.write().extend(response.logs);
+    });
+
+    rsx! {
+        div { class: "flex gap-1 items-center",
+            input {
+                value: keyword,
+                r#type: "text",
+                class: "input input-sm w-full",
+                placeholder: "搜索请求",
+                oninput: move |e| keyword.set(e.value()),
+            }
+            HarMenu { source: HarSource::History }
+        }
+        ul { class: "list overflow-y-auto",
+            for log in logs() {
+                HistoryItem {
+                    key: "{log.id}",
+                    log,
+                    onload,
+                    onreplay,
+                    ondelete: move |id| async move {
+                        if delete_request_log(id).await.is_ok() {
+                            logs.write().retain(|log| log.id != id);
+                        }
+                    },
+                }
+            }
+            if let Some(token) = next_page_token() {
+                li {
+                    button {
+                        class: "btn btn-ghost btn-xs w-full",
+                        onclick: move |_| page_token.set(token.clone()),
+                        "加载更多"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn HistoryItem(
+    log: RequestLog,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+    ondelete: EventHandler<i64>,
+) -> Element {
+    let id = log.id;
+    let draft = RequestDraft::from(&log);
+    let time = log
+        .create_time
+        .format(format_description!("[month]-[day] [hour]:[minute]:[second]"))
+        .unwrap_or_default();
+    let result = match (log.status, &log.error) {
+        (Some(status), _) => format!("{status} · {}ms", log.elapsed_time.unwrap_or_default()),
+        (None, Some(error)) => error.clone(),
+        (None, None) => String::new(),
+    };
+
+    rsx! {
+        li {
+            class: "list-row items-center p-2 cursor-pointer",
+            onclick: move |_| onload.call(draft.clone()),
+            MethodBadge { method: log.method.clone() }
+            div { class: "list-col-grow min-w-0",
+                div { class: "truncate text-sm", title: "{log.url}", "{log.url}" }
+                div { class: "truncate text-xs opacity-60", "{time} {result}" }
+            }
+            ItemActions {
+                onreplay: move |_| onreplay.call(Replay::Log(id)),
+                ondelete: move |_| ondelete.call(id),
+            }
+        }
+    }
+}
+
+#[component]
+fn CollectionTree(
+    draft: ReadSignal<RequestDraft>,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+) -> Element {
+    let mut name = use_signal(String::new);
+    let mut collections =
+        use_resource(|| async { query_collections().await.unwrap_or_default() });
+    let all = collections().unwrap_or_default();
+    let roots: Vec<i64> = all
+        .iter()
+        .filter(|collection| collection.parent_id.is_none())
+        .map(|collection| collection.id)
+        .collect();
+
+    rsx! {
+        div { class: "join w-full",
+            input {
+                value: name,
+                r#type: "text",
+                class: "input input-sm join-item w-full",
+                placeholder: "名称",
+                oninput: move |e| name.set(e.value()),
+            }
+            button {
+                class: "btn btn-sm join-item",
+                disabled: name().is_empty(),
+                onclick: move |_| async move {
+                    if save_collection(None, name()).await.is_ok() {
+                        name.set(String::new());
+                        collections.restart();
+                    }
+                },
+                "新建集合"
+            }
+        }
+        ul { class: "menu menu-sm w-full overflow-y-auto",
+            for id in roots {
+                CollectionNode {
+                    key: "{id}",
+                    id,
+                    collections: all.clone(),
+                    name,
+                    draft,
+                    onload,
+                    onreplay,
+                    onchange: move |_| collections.restart(),
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn CollectionNode(
+    id: i64,
+    collections: Vec<Collection>,
+    name: Signal<String>,
+    draft: ReadSignal<RequestDraft>,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+    onchange: EventHandler<()>,
+) -> Element {
+    let Some(collection) = collections.iter().find(|c| c.id == id).cloned() else {
+        return rsx! {};
+    };
+    let children: Vec<i64> = collections
+        .iter()
+        .filter(|c| c.parent_id == Some(id))
+        .map(|c| c.id)
+        .collect();
+
+    let add_folder = move |_| async move {
+        if !name().is_empty() && save_collection(Some(id), name()).await.is_ok() {
+            name.set(String::new());
+            onchange.call(());
+        }
+    };
+    let save_draft = move |_| async move {
+        let RequestDraft {
+            method,
+            url,
+            headers,
+            body,
+            credential_id,
+            assertions,
+            captures,
+        } = draft();
+        // 未填写名称时以 URL 命名
+        let request_name = if name().is_empty() { url.clone() } else { name() };
+        let request = SavedRequest {
+            id: None,
+            collection_id: id,
+            name: request_name,
+            method: if method.is_empty() { "GET".to_string() } else { method },
+            url,
+            headers,
+            body,
+            credential_id,
+            assertions,
+            captures,
+            update_time: None,
+        };
+        if save_request(request).await.is_ok() {
+            name.set(String::new());
+            onchange.call(());
+        }
+    };
+    let delete = move |_| async move {
+        if delete_collection(id).await.is_ok() {
+            onchange.call(());
+        }
+    };
+
+    rsx! {
+        li {
+            details { open: true,
+                summary { class: "group",
+                    span { class: "truncate", "{collection.name}" }
+                    span { class: "ml-auto hidden group-hover:flex gap-1",
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            title: "新建文件夹",
+                            onclick: add_folder,
+                            "📁"
+                        }
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            title: "保存当前请求",
+                            onclick: save_draft,
+                            "💾"
+                        }
+                        RunCollectionMenu { id }
+                        HarMenu {
+                            source: HarSource::Collection { id },
+                            collection_id: Some(id),
+                            onimport: onchange,
+                        }
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            title: "删除",
+                            onclick: delete,
+                            "✕"
+                        }
+                    }
+                }
+                ul {
+                    for child in children {
+                        CollectionNode {
+                            key: "{child}",
+                            id: child,
+                            collections: collections.clone(),
+                            name,
+                            draft,
+                            onload,
+                            onreplay,
+                            onchange,
+                        }
+                    }
+                    for request in collection.requests {
+                        SavedRequestItem {
+                            key: "{request.id.unwrap_or_default()}",
+                            request,
+                            onload,
+                            onreplay,
+                            onchange,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SavedRequestItem(
+    request: SavedRequest,
+    onload: EventHandler<RequestDraft>,
+    onreplay: EventHandler<Replay>,
+    onchange: EventHandler<()>,
+) -> Element {
+    let id = request.id.unwrap_or_default();
+    let draft = RequestDraft::from(&request);
+
+    rsx! {
+        li {
+            a { class: "flex items-center", onclick: move |_| onload.call(draft.clone()),
+                MethodBadge { method: request.method.clone() }
+                span { class: "truncate", title: "{request.url}", "{request.name}" }
+                ItemActions {
+                    onreplay: move |_| onreplay.call(Replay::Saved(id)),
+                    ondelete: move |_| async move {
+                        if delete_saved_request(id).await.is_ok() {
+                            onchange.call(());
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ItemActions(onreplay: EventHandler<()>, ondelete: EventHandler<()>) -> Element {
+    rsx! {
+        span { class: "ml-auto flex gap-1",
+            button {
+                class: "btn btn-ghost btn-xs",
+                title: "重新发送",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    onreplay.call(());
+                },
+                "▶"
+            }
+            button {
+                class: "btn btn-ghost btn-xs",
+                title: "删除",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    ondelete.call(());
+                },
+                "✕"
+            }
+        }
+    }
+}
+
+#[component]
+pub fn MethodBadge(method: String) -> Element {
+    let color = match method.as_str() {
+        "GET" => "badge-success",
+        "POST" => "badge-warning",
+        "PUT" | "PATCH" => "badge-info",
+        "DELETE" => "badge-error",
+        _ => "badge-neutral",
+    };
+
+    rsx! {
+        span { class: "badge badge-xs font-mono {color}", "{method}" }
+    }
+}
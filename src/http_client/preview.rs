@@ -0,0 +1,354 @@
+use mime::Mime;
+use serde_json::Value;
+
+/// 不换行缩进的 HTML 空元素
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track",
+];
+/// 内容按原样保留的元素
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "pre", "textarea"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyKind {
+    Json,
+    Markup,
+    Text,
+    Image,
+    Binary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyView {
+    Tree,
+    Pretty,
+    Raw,
+    Image,
+    Hex,
+}
+
+impl BodyKind {
+    pub fn of(content_type: Option<&Mime>, body: &[u8]) -> Self {
+        let Some(content_type) = content_type else {
+            // 未声明类型时按内容猜测
+            return match std::str::from_utf8(body) {
+                Ok(text) if serde_json::from_str::<Value>(text).is_ok() => BodyKind::Json,
+                Ok(text) if text.trim_start().starts_with('<') => BodyKind::Markup,
+                Ok(_) => BodyKind::Text,
+                Err(_) => BodyKind::Binary,
+            };
+        };
+
+        let base = content_type.type_();
+        let sub = content_type.subtype().as_str();
+        let suffix = content_type.suffix().map(|suffix| suffix.as_str());
+        if base == mime::IMAGE {
+            BodyKind::Image
+        } else if sub == "json" || suffix == Some("json") {
+            BodyKind::Json
+        } else if sub.contains("xml") || sub == "html" || suffix == Some("xml") {
+            BodyKind::Markup
+        } else if base == mime::TEXT
+            || matches!(
+                sub,
+                "x-www-form-urlencoded" | "markdown" | "rtf" | "javascript"
+            )
+        {
+            BodyKind::Text
+        } else {
+            BodyKind::Binary
+        }
+    }
+
+    /// 可切换的视图，第一个为默认视图
+    pub fn views(self) -> &'static [(BodyView, &'static str)] {
+        match self {
+            BodyKind::Json => &[
+                (BodyView::Tree, "树"),
+                (BodyView::Pretty, "格式化"),
+                (BodyView::Raw, "原始"),
+            ],
+            BodyKind::Markup => &[(BodyView::Pretty, "格式化"), (BodyView::Raw, "原始")],
+            BodyKind::Text => &[(BodyView::Raw, "文本"), (BodyView::Hex, "十六进制")],
+            BodyKind::Image => &[(BodyView::Image, "预览"), (BodyView::Hex, "十六进制")],
+            BodyKind::Binary => &[(BodyView::Hex, "十六进制"), (BodyView::Raw, "文本")],
+        }
+    }
+}
+
+/// 按标签缩进 XML/HTML，元素只含短文本时保持在同一行
+pub fn pretty_markup(source: &str) -> String {
+    let mut lines = Vec::new();
+    let mut depth = 0;
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            push_line(&mut lines, depth, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|i| i + 3)
+        } else if rest.starts_with("<![CDATA[") {
+            rest.find("]]>").map(|i| i + 3)
+        } else {
+            rest.find('>').map(|i| i + 1)
+        };
+        let Some(end) = end else {
+            push_line(&mut lines, depth, rest);
+            break;
+        };
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        if tag.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            push_line(&mut lines, depth, tag);
+            continue;
+        }
+
+        let name = tag_name(tag);
+        if tag.starts_with("<?")
+            || tag.starts_with("<!")
+            || tag.ends_with("/>")
+            || VOID_ELEMENTS.contains(&name.as_str())
+        {
+            push_line(&mut lines, depth, tag);
+            continue;
+        }
+
+        let close = format!("</{name}");
+        let text_end = if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            find_ignore_case(rest, &close).unwrap_or(rest.len())
+        } else {
+            rest.find('<').unwrap_or(rest.len())
+        };
+        let text = rest[..text_end].trim();
+        let after = &rest[text_end..];
+        if find_ignore_case(after, &close) == Some(0)
+            && let Some(close_end) = after.find('>')
+            && !text.contains('\n')
+        {
+            // 短元素合并为一行
+            push_line(
+                &mut lines,
+                depth,
+                &format!("{tag}{text}{}", &after[..=close_end]),
+            );
+            rest = &after[close_end + 1..];
+        } else {
+            push_line(&mut lines, depth, tag);
+            depth += 1;
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                push_line(&mut lines, depth, text);
+                rest = after;
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn push_line(lines: &mut Vec<String>, depth: usize, text: &str) {
+    let text = text.trim();
+    if !text.is_empty() {
+        lines.push(format!("{}{text}", "  ".repeat(depth)));
+    }
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn find_ignore_case(text: &str, pattern: &str) -> Option<usize> {
+    text.to_ascii_lowercase()
+        .find(&pattern.to_ascii_lowercase())
+}
+
+/// 每行 16 字节：偏移、十六进制、可打印字符
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {:<47}  |{ascii}|", i * 16, hex.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 按关键字切分文本，返回片段及是否匹配，忽略 ASCII 大小写
+pub fn split_matches<'a>(text: &'a str, keyword: &str) -> Vec<(&'a str, bool)> {
+    if keyword.is_empty() {
+        return vec![(text, false)];
+    }
+
+    // ASCII 小写不改变字节位置
+    let lower = text.to_ascii_lowercase();
+    let keyword = keyword.to_ascii_lowercase();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (i, _) in lower.match_indices(&keyword) {
+        if i > start {
+            segments.push((&text[start..i], false));
+        }
+        segments.push((&text[i..i + keyword.len()], true));
+        start = i + keyword.len();
+    }
+    if start < text.len() {
+        segments.push((&text[start..], false));
+    }
+    segments
+}
+
+/// 截取前 `max` 字节用于显示，不切断 UTF-8 字符
+pub fn truncate(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// 保存响应体时的默认文件名，优先使用 Content-Disposition
+pub fn file_name(content_disposition: Option<&str>, content_type: Option<&Mime>) -> String {
+    let disposition = content_disposition.and_then(|disposition| {
+        disposition.split(';').find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            (key.trim().eq_ignore_ascii_case("filename"))
+                .then(|| value.trim().trim_matches('"').to_string())
+                .filter(|name| !name.is_empty())
+        })
+    });
+    if let Some(name) = disposition {
+        return name;
+    }
+
+    let extension = content_type.map_or("bin", |content_type| {
+        match (
+            content_type.subtype().as_str(),
+            content_type.suffix().map(|suffix| suffix.as_str()),
+        ) {
+            (_, Some("json")) | ("json", _) => "json",
+            (_, Some("xml")) | ("xml", _) => "xml",
+            ("html", _) => "html",
+            ("plain", _) => "txt",
+            ("javascript", _) => "js",
+            ("css", _) => "css",
+            ("csv", _) => "csv",
+            ("png", _) => "png",
+            ("jpeg", _) => "jpg",
+            ("gif", _) => "gif",
+            ("webp", _) => "webp",
+            ("pdf", _) => "pdf",
+            ("zip", _) => "zip",
+            _ => "bin",
+        }
+    });
+    format!("response.{extension}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_kind() {
+        let mime = |s: &str| s.parse::<Mime>().unwrap();
+        assert_eq!(
+            BodyKind::of(Some(&mime("application/vnd.api+json")), b"{}"),
+            BodyKind::Json
+        );
+        assert_eq!(
+            BodyKind::of(Some(&mime("text/html; charset=utf-8")), b""),
+            BodyKind::Markup
+        );
+        assert_eq!(BodyKind::of(Some(&mime("image/png")), b""), BodyKind::Image);
+        assert_eq!(BodyKind::of(None, br#"{"a":1}"#), BodyKind::Json);
+        assert_eq!(BodyKind::of(None, &[0xff, 0x00]), BodyKind::Binary);
+    }
+
+    #[test]
+    fn test_pretty_markup() {
+        let html = "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>白洞</title>\
+            <script>if (a < b) { run(); }</script></head><body><p>hi<br/>there</p></body></html>";
+        assert_eq!(
+            pretty_markup(html),
+            [
+                "<!DOCTYPE html>",
+                "<html>",
+                "  <head>",
+                "    <meta charset=\"utf-8\">",
+                "    <title>白洞</title>",
+                "    <script>if (a < b) { run(); }</script>",
+                "  </head>",
+                "  <body>",
+                "    <p>",
+                "      hi",
+                "      <br/>",
+                "      there",
+                "    </p>",
+                "  </body>",
+                "</html>",
+            ]
+            .join("\n")
+        );
+
+        let xml = "<?xml version=\"1.0\"?><a><!-- note --><b id=\"1\">x</b><c/></a>";
+        assert_eq!(
+            pretty_markup(xml),
+            "<?xml version=\"1.0\"?>\n<a>\n  <!-- note -->\n  <b id=\"1\">x</b>\n  <c/>\n</a>"
+        );
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        assert_eq!(
+            hex_dump(b"white hole\x00\x01\xff~abcdefg"),
+            "00000000  77 68 69 74 65 20 68 6f 6c 65 00 01 ff 7e 61 62  |white hole...~ab|\n\
+             00000010  63 64 65 66 67                                   |cdefg|"
+        );
+    }
+
+    #[test]
+    fn test_split_matches() {
+        assert_eq!(
+            split_matches("Token: 白洞 token", "TOKEN"),
+            [("Token", true), (": 白洞 ", false), ("token", true)]
+        );
+        assert_eq!(split_matches("abc", ""), [("abc", false)]);
+        assert_eq!(truncate("白洞", 4), "白");
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name(Some("attachment; filename=\"report.pdf\""), None),
+            "report.pdf"
+        );
+        let json: Mime = "application/problem+json".parse().unwrap();
+        assert_eq!(file_name(None, Some(&json)), "response.json");
+        assert_eq!(file_name(None, None), "response.bin");
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, str::FromStr, string::FromUtf8Error};
 
-use crate::api::{HttpHeader, HttpResponse};
+use crate::api::{HttpHeader, HttpResponse, save_response_body};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
 use dioxus::{logger::tracing::error, prelude::*};
 use encoding::{
     DecoderTrap, Encoding,
@@ -11,6 +12,16 @@ use mime::{CHARSET, Mime};
 use serde_json::Value;
 use time::{OffsetDateTime, macros::format_description};
 
+use super::{
+    json_tree::JsonTree,
+    preview::{BodyKind, BodyView, file_name, hex_dump, pretty_markup, split_matches, truncate},
+};
+
+/// 超过该字节数的文本只显示开头部分，完整内容仍可保存
+const MAX_DISPLAY_SIZE: usize = 512 * 1024;
+/// 十六进制视图默认显示的字节数
+const MAX_HEX_SIZE: usize = 64 * 1024;
+
 #[component]
 pub fn ResponseView(resp: ReadSignal<HttpResponse>) -> Element {
     let HttpResponse {
@@ -34,49 +45,202 @@ pub fn ResponseView(resp: ReadSignal<HttpResponse>) -> Element {
         .transpose()
         .unwrap_or(None);
 
+    let file_name = file_name(
+        header_map.get("content-disposition").map(String::as_str),
+        content_type.as_ref(),
+    );
+
     rsx! {
         Stat { status, elapsed_time, done_date }
         div { class: "divider h-0" }
         Header { header: header_map }
         div { class: "divider h-0" }
-        Body { content_type, body }
+        Body { content_type, body, file_name, done_date }
     }
 }
 
 #[component]
-fn Body(body: Vec<u8>, content_type: Option<Mime>) -> Element {
-    let body = if let Some(content_type) = content_type {
-        let base = content_type.type_();
-        let sub = content_type.subtype().as_str();
-        if "text" == base
-            || matches!(sub, "json" | "x-www-form-urlencoded" | "markdown" | "rtf")
-            || sub.contains("xml")
-        {
-            let body = if "json" == sub {
-                serde_json::from_slice::<Value>(&body)
-                    .and_then(|body| serde_json::to_vec_pretty(&body))
-                    .unwrap_or(body)
-            } else {
-                body
-            };
+fn Body(
+    body: Vec<u8>,
+    content_type: Option<Mime>,
+    file_name: String,
+    done_date: OffsetDateTime,
+) -> Element {
+    let kind = BodyKind::of(content_type.as_ref(), &body);
+    let views = kind.views();
+    let mut selected = use_signal(|| views[0].0);
+    // 新响应的类型不同时回到默认视图
+    let view = if views.iter().any(|(view, _)| *view == selected()) {
+        selected()
+    } else {
+        views[0].0
+    };
+    let mut keyword = use_signal(String::new);
+    // 记录点击“显示全部”时的响应，新响应重新截断
+    let mut full_response = use_signal(|| None);
+    let full = move || full_response() == Some(done_date);
 
+    let text = match view {
+        BodyView::Tree | BodyView::Image => String::new(),
+        BodyView::Hex => {
+            let limit = if full() { body.len() } else { MAX_HEX_SIZE };
+            hex_dump(&body[..body.len().min(limit)])
+        }
+        BodyView::Pretty | BodyView::Raw => {
             let charset = content_type
-                .get_param(CHARSET)
+                .as_ref()
+                .and_then(|content_type| content_type.get_param(CHARSET))
                 .unwrap_or(mime::UTF_8)
                 .as_str();
-
-            decode(body, charset)?
-        } else {
-            format!("{body:?}")
+            let text = decode(body.clone(), charset)
+                .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+            match (view, kind) {
+                (BodyView::Pretty, BodyKind::Json) => serde_json::from_str::<Value>(&text)
+                    .and_then(|json| serde_json::to_string_pretty(&json))
+                    .unwrap_or(text),
+                (BodyView::Pretty, BodyKind::Markup) => pretty_markup(&text),
+                _ => text,
+            }
         }
+    };
+    let shown = if full() {
+        text.as_str()
     } else {
-        decode(body, "utf-8")?
+        truncate(&text, MAX_DISPLAY_SIZE)
     };
+    let truncated = !full()
+        && ((view == BodyView::Hex && body.len() > MAX_HEX_SIZE) || shown.len() < text.len());
+
+    rsx! {
+        div { class: "flex items-center gap-2 mb-2",
+            div { role: "tablist", class: "tabs tabs-box tabs-xs",
+                for (tab , label) in views.iter().copied() {
+                    a {
+                        role: "tab",
+                        class: if tab == view { "tab tab-active" } else { "tab" },
+                        onclick: move |_| selected.set(tab),
+                        "{label}"
+                    }
+                }
+            }
+            if !text.is_empty() {
+                input {
+                    value: keyword,
+                    r#type: "search",
+                    class: "input input-xs w-40",
+                    placeholder: "搜索",
+                    oninput: move |e| keyword.set(e.value()),
+                }
+            }
+            SaveBody { class: "ml-auto", file_name, body: body.clone() }
+        }
+        if truncated {
+            div { role: "alert", class: "alert alert-warning alert-soft py-1 mb-2 text-xs",
+                span { "响应体共 {body.len()} 字节，只显示开头部分" }
+                button {
+                    class: "btn btn-ghost btn-xs",
+                    onclick: move |_| full_response.set(Some(done_date)),
+                    "显示全部"
+                }
+            }
+        }
+        match view {
+            BodyView::Tree => match serde_json::from_slice::<Value>(&body) {
+                Ok(value) => rsx! {
+                    JsonTree { value }
+                },
+                Err(e) => rsx! {
+                    div { class: "text-xs text-error", "JSON 解析失败：{e}" }
+                },
+            },
+            BodyView::Image => {
+                let mime = content_type
+                    .as_ref()
+                    .map(|content_type| content_type.essence_str().to_string())
+                    .unwrap_or_default();
+                let data = STANDARD.encode(&body);
+                rsx! {
+                    img { class: "max-w-full", src: "data:{mime};base64,{data}" }
+                }
+            }
+            _ => rsx! {
+                TextView { text: shown.to_string(), keyword: keyword() }
+            },
+        }
+    }
+}
+
+#[component]
+fn TextView(text: String, keyword: String) -> Element {
+    let segments = split_matches(&text, &keyword);
+    let count = segments.iter().filter(|(_, matched)| *matched).count();
 
-    // TODO raw body
     rsx! {
+        if !keyword.is_empty() {
+            div { class: "text-xs opacity-60", "找到 {count} 处" }
+        }
         pre { class: "p-4 rounded-md w-full overflow-x-auto",
-            code { {body} }
+            code {
+                for (segment , matched) in segments {
+                    if matched {
+                        mark { "{segment}" }
+                    } else {
+                        "{segment}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 完整响应体保存到下载目录，不受显示截断影响
+#[component]
+fn SaveBody(#[props(default)] class: String, file_name: String, body: Vec<u8>) -> Element {
+    let mut name = use_signal(|| file_name.clone());
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    use_effect(use_reactive!(|file_name| {
+        name.set(file_name);
+        result.set(None);
+    }));
+
+    rsx! {
+        div { class: "dropdown dropdown-end {class}",
+            div { tabindex: "0", role: "button", class: "btn btn-ghost btn-xs", "💾 保存" }
+            div {
+                tabindex: "0",
+                class: "dropdown-content z-[1] bg-base-100 rounded-box shadow w-80 p-2 flex flex-col gap-1",
+                div { class: "join w-full",
+                    input {
+                        value: name,
+                        r#type: "text",
+                        class: "input input-sm join-item w-full",
+                        placeholder: "文件名",
+                        oninput: move |e| name.set(e.value()),
+                    }
+                    button {
+                        class: "btn btn-sm join-item",
+                        disabled: name().is_empty(),
+                        onclick: move |_| {
+                            let body = body.clone();
+                            async move {
+                                let saved = save_response_body(name(), body).await;
+                                result.set(Some(saved.map_err(|e| e.to_string())));
+                            }
+                        },
+                        "保存到下载目录"
+                    }
+                }
+                match result() {
+                    Some(Ok(path)) => rsx! {
+                        div { class: "text-xs text-success break-all", "已保存：{path}" }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-xs text-error", "{e}" }
+                    },
+                    None => rsx! {},
+                }
+            }
         }
     }
 }
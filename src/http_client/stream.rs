@@ -0,0 +1,344 @@
+use dioxus::prelude::*;
+use futures_util::{
+    StreamExt as _,
+    future::{self, Either},
+};
+use tauri_sys::core::Channel;
+
+use super::{log::RequestDraft, preview::hex_dump};
+use crate::api::{
+    HttpRequest, SseEvent, WebSocketEvent, WebSocketMessage, cancel_fetch, close_websocket,
+    connect_websocket, send_websocket, subscribe_sse,
+};
+
+/// 日志最多保留的消息条数，超出时丢弃最早的消息
+const MAX_LOG_SIZE: usize = 1000;
+const NORMAL_CLOSURE: u16 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClientMode {
+    Http,
+    WebSocket,
+    Sse,
+}
+
+impl ClientMode {
+    pub const ALL: [(ClientMode, &'static str); 3] = [
+        (ClientMode::Http, "HTTP"),
+        (ClientMode::WebSocket, "WebSocket"),
+        (ClientMode::Sse, "SSE"),
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Sent,
+    Received,
+    System,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LogEntry {
+    direction: Direction,
+    title: String,
+    content: String,
+}
+
+impl LogEntry {
+    fn system(content: impl Into<String>) -> Self {
+        Self {
+            direction: Direction::System,
+            title: String::new(),
+            content: content.into(),
+        }
+    }
+
+    fn message(direction: Direction, message: &WebSocketMessage) -> Self {
+        let (title, content) = match message {
+            WebSocketMessage::Text(text) => ("文本".to_string(), text.clone()),
+            WebSocketMessage::Binary(data) => {
+                (format!("二进制 {} 字节", data.len()), hex_dump(data))
+            }
+        };
+        Self {
+            direction,
+            title,
+            content,
+        }
+    }
+}
+
+/// 解析以空白分隔或连续的十六进制字节，如 `01 ff` 或 `01ff`
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text.split_whitespace().collect();
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn push_log(mut log: Signal<Vec<LogEntry>>, entry: LogEntry) {
+    let mut log = log.write();
+    if log.len() >= MAX_LOG_SIZE {
+        log.remove(0);
+    }
+    log.push(entry);
+}
+
+/// WebSocket 客户端，使用编辑器中的 URL、请求头和认证凭据握手
+#[component]
+pub fn WebSocketPanel(draft: ReadSignal<RequestDraft>) -> Element {
+    let log = use_signal(Vec::<LogEntry>::new);
+    let mut connection = use_signal(|| None::<String>);
+    let mut seq = use_signal(|| 0u32);
+    let mut input = use_signal(String::new);
+    let mut binary = use_signal(|| false);
+    let mut close_code = use_signal(|| NORMAL_CLOSURE.to_string());
+    let mut close_reason = use_signal(String::new);
+
+    let connect = move |_| async move {
+        seq += 1;
+        let connection_id = format!("websocket-{}", seq());
+        let request = HttpRequest::from(&draft());
+        let mut channel = Channel::<WebSocketEvent>::new();
+        let done = connect_websocket(
+            request.url.clone(),
+            request.options,
+            connection_id.clone(),
+            &channel,
+        );
+
+        push_log(log, LogEntry::system(format!("正在连接 {}", request.url)));
+        connection.set(Some(connection_id));
+        let receive = async move {
+            while let Some(event) = channel.next().await {
+                let entry = match event {
+                    WebSocketEvent::Open { status, .. } => {
+                        LogEntry::system(format!("已连接，状态码 {status}"))
+                    }
+                    WebSocketEvent::Message(message) => {
+                        LogEntry::message(Direction::Received, &message)
+                    }
+                    WebSocketEvent::Close { code, reason } => {
+                        LogEntry::system(format!("连接关闭：{code} {reason}"))
+                    }
+                };
+                push_log(log, entry);
+            }
+        };
+        // 后端在连接关闭后返回，通道不会结束
+        if let Either::Left((Err(e), _)) = future::select(Box::pin(done), Box::pin(receive)).await {
+            push_log(log, LogEntry::system(e.to_string()));
+        }
+        connection.set(None);
+    };
+
+    let send = move |_| async move {
+        let Some(connection_id) = connection() else {
+            return;
+        };
+        let message = if binary() {
+            let Some(data) = parse_hex(&input()) else {
+                push_log(log, LogEntry::system("十六进制格式错误"));
+                return;
+            };
+            WebSocketMessage::Binary(data)
+        } else {
+            WebSocketMessage::Text(input())
+        };
+        match send_websocket(connection_id, message.clone()).await {
+            Ok(()) => push_log(log, LogEntry::message(Direction::Sent, &message)),
+            Err(e) => push_log(log, LogEntry::system(e.to_string())),
+        }
+    };
+
+    let close = move |_| async move {
+        let Some(connection_id) = connection() else {
+            return;
+        };
+        let code = close_code().trim().parse().ok();
+        let reason = Some(close_reason()).filter(|reason| !reason.is_empty());
+        if let Err(e) = close_websocket(connection_id, code, reason).await {
+            push_log(log, LogEntry::system(e.to_string()));
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-2 h-full",
+            div { class: "join w-full",
+                if connection().is_some() {
+                    input {
+                        value: close_code,
+                        r#type: "number",
+                        class: "input input-sm join-item w-24",
+                        title: "关闭码",
+                        oninput: move |e| close_code.set(e.value()),
+                    }
+                    input {
+                        value: close_reason,
+                        r#type: "text",
+                        class: "input input-sm join-item w-full",
+                        placeholder: "关闭原因",
+                        oninput: move |e| close_reason.set(e.value()),
+                    }
+                    button { class: "btn btn-sm btn-warning join-item", onclick: close, "断开" }
+                } else {
+                    button {
+                        class: "btn btn-sm btn-primary join-item w-full",
+                        disabled: draft.read().url.is_empty(),
+                        onclick: connect,
+                        "连接"
+                    }
+                }
+            }
+            textarea {
+                value: input,
+                class: "textarea textarea-sm w-full font-mono",
+                placeholder: if binary() { "十六进制字节，如 01 ff" } else { "消息内容" },
+                oninput: move |e| input.set(e.value()),
+            }
+            div { class: "flex gap-2 items-center",
+                label { class: "label text-sm",
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm",
+                        checked: binary(),
+                        onchange: move |e| binary.set(e.checked()),
+                    }
+                    "二进制"
+                }
+                button {
+                    class: "btn btn-sm ml-auto",
+                    disabled: connection().is_none(),
+                    onclick: send,
+                    "发送"
+                }
+            }
+            MessageLog { log }
+        }
+    }
+}
+
+/// SSE 订阅，使用编辑器中的 URL、请求头和认证凭据
+#[component]
+pub fn SsePanel(draft: ReadSignal<RequestDraft>) -> Element {
+    let log = use_signal(Vec::<LogEntry>::new);
+    let mut subscription = use_signal(|| None::<String>);
+    let mut seq = use_signal(|| 0u32);
+
+    let subscribe = move |_| async move {
+        seq += 1;
+        let request_id = format!("sse-{}", seq());
+        let request = HttpRequest::from(&draft());
+        let mut channel = Channel::<SseEvent>::new();
+        let done = subscribe_sse(
+            request.url.clone(),
+            request.options,
+            request_id.clone(),
+            &channel,
+        );
+
+        push_log(log, LogEntry::system(format!("正在订阅 {}", request.url)));
+        subscription.set(Some(request_id));
+        let receive = async move {
+            while let Some(event) = channel.next().await {
+                let entry = match event {
+                    SseEvent::Open { status, .. } => {
+                        LogEntry::system(format!("已订阅，状态码 {status}"))
+                    }
+                    SseEvent::Message { id, r#type, data } => LogEntry {
+                        direction: Direction::Received,
+                        title: match id {
+                            Some(id) => format!("{} #{id}", r#type),
+                            None => r#type,
+                        },
+                        content: data,
+                    },
+                };
+                push_log(log, entry);
+            }
+        };
+        match future::select(Box::pin(done), Box::pin(receive)).await {
+            Either::Left((Ok(()), _)) => push_log(log, LogEntry::system("服务端已关闭连接")),
+            Either::Left((Err(e), _)) => push_log(log, LogEntry::system(e.to_string())),
+            Either::Right(_) => {}
+        }
+        subscription.set(None);
+    };
+
+    let unsubscribe = move |_| async move {
+        if let Some(request_id) = subscription() {
+            let _ = cancel_fetch(request_id).await;
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-2 h-full",
+            if subscription().is_some() {
+                button { class: "btn btn-sm btn-warning w-full", onclick: unsubscribe, "取消订阅" }
+            } else {
+                button {
+                    class: "btn btn-sm btn-primary w-full",
+                    disabled: draft.read().url.is_empty(),
+                    onclick: subscribe,
+                    "订阅"
+                }
+            }
+            MessageLog { log }
+        }
+    }
+}
+
+#[component]
+fn MessageLog(log: Signal<Vec<LogEntry>>) -> Element {
+    rsx! {
+        div { class: "flex items-center",
+            span { class: "text-xs opacity-60", "共 {log.read().len()} 条" }
+            button {
+                class: "btn btn-ghost btn-xs ml-auto",
+                onclick: move |_| log.write().clear(),
+                "清空"
+            }
+        }
+        ul { class: "list overflow-y-auto font-mono text-sm",
+            for (i , entry) in log().into_iter().enumerate().rev() {
+                li { key: "{i}", class: "list-row p-2",
+                    match entry.direction {
+                        Direction::Sent => rsx! {
+                            span { class: "badge badge-sm badge-primary", "↑" }
+                        },
+                        Direction::Received => rsx! {
+                            span { class: "badge badge-sm badge-success", "↓" }
+                        },
+                        Direction::System => rsx! {
+                            span { class: "badge badge-sm badge-ghost", "·" }
+                        },
+                    }
+                    div { class: "list-col-grow min-w-0",
+                        if !entry.title.is_empty() {
+                            div { class: "text-xs opacity-60", "{entry.title}" }
+                        }
+                        pre { class: "whitespace-pre-wrap break-all", "{entry.content}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("01 ff\n0A"), Some(vec![0x01, 0xff, 0x0a]));
+        assert_eq!(parse_hex("01ff"), Some(vec![0x01, 0xff]));
+        assert_eq!(parse_hex("1"), None);
+        assert_eq!(parse_hex("zz"), None);
+        assert_eq!(parse_hex(""), Some(Vec::new()));
+    }
+}
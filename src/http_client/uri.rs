@@ -13,8 +13,10 @@ pub fn UriInput(#[props(default)] class: String, value: Signal<String>) -> Eleme
             },
             onfocusout: move |_| {
                 let mut input = value.write();
-                if !input.is_empty() && !input.starts_with("http://")
-                    && !input.starts_with("https://")
+                if !input.is_empty()
+                    && !["http://", "https://", "ws://", "wss://"]
+                        .iter()
+                        .any(|scheme| input.starts_with(scheme))
                 {
                     *input = format!("http://{}", *input);
                 }
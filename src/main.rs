@@ -2,6 +2,7 @@ mod api;
 mod app;
 mod darkreader;
 mod extension;
+mod hotkey;
 mod http_client;
 mod incognito;
 mod navigation;
@@ -10,6 +11,7 @@ mod search_page;
 mod settings;
 mod title_bar;
 mod url;
+mod vim;
 mod window_decoration;
 
 use app::App;
@@ -9,6 +9,7 @@ use crate::{
         update_star,
     },
     app::use_browser,
+    hotkey::{HotkeyMenu, HotkeySettings},
     incognito::Incognito,
     search_input::SearchInput,
     settings::Settings,
@@ -26,16 +27,22 @@ struct FocusedLog {
 #[component]
 pub fn SearchPage() -> Element {
     let mut keyword = use_signal(String::new);
+    let group_by_site = use_signal(|| false);
     let mut page_token = use_signal(PageToken::default);
     let mut next_page_token = use_signal(|| None);
     let mut main_element = use_signal::<Option<Rc<MountedData>>>(|| None);
     let mut logs = use_store(Vec::new);
     let mut focused_log = use_signal::<Option<FocusedLog>>(|| None);
     let mut input_element = use_signal::<Option<Rc<MountedData>>>(|| None);
+    let hotkey_settings = use_signal(|| false);
 
     use_effect(move || {
         // 输入关键字进行检索、切换模式时，重置页码
-        let _ = (keyword.read(), use_browser().incognito.read());
+        let _ = (
+            keyword.read(),
+            group_by_site.read(),
+            use_browser().incognito.read(),
+        );
         page_token.set(PageToken::default());
         next_page_token.set(None);
     });
@@ -50,7 +57,8 @@ pub fn SearchPage() -> Element {
 
     use_resource(move || async move {
         // 检索日志
-        let Ok(response) = query_navigation_log(keyword(), page_token()).await else {
+        let Ok(response) = query_navigation_log(keyword(), group_by_site(), page_token()).await
+        else {
             return;
         };
 
@@ -115,10 +123,16 @@ pub fn SearchPage() -> Element {
                         onenter,
                         onmounted: oninputmounted,
                     }
-                    Settings { class: "join-item", Incognito {} }
+                    Settings { class: "join-item",
+                        Incognito {}
+                        GroupBySite { value: group_by_site }
+                        HotkeyMenu { open: hotkey_settings }
+                    }
                 }
             }
 
+            HotkeySettings { open: hotkey_settings }
+
             main {
                 class: "flex-1 overflow-auto",
                 onmounted: onmainmounted,
@@ -144,6 +158,25 @@ pub fn SearchPage() -> Element {
     }
 }
 
+#[component]
+fn GroupBySite(value: Signal<bool>) -> Element {
+    rsx! {
+        li {
+            label { class: "btn btn-ghost btn-block swap",
+                input {
+                    r#type: "checkbox",
+                    checked: value,
+                    onclick: move |_| value.toggle(),
+                }
+
+                div { class: "swap-on", "🗂️ 按站点分组" }
+
+                div { class: "swap-off", "📄 按页面列出" }
+            }
+        }
+    }
+}
+
 #[component]
 fn LogItem(log: Store<NavigationLog>, onfocus: EventHandler<FocusEvent>) -> Element {
     rsx! {
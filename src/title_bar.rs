@@ -4,7 +4,8 @@ use crate::{
     darkreader::Darkreader,
     extension::Extension,
     navigation::Navigator,
-    url::DecodeUrl,
+    url::{DecodeUrl, DomainUrl},
+    vim::Vim,
     window_decoration::WindowDecoration,
 };
 use dioxus::{html::input_data::MouseButton, prelude::*};
@@ -33,6 +34,7 @@ pub fn TitleBar(#[props(default)] class: String) -> Element {
             div { class: "fixed top-0 right-0 join",
                 Extension { class: "join-item",
                     Darkreader { class: "tab" }
+                    Vim { class: "tab" }
                 }
                 WindowDecoration { class: "join-item" }
             }
@@ -92,9 +94,15 @@ fn Title() -> Element {
 
 #[component]
 fn Url() -> Element {
+    let browser = use_browser();
+
     rsx! {
         div { class: "url text-xs text-blue-300 truncate",
-            DecodeUrl { url: use_browser().url }
+            if let Some(url) = (browser.display_url)() {
+                DomainUrl { url }
+            } else {
+                DecodeUrl { url: browser.url }
+            }
         }
     }
 }
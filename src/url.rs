@@ -2,6 +2,8 @@ use std::{borrow::Cow, collections::VecDeque, slice, str::Utf8Error};
 
 use dioxus::prelude::*;
 
+use crate::api::DisplayUrl;
+
 const KEEP_ENCODED_CHAR: &[u8] = b" \"#$%&'()*+,/:;<=>?@[\\]^`{|}";
 
 #[component]
@@ -11,6 +13,25 @@ pub fn DecodeUrl(url: String) -> Element {
     }
 }
 
+/// 突出显示可注册域名，可疑域名保留 punycode 并给出警告
+#[component]
+pub fn DomainUrl(url: DisplayUrl) -> Element {
+    rsx! {
+        if url.suspicious {
+            span {
+                class: "badge badge-warning badge-xs mr-1",
+                title: "域名包含混合脚本或易混淆字符",
+                "⚠"
+            }
+        }
+        span { class: "opacity-60", "{url.prefix}{url.subdomain}" }
+        span { class: "font-semibold", "{url.domain}" }
+        span { class: "opacity-60",
+            DecodeUrl { url: url.suffix }
+        }
+    }
+}
+
 /// Percent-decode the given string.
 ///
 /// <https://url.spec.whatwg.org/#string-percent-decode>
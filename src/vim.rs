@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+
+use crate::{api::vim, app::use_browser};
+
+const KEYBOARD_PATH: &str = "M20 5H4c-1.1 0-1.99.9-1.99 2L2 17c0 1.1.9 2 2 2h16c1.1 0 2-.9 2-2V7c0-1.1-.9-2-2-2zm-9 3h2v2h-2V8zm0 3h2v2h-2v-2zM8 8h2v2H8V8zm0 3h2v2H8v-2zm-1 2H5v-2h2v2zm0-3H5V8h2v2zm9 7H8v-2h8v2zm0-4h-2v-2h2v2zm0-3h-2V8h2v2zm3 3h-2v-2h2v2zm0-3h-2V8h2v2z";
+
+/// 当前站点的 Vim 风格键盘浏览开关
+#[component]
+pub fn Vim(#[props(default)] class: String) -> Element {
+    rsx! {
+        label { class: "vim swap {class}", title: "Vim 模式",
+            input {
+                tabindex: "-1",
+                r#type: "checkbox",
+                checked: use_browser().vim,
+                onclick: |_| async { vim().await },
+            }
+
+            svg {
+                class: "swap-on fill-current size-6",
+                view_box: "0 0 24 24",
+                xmlns: "http://www.w3.org/2000/svg",
+                path { d: KEYBOARD_PATH }
+            }
+            svg {
+                class: "swap-off fill-current size-6 opacity-40",
+                view_box: "0 0 24 24",
+                xmlns: "http://www.w3.org/2000/svg",
+                path { d: KEYBOARD_PATH }
+            }
+        }
+    }
+}